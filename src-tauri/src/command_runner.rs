@@ -0,0 +1,315 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io::Read,
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use super::LaunchCommand;
+
+const OUTPUT_TAIL_LIMIT_BYTES: usize = 2048;
+const READER_DRAIN_WAIT: Duration = Duration::from_millis(500);
+const TIMED_RUN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Default)]
+pub(crate) struct OutputTail {
+    bytes: VecDeque<u8>,
+}
+
+impl OutputTail {
+    fn push(&mut self, chunk: &[u8]) {
+        self.bytes.extend(chunk.iter().copied());
+        if self.bytes.len() > OUTPUT_TAIL_LIMIT_BYTES {
+            let overflow = self.bytes.len() - OUTPUT_TAIL_LIMIT_BYTES;
+            self.bytes.drain(0..overflow);
+        }
+    }
+
+    pub(crate) fn text(&self) -> String {
+        let bytes = self.bytes.iter().copied().collect::<Vec<u8>>();
+        String::from_utf8_lossy(&bytes).trim().to_string()
+    }
+}
+
+fn spawn_tail_reader<R>(mut reader: R) -> (Arc<Mutex<OutputTail>>, JoinHandle<()>)
+where
+    R: Read + Send + 'static,
+{
+    let tail = Arc::new(Mutex::new(OutputTail::default()));
+    let sink = Arc::clone(&tail);
+    let handle = thread::spawn(move || {
+        let mut buffer = [0u8; 1024];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => {
+                    let mut tail = match sink.lock() {
+                        Ok(guard) => guard,
+                        Err(poisoned) => poisoned.into_inner(),
+                    };
+                    tail.push(&buffer[..read]);
+                }
+            }
+        }
+    });
+    (tail, handle)
+}
+
+fn capture_tail<R>(reader: Option<R>) -> (Arc<Mutex<OutputTail>>, Option<JoinHandle<()>>)
+where
+    R: Read + Send + 'static,
+{
+    match reader {
+        Some(reader) => {
+            let (tail, handle) = spawn_tail_reader(reader);
+            (tail, Some(handle))
+        }
+        None => (Arc::new(Mutex::new(OutputTail::default())), None),
+    }
+}
+
+fn read_tail(tail: &Arc<Mutex<OutputTail>>, reader: Option<JoinHandle<()>>) -> String {
+    // A grandchild can keep the pipe open, so draining never blocks.
+    if let Some(reader) = reader {
+        let deadline = Instant::now() + READER_DRAIN_WAIT;
+        while !reader.is_finished() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    match tail.lock() {
+        Ok(guard) => guard.text(),
+        Err(poisoned) => poisoned.into_inner().text(),
+    }
+}
+
+pub(crate) fn build_command(spec: &LaunchCommand) -> Command {
+    let mut command = Command::new(&spec.program);
+    command.args(&spec.args);
+    if let Some(working_dir) = spec.working_dir.as_ref() {
+        command.current_dir(working_dir);
+    }
+    command
+}
+
+pub(crate) fn describe_command(spec: &LaunchCommand) -> String {
+    std::iter::once(spec.program.as_str())
+        .chain(spec.args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LaunchExit {
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) stderr_tail: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TimedRun {
+    // `None` when killed by the timeout or a signal.
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) timed_out: bool,
+    pub(crate) elapsed: Duration,
+    pub(crate) stdout_tail: String,
+    pub(crate) stderr_tail: String,
+}
+
+pub(crate) fn run_with_timeout(
+    spec: &LaunchCommand,
+    timeout: Duration,
+) -> Result<TimedRun, String> {
+    let started = Instant::now();
+    let mut child = build_command(spec)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("failed to run `{}`: {error}", describe_command(spec)))?;
+    let (stdout_tail, stdout_reader) = capture_tail(child.stdout.take());
+    let (stderr_tail, stderr_reader) = capture_tail(child.stderr.take());
+
+    let (exit_code, timed_out) = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break (status.code(), false),
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break (None, true);
+            }
+            Ok(None) => thread::sleep(TIMED_RUN_POLL_INTERVAL),
+            Err(error) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("failed to query `{}` status: {error}", describe_command(spec)));
+            }
+        }
+    };
+
+    Ok(TimedRun {
+        exit_code,
+        timed_out,
+        elapsed: started.elapsed(),
+        stdout_tail: read_tail(&stdout_tail, stdout_reader),
+        stderr_tail: read_tail(&stderr_tail, stderr_reader),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum LaunchPoll {
+    Running,
+    Exited(LaunchExit),
+    Failed(String),
+}
+
+#[derive(Debug)]
+struct LaunchedChild {
+    child: Child,
+    stderr_tail: Arc<Mutex<OutputTail>>,
+    stderr_reader: Option<JoinHandle<()>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct LaunchSupervisor {
+    children: HashMap<String, LaunchedChild>,
+}
+
+impl LaunchSupervisor {
+    pub(crate) fn spawn(&mut self, schedule_id: &str, spec: &LaunchCommand) -> Result<u32, String> {
+        let mut child = build_command(spec)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| format!("failed to launch `{}`: {error}", describe_command(spec)))?;
+
+        let pid = child.id();
+        let (stderr_tail, stderr_reader) = capture_tail(child.stderr.take());
+
+        self.children.insert(
+            schedule_id.to_string(),
+            LaunchedChild {
+                child,
+                stderr_tail,
+                stderr_reader,
+            },
+        );
+        Ok(pid)
+    }
+
+    pub(crate) fn poll(&mut self, schedule_id: &str) -> LaunchPoll {
+        let Some(launched) = self.children.get_mut(schedule_id) else {
+            return LaunchPoll::Failed("launched command is no longer tracked".to_string());
+        };
+
+        match launched.child.try_wait() {
+            Ok(None) => LaunchPoll::Running,
+            Ok(Some(status)) => {
+                let Some(launched) = self.children.remove(schedule_id) else {
+                    return LaunchPoll::Failed("launched command is no longer tracked".to_string());
+                };
+                LaunchPoll::Exited(LaunchExit {
+                    exit_code: status.code(),
+                    stderr_tail: read_tail(&launched.stderr_tail, launched.stderr_reader),
+                })
+            }
+            Err(error) => {
+                self.children.remove(schedule_id);
+                LaunchPoll::Failed(format!("failed to query launched command status: {error}"))
+            }
+        }
+    }
+
+    // A detached waiter reaps released children so they do not linger as zombies.
+    pub(crate) fn release_all_except(&mut self, keep_ids: &[&str]) {
+        let released = self
+            .children
+            .keys()
+            .filter(|schedule_id| !keep_ids.contains(&schedule_id.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for schedule_id in released {
+            if let Some(mut launched) = self.children.remove(&schedule_id) {
+                thread::spawn(move || {
+                    let _ = launched.child.wait();
+                });
+            }
+        }
+    }
+
+    pub(crate) fn terminate(&mut self, schedule_id: &str) {
+        if let Some(mut launched) = self.children.remove(schedule_id) {
+            let _ = launched.child.kill();
+            let _ = launched.child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_tail_keeps_only_the_most_recent_bytes() {
+        let mut tail = OutputTail::default();
+        tail.push(&vec![b'a'; OUTPUT_TAIL_LIMIT_BYTES]);
+        tail.push(b"done\n");
+
+        let text = tail.text();
+        assert_eq!(text.len(), OUTPUT_TAIL_LIMIT_BYTES - 1);
+        assert!(text.ends_with("done"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn launch_supervisor_reports_exit_code_and_stderr_tail() {
+        let mut supervisor = LaunchSupervisor::default();
+        let spec = LaunchCommand {
+            program: "sh".to_string(),
+            args: vec!["-c".to_string(), "echo backup failed >&2; exit 3".to_string()],
+            working_dir: None,
+        };
+        supervisor.spawn("sch-launch", &spec).expect("sh should spawn");
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let exit = loop {
+            match supervisor.poll("sch-launch") {
+                LaunchPoll::Running if Instant::now() < deadline => {
+                    thread::sleep(Duration::from_millis(20));
+                }
+                LaunchPoll::Exited(exit) => break exit,
+                other => panic!("unexpected launch poll result: {other:?}"),
+            }
+        };
+
+        assert_eq!(exit.exit_code, Some(3));
+        assert_eq!(exit.stderr_tail, "backup failed");
+        assert!(matches!(supervisor.poll("sch-launch"), LaunchPoll::Failed(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_with_timeout_captures_output_and_kills_overruns() {
+        let sh = |script: &str| LaunchCommand {
+            program: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            working_dir: None,
+        };
+
+        let run = run_with_timeout(&sh("echo synced; echo warn >&2"), Duration::from_secs(10))
+            .expect("sh should run");
+        assert_eq!(run.exit_code, Some(0));
+        assert!(!run.timed_out);
+        assert_eq!(run.stdout_tail, "synced");
+        assert_eq!(run.stderr_tail, "warn");
+
+        let run = run_with_timeout(&sh("exec sleep 5"), Duration::from_millis(200))
+            .expect("sh should run");
+        assert!(run.timed_out);
+        assert_eq!(run.exit_code, None);
+        assert!(run.elapsed < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::{IpAddr, SocketAddr, TcpStream},
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+
+const HTTP_TIMEOUT: Duration = Duration::from_millis(1500);
+// Caps a trickling endpoint; the probe runs on the scheduler thread.
+const HTTP_TOTAL_TIMEOUT: Duration = Duration::from_secs(5);
+const HTTP_BODY_LIMIT_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LoopbackUrl {
+    host: String,
+    addr: SocketAddr,
+    path: String,
+}
+
+pub(crate) fn parse_loopback_url(url: &str) -> Result<LoopbackUrl, String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or("httpWatch.url must start with http://".to_string())?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], rest[index..].to_string()),
+        None => (rest, "/".to_string()),
+    };
+    if authority.contains('@') {
+        return Err("httpWatch.url must not contain credentials".to_string());
+    }
+
+    let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+        let (host, tail) = bracketed
+            .split_once(']')
+            .ok_or("httpWatch.url has an unterminated IPv6 host".to_string())?;
+        (host, tail.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    let port = match port {
+        Some(port) => port
+            .parse::<u16>()
+            .ok()
+            .filter(|port| *port != 0)
+            .ok_or(format!("httpWatch.url has an invalid port: {port}"))?,
+        None => 80,
+    };
+
+    let ip = if host.eq_ignore_ascii_case("localhost") {
+        IpAddr::from([127, 0, 0, 1])
+    } else {
+        host.parse::<IpAddr>()
+            .map_err(|_| format!("httpWatch.url host must be localhost or a loopback IP: {host}"))?
+    };
+    if !ip.is_loopback() {
+        return Err(format!("httpWatch.url host must be a loopback address: {host}"));
+    }
+
+    Ok(LoopbackUrl {
+        host: authority.to_string(),
+        addr: SocketAddr::new(ip, port),
+        path,
+    })
+}
+
+pub(crate) fn fetch_json(url: &LoopbackUrl) -> Result<Value, String> {
+    fetch_json_within(url, HTTP_TOTAL_TIMEOUT)
+}
+
+fn fetch_json_within(url: &LoopbackUrl, total: Duration) -> Result<Value, String> {
+    let deadline = Instant::now() + total;
+    let mut stream = TcpStream::connect_timeout(&url.addr, HTTP_TIMEOUT)
+        .map_err(|error| format!("connect to {} failed: {error}", url.addr))?;
+    stream
+        .set_read_timeout(Some(HTTP_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(HTTP_TIMEOUT)))
+        .map_err(|error| format!("failed to set timeouts: {error}"))?;
+
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nAccept: application/json\r\nConnection: close\r\n\r\n",
+        url.path, url.host
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|error| format!("request failed: {error}"))?;
+
+    let limit = HTTP_BODY_LIMIT_BYTES + 16 * 1024;
+    let mut raw = Vec::new();
+    let mut chunk = [0u8; 8 * 1024];
+    while raw.len() < limit {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(format!("response did not complete within {}s", total.as_secs_f64()));
+        }
+        let bounded_by_deadline = remaining < HTTP_TIMEOUT;
+        stream
+            .set_read_timeout(Some(remaining.min(HTTP_TIMEOUT)))
+            .map_err(|error| format!("failed to set timeouts: {error}"))?;
+        let want = (limit - raw.len()).min(chunk.len());
+        let read = match stream.read(&mut chunk[..want]) {
+            Ok(read) => read,
+            Err(error)
+                if bounded_by_deadline
+                    && matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                return Err(format!("response did not complete within {}s", total.as_secs_f64()));
+            }
+            Err(error) => return Err(format!("response read failed: {error}")),
+        };
+        if read == 0 {
+            break;
+        }
+        raw.extend_from_slice(&chunk[..read]);
+    }
+    let body = parse_http_response(&raw)?;
+    serde_json::from_slice(&body).map_err(|error| format!("response is not valid JSON: {error}"))
+}
+
+fn parse_http_response(raw: &[u8]) -> Result<Vec<u8>, String> {
+    let header_end = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or("response headers are incomplete".to_string())?;
+    let head = String::from_utf8_lossy(&raw[..header_end]);
+    let body = &raw[header_end + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or("response status line is malformed".to_string())?;
+    if !(200..300).contains(&status) {
+        return Err(format!("endpoint returned HTTP {status}"));
+    }
+
+    let mut chunked = false;
+    let mut content_length = None::<usize>;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.to_ascii_lowercase().contains("chunked");
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().ok();
+        }
+    }
+
+    if chunked {
+        decode_chunked(body)
+    } else if let Some(length) = content_length {
+        body.get(..length)
+            .map(<[u8]>::to_vec)
+            .ok_or("response body is shorter than Content-Length".to_string())
+    } else {
+        Ok(body.to_vec())
+    }
+}
+
+fn decode_chunked(mut body: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or("chunked body is truncated".to_string())?;
+        let size_line = String::from_utf8_lossy(&body[..line_end]);
+        let size_text = size_line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_text, 16)
+            .map_err(|_| format!("invalid chunk size: {size_text}"))?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Ok(decoded);
+        }
+        if body.len() < size + 2 || decoded.len() + size > HTTP_BODY_LIMIT_BYTES {
+            return Err("chunked body is truncated or too large".to_string());
+        }
+        decoded.extend_from_slice(&body[..size]);
+        body = &body[size + 2..];
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+// `/pointer` tests truthiness; `/pointer <op> <json literal>` compares.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct JsonExpression {
+    pointer: String,
+    comparison: Option<(CompareOp, Value)>,
+}
+
+pub(crate) fn parse_json_expression(expression: &str) -> Result<JsonExpression, String> {
+    let expression = expression.trim();
+    if !expression.starts_with('/') {
+        return Err("httpWatch.expression must start with a JSON pointer such as /active".to_string());
+    }
+
+    let operators = [
+        ("==", CompareOp::Eq),
+        ("!=", CompareOp::Ne),
+        ("<=", CompareOp::Le),
+        (">=", CompareOp::Ge),
+        ("<", CompareOp::Lt),
+        (">", CompareOp::Gt),
+    ];
+    let found = operators
+        .iter()
+        .filter_map(|(token, op)| expression.find(token).map(|index| (index, *token, *op)))
+        .min_by_key(|(index, token, _)| (*index, std::cmp::Reverse(token.len())));
+
+    let Some((index, token, op)) = found else {
+        return Ok(JsonExpression {
+            pointer: expression.to_string(),
+            comparison: None,
+        });
+    };
+    let pointer = expression[..index].trim().to_string();
+    let literal = expression[index + token.len()..].trim();
+    let literal = serde_json::from_str::<Value>(literal)
+        .map_err(|_| format!("httpWatch.expression has an invalid JSON literal: {literal}"))?;
+    if matches!(op, CompareOp::Lt | CompareOp::Le | CompareOp::Gt | CompareOp::Ge)
+        && !literal.is_number()
+    {
+        return Err("httpWatch.expression can only order-compare against numbers".to_string());
+    }
+
+    Ok(JsonExpression {
+        pointer,
+        comparison: Some((op, literal)),
+    })
+}
+
+impl JsonExpression {
+    pub(crate) fn evaluate(&self, document: &Value) -> Result<bool, String> {
+        let value = document
+            .pointer(&self.pointer)
+            .ok_or(format!("{} is missing from the response", self.pointer))?;
+
+        let Some((op, literal)) = self.comparison.as_ref() else {
+            return Ok(match value {
+                Value::Null => false,
+                Value::Bool(flag) => *flag,
+                Value::Number(number) => number.as_f64().map(|item| item != 0.0).unwrap_or(false),
+                Value::String(text) => !text.is_empty(),
+                Value::Array(items) => !items.is_empty(),
+                Value::Object(fields) => !fields.is_empty(),
+            });
+        };
+
+        match op {
+            CompareOp::Eq => Ok(json_equal(value, literal)),
+            CompareOp::Ne => Ok(!json_equal(value, literal)),
+            CompareOp::Lt | CompareOp::Le | CompareOp::Gt | CompareOp::Ge => {
+                let (Some(left), Some(right)) = (value.as_f64(), literal.as_f64()) else {
+                    return Err(format!("{} is not a number", self.pointer));
+                };
+                Ok(match op {
+                    CompareOp::Lt => left < right,
+                    CompareOp::Le => left <= right,
+                    CompareOp::Gt => left > right,
+                    _ => left >= right,
+                })
+            }
+        }
+    }
+}
+
+fn json_equal(left: &Value, right: &Value) -> bool {
+    match (left.as_f64(), right.as_f64()) {
+        (Some(left), Some(right)) => left == right,
+        _ => left == right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, thread};
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn loopback_urls_only() {
+        let url = parse_loopback_url("http://localhost:6800/jsonrpc?x=1").expect("valid url");
+        assert_eq!(url.addr, "127.0.0.1:6800".parse().unwrap());
+        assert_eq!(url.path, "/jsonrpc?x=1");
+        assert_eq!(
+            parse_loopback_url("http://[::1]:9000").expect("valid url").addr,
+            "[::1]:9000".parse().unwrap()
+        );
+        assert!(parse_loopback_url("https://localhost/").is_err());
+        assert!(parse_loopback_url("http://192.168.0.10:80/").is_err());
+        assert!(parse_loopback_url("http://example.com/").is_err());
+        assert!(parse_loopback_url("http://user@localhost/").is_err());
+    }
+
+    #[test]
+    fn expressions_compare_pointer_values() {
+        let document = json!({"active": 0, "queue": {"state": "idle", "items": []}, "ratio": 0.5});
+
+        let check = |expression: &str| {
+            parse_json_expression(expression)
+                .expect("valid expression")
+                .evaluate(&document)
+        };
+        assert_eq!(check("/active == 0"), Ok(true));
+        assert_eq!(check("/active != 0"), Ok(false));
+        assert_eq!(check("/queue/state == \"idle\""), Ok(true));
+        assert_eq!(check("/ratio >= 0.5"), Ok(true));
+        assert_eq!(check("/ratio < 0.5"), Ok(false));
+        assert_eq!(check("/queue/items"), Ok(false));
+        assert!(check("/missing == 0").is_err());
+        assert!(parse_json_expression("active == 0").is_err());
+        assert!(parse_json_expression("/state > \"a\"").is_err());
+    }
+
+    #[test]
+    fn fetch_json_decodes_a_chunked_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind local server");
+        let port = listener.local_addr().expect("server address").port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept request");
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n7\r\n{\"activ\r\n6\r\ne\": 0}\r\n0\r\n\r\n",
+                )
+                .expect("write response");
+        });
+
+        let url = parse_loopback_url(&format!("http://127.0.0.1:{port}/status")).expect("valid url");
+        assert_eq!(fetch_json(&url), Ok(json!({"active": 0})));
+        server.join().expect("server thread");
+    }
+
+    #[test]
+    fn fetch_json_gives_up_on_a_trickling_endpoint() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind local server");
+        let port = listener.local_addr().expect("server address").port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept request");
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            for byte in b"HTTP/1.1 200 OK\r\n".iter().cycle().take(40) {
+                if stream.write_all(&[*byte]).is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        });
+
+        let url = parse_loopback_url(&format!("http://127.0.0.1:{port}/")).expect("valid url");
+        let started = Instant::now();
+        let error = fetch_json_within(&url, Duration::from_millis(500)).unwrap_err();
+        assert!(error.contains("did not complete"), "{error}");
+        assert!(started.elapsed() < Duration::from_secs(2));
+        server.join().expect("server thread");
+    }
+}
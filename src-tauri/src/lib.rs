@@ -1,31 +1,75 @@
 ﻿
+mod command_runner;
+mod http_probe;
+mod log_watch;
+mod marker_watch;
+mod media_watch;
+mod net_scan;
+mod power_supply;
 mod process_scan;
 mod scheduler;
+#[cfg(test)]
+mod test_support;
+mod thermal;
+mod unit_watch;
 
 use chrono::{Days, Local, LocalResult, NaiveTime, TimeZone};
+use command_runner::{describe_command, run_with_timeout, LaunchPoll, LaunchSupervisor, TimedRun};
+use http_probe::{fetch_json, parse_json_expression, parse_loopback_url};
+use log_watch::{capture_log_start, LogStart, LogTailer};
+use marker_watch::{has_glob, marker_present};
+use media_watch::{MprisWatcher, PlaybackState};
+use net_scan::{accepts_local_connection, NetScanner, NetworkActivity};
+use power_supply::PowerSupplyReader;
 use process_scan::ProcessScanner;
+use thermal::ThermalReader;
+use unit_watch::{UnitPhase, UnitState, UnitWatcher};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fs, io,
     path::{Path, PathBuf},
     process::Command,
     sync::{Arc, Mutex, MutexGuard},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{Menu, MenuBuilder, MenuItem, MenuItemBuilder, SubmenuBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager, RunEvent,
 };
 use tauri_plugin_notification::NotificationExt;
 
 const HISTORY_LIMIT: usize = 250;
+const SCHEDULE_QUEUE_LIMIT: usize = 10;
+const PRESET_LIMIT: usize = 20;
+const PRESET_NAME_MAX_CHARS: usize = 40;
+const TRAY_PRESET_ID_PREFIX: &str = "preset:";
 const STATE_FILE_NAME: &str = "scheduler-state.json";
 const FINAL_WARNING_DEFAULT_SEC: u64 = 60;
 const FINAL_WARNING_MIN_SEC: u64 = 15;
 const FINAL_WARNING_MAX_SEC: u64 = 300;
 const FINAL_WARNING_RANGE_ERROR: &str =
     "최종 경고 시간은 15초에서 300초 사이로 설정해 주세요.";
+const PROCESS_INSTANCE_THRESHOLD_MAX: u32 = 4096;
+const LAUNCH_STDERR_HISTORY_CHARS: usize = 500;
+const LOG_PATTERN_MAX_CHARS: usize = 512;
+const LOG_MATCHED_LINE_CHARS: usize = 300;
+const THERMAL_FINAL_WARNING_SEC: u64 = FINAL_WARNING_MIN_SEC;
+const UPTIME_BOOT_DRIFT_MS: i64 = 5_000;
+const UPTIME_LIMIT_MAX_SEC: u64 = 30 * 24 * 60 * 60;
+const UNDO_CANCEL_WINDOW_DEFAULT_SEC: u64 = 60;
+const UNDO_CANCEL_WINDOW_MAX_SEC: u64 = 600;
+const POST_CONDITION_DELAY_MAX_SEC: u64 = 24 * 60 * 60;
+const SHUTDOWN_HOOK_LIMIT: usize = 8;
+const SHUTDOWN_HOOK_TIMEOUT_MAX_SEC: u64 = 600;
+const RUN_COMMAND_TIMEOUT_MAX_SEC: u64 = 6 * 60 * 60;
+const GRACEFUL_CLOSE_SELECTOR_LIMIT: usize = 8;
+const GRACEFUL_CLOSE_TIMEOUT_MAX_SEC: u64 = 300;
+const GRACEFUL_CLOSE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const TRAY_ID: &str = "main";
+const TRAY_TOOLTIP: &str = "Auto Shutdown Scheduler";
 #[cfg(target_os = "windows")]
 const WINDOWS_ABORTABLE_SHUTDOWN_SEC: u64 = 30;
 
@@ -35,6 +79,17 @@ enum ScheduleMode {
     Countdown,
     SpecificTime,
     ProcessExit,
+    LaunchAndWatch,
+    SystemdUnit,
+    NetworkIdle,
+    PortClosed,
+    LogPattern,
+    MarkerFile,
+    HttpPoll,
+    MediaIdle,
+    BatteryLow,
+    ThermalHigh,
+    UptimeLimit,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -54,6 +109,141 @@ struct ProcessSelector {
     cmdline_contains: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchCommand {
+    program: String,
+    #[serde(default)]
+    args: Vec<String>,
+    working_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShutdownHook {
+    name: String,
+    command: LaunchCommand,
+    timeout_sec: u64,
+    #[serde(default)]
+    abort_on_failure: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum ScheduleAction {
+    #[default]
+    Shutdown,
+    // Replaces the shutdown; graceful close and hooks are skipped.
+    #[serde(rename_all = "camelCase")]
+    RunCommand {
+        command: LaunchCommand,
+        timeout_sec: u64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GracefulClose {
+    selectors: Vec<ProcessSelector>,
+    timeout_sec: u64,
+    on_timeout: LingeringProcessPolicy,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+enum LingeringProcessPolicy {
+    Escalate,
+    Abort,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+enum LaunchExitPolicy {
+    Always,
+    OnSuccess,
+    OnFailure,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemdUnitWatch {
+    unit: String,
+    #[serde(default)]
+    require_success: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum PortProtocol {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum PortProbe {
+    #[default]
+    Listening,
+    Connect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PortWatch {
+    port: u16,
+    #[serde(default)]
+    protocol: PortProtocol,
+    #[serde(default)]
+    probe: PortProbe,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogWatch {
+    path: String,
+    pattern: String,
+    #[serde(default)]
+    only_new_lines: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum MarkerCondition {
+    #[default]
+    Appears,
+    Disappears,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarkerWatch {
+    path: String,
+    #[serde(default)]
+    condition: MarkerCondition,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HttpWatch {
+    url: String,
+    expression: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MediaWatch {
+    #[serde(default)]
+    player_filter: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThermalWatch {
+    sensor: String,
+    threshold_celsius: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ScheduleRequest {
@@ -63,6 +253,40 @@ struct ScheduleRequest {
     process_selector: Option<ProcessSelector>,
     pre_alerts: Option<Vec<u64>>,
     process_stable_sec: Option<u64>,
+    #[serde(default)]
+    process_instance_threshold: Option<u32>,
+    #[serde(default)]
+    launch_command: Option<LaunchCommand>,
+    #[serde(default)]
+    launch_exit_policy: Option<LaunchExitPolicy>,
+    #[serde(default)]
+    systemd_unit: Option<SystemdUnitWatch>,
+    #[serde(default)]
+    port_watch: Option<PortWatch>,
+    #[serde(default)]
+    log_watch: Option<LogWatch>,
+    #[serde(default)]
+    marker_watch: Option<MarkerWatch>,
+    #[serde(default)]
+    http_watch: Option<HttpWatch>,
+    #[serde(default)]
+    media_watch: Option<MediaWatch>,
+    #[serde(default)]
+    battery_threshold_percent: Option<u8>,
+    #[serde(default)]
+    require_battery_power: bool,
+    #[serde(default)]
+    thermal_watch: Option<ThermalWatch>,
+    #[serde(default)]
+    uptime_limit_sec: Option<u64>,
+    #[serde(default)]
+    not_before_local_time: Option<String>,
+    #[serde(default)]
+    deadline_local_time: Option<String>,
+    #[serde(default)]
+    post_condition_delay_sec: Option<u64>,
+    #[serde(default)]
+    action: Option<ScheduleAction>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +307,80 @@ struct ActiveSchedule {
     process_stable_sec: u64,
     process_missing_since_ms: Option<i64>,
     #[serde(default)]
+    process_instance_threshold: Option<u32>,
+    #[serde(default)]
+    process_instance_count: Option<u32>,
+    #[serde(default)]
+    launch_command: Option<LaunchCommand>,
+    #[serde(default)]
+    launch_exit_policy: Option<LaunchExitPolicy>,
+    #[serde(default)]
+    launch_pid: Option<u32>,
+    #[serde(default)]
+    launch_exit_code: Option<i32>,
+    #[serde(default)]
+    launch_exited_at_ms: Option<i64>,
+    #[serde(default)]
+    systemd_unit: Option<SystemdUnitWatch>,
+    #[serde(default)]
+    unit_seen_active: bool,
+    #[serde(default)]
+    network_connection_count: Option<u32>,
+    #[serde(default)]
+    port_watch: Option<PortWatch>,
+    #[serde(default)]
+    port_open: Option<bool>,
+    #[serde(default)]
+    log_watch: Option<LogWatch>,
+    #[serde(default)]
+    log_matched_line: Option<String>,
+    #[serde(default)]
+    log_start: Option<LogStart>,
+    #[serde(default)]
+    marker_watch: Option<MarkerWatch>,
+    #[serde(default)]
+    http_watch: Option<HttpWatch>,
+    #[serde(default)]
+    media_watch: Option<MediaWatch>,
+    #[serde(default)]
+    battery_threshold_percent: Option<u8>,
+    #[serde(default)]
+    require_battery_power: bool,
+    #[serde(default)]
+    battery_guard_holding: bool,
+    #[serde(default)]
+    thermal_watch: Option<ThermalWatch>,
+    #[serde(default)]
+    thermal_peak_celsius: Option<f64>,
+    #[serde(default)]
+    uptime_limit_sec: Option<u64>,
+    #[serde(default)]
+    boot_time_ms: Option<i64>,
+    #[serde(default)]
+    duration_sec: Option<u64>,
+    #[serde(default)]
+    not_before_ms: Option<i64>,
+    #[serde(default)]
+    deadline_ms: Option<i64>,
+    #[serde(default)]
+    final_warning_deferred: bool,
+    #[serde(default)]
+    post_condition_delay_sec: Option<u64>,
+    #[serde(default)]
+    post_delay_started_at_ms: Option<i64>,
+    #[serde(default)]
+    paused_at_ms: Option<i64>,
+    #[serde(default)]
+    postpone_count: u32,
+    #[serde(default)]
+    postponed_total_sec: u64,
+    #[serde(default)]
+    action: ScheduleAction,
+    #[serde(default)]
+    condition_met_since_ms: Option<i64>,
+    #[serde(default)]
+    condition_detail: Option<String>,
+    #[serde(default)]
     snooze_until_ms: Option<i64>,
     #[serde(default)]
     process_match_degraded_logged: bool,
@@ -111,6 +409,22 @@ struct AppSettings {
     default_pre_alerts: Vec<u64>,
     final_warning_sec: u64,
     simulate_only: bool,
+    #[serde(default)]
+    max_postpone_count: Option<u32>,
+    #[serde(default)]
+    max_postponed_total_sec: Option<u64>,
+    #[serde(default)]
+    postpone_deadline_local_time: Option<String>,
+    #[serde(default = "default_undo_cancel_window_sec")]
+    undo_cancel_window_sec: u64,
+    #[serde(default)]
+    pre_shutdown_hooks: Vec<ShutdownHook>,
+    #[serde(default)]
+    graceful_close: Option<GracefulClose>,
+}
+
+fn default_undo_cancel_window_sec() -> u64 {
+    UNDO_CANCEL_WINDOW_DEFAULT_SEC
 }
 
 impl Default for AppSettings {
@@ -119,16 +433,47 @@ impl Default for AppSettings {
             default_pre_alerts: vec![600, 300, 60],
             final_warning_sec: FINAL_WARNING_DEFAULT_SEC,
             simulate_only: cfg!(debug_assertions),
+            max_postpone_count: None,
+            max_postponed_total_sec: None,
+            postpone_deadline_local_time: None,
+            undo_cancel_window_sec: UNDO_CANCEL_WINDOW_DEFAULT_SEC,
+            pre_shutdown_hooks: Vec::new(),
+            graceful_close: None,
         }
     }
 }
 
+// A snooze limit of `0`, or an empty deadline, removes that limit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SettingsUpdate {
     default_pre_alerts: Option<Vec<u64>>,
     final_warning_sec: Option<u64>,
     simulate_only: Option<bool>,
+    #[serde(default)]
+    max_postpone_count: Option<u32>,
+    #[serde(default)]
+    max_postponed_total_sec: Option<u64>,
+    #[serde(default)]
+    postpone_deadline_local_time: Option<String>,
+    // `0` turns undo off.
+    #[serde(default)]
+    undo_cancel_window_sec: Option<u64>,
+    #[serde(default)]
+    pre_shutdown_hooks: Option<Vec<ShutdownHook>>,
+    // An empty selector list turns graceful close off.
+    #[serde(default)]
+    graceful_close: Option<GracefulClose>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScheduleUpdate {
+    duration_sec: Option<u64>,
+    target_local_time: Option<String>,
+    pre_alerts: Option<Vec<u64>>,
+    process_stable_sec: Option<u64>,
+    process_selector: Option<ProcessSelector>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -138,6 +483,27 @@ struct SchedulerSnapshot {
     settings: AppSettings,
     history: Vec<ExecutionEvent>,
     now_ms: i64,
+    battery: Option<BatteryStatus>,
+    undo_cancel_until_ms: Option<i64>,
+    queue: Vec<QueuedSchedule>,
+    presets: Vec<SchedulePreset>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatteryStatus {
+    percent: u8,
+    on_ac: bool,
+    charging: bool,
+    batteries: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThermalSensor {
+    id: String,
+    label: String,
+    celsius: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -161,10 +527,59 @@ enum ProcessMatchSource {
 struct ProcessMatchResult {
     running: bool,
     matched_pids: Vec<u32>,
+    instance_count: u32,
     source: ProcessMatchSource,
     degraded_to_name: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConditionProbe {
+    Pending,
+    // The observation failed; this says nothing about the condition.
+    Unavailable,
+    Met { reason: String },
+    Skip { reason: String },
+    Failed { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConditionTransition {
+    Unchanged,
+    WindowOpened,
+    WindowReset,
+    FinalWarning { reason: String },
+    DelayStarted { reason: String },
+    Deferred,
+    Held,
+    Reverted { reason: String },
+    Skipped { reason: String },
+    Failed { reason: String },
+}
+
+enum ConditionObservation {
+    Launch(Option<LaunchPoll>),
+    SystemdUnit(Result<Option<UnitState>, String>),
+    NetworkIdle(Result<NetworkActivity, String>),
+    PortOpen(Result<bool, String>),
+    LogMatch(Result<Option<String>, String>),
+    MarkerPresent(Result<bool, String>),
+    HttpCheck(Result<bool, String>),
+    Playback(Result<PlaybackState, String>),
+    Battery(Result<Option<BatteryStatus>, String>),
+    Thermal(Result<Option<ThermalSensor>, String>),
+    Invalid(String),
+}
+
+#[derive(Debug, Default)]
+struct ConditionWatchers {
+    units: UnitWatcher,
+    net: NetScanner,
+    log: Option<LogTailer>,
+    media: MprisWatcher,
+    power: PowerSupplyReader,
+    thermal: ThermalReader,
+}
+
 fn process_match_source_label(source: ProcessMatchSource) -> &'static str {
     match source {
         ProcessMatchSource::PidTree => "pidTree",
@@ -175,107 +590,495 @@ fn process_match_source_label(source: ProcessMatchSource) -> &'static str {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct PersistedState {
-    version: u8,
-    settings: AppSettings,
-    history: Vec<ExecutionEvent>,
-    active: Option<ActiveSchedule>,
-    id_seq: u64,
-    #[serde(default)]
-    last_schedule_request: Option<ScheduleRequest>,
+fn launch_exit_policy_label(policy: LaunchExitPolicy) -> &'static str {
+    match policy {
+        LaunchExitPolicy::Always => "always",
+        LaunchExitPolicy::OnSuccess => "onSuccess",
+        LaunchExitPolicy::OnFailure => "onFailure",
+    }
 }
 
-impl Default for PersistedState {
-    fn default() -> Self {
-        Self {
-            version: 1,
-            settings: AppSettings::default(),
-            history: Vec::new(),
-            active: None,
-            id_seq: 0,
-            last_schedule_request: None,
-        }
+fn launch_exit_policy_allows(policy: LaunchExitPolicy, success: bool) -> bool {
+    match policy {
+        LaunchExitPolicy::Always => true,
+        LaunchExitPolicy::OnSuccess => success,
+        LaunchExitPolicy::OnFailure => !success,
     }
 }
 
-#[derive(Debug, Clone)]
-struct SchedulerStore {
-    settings: AppSettings,
-    history: Vec<ExecutionEvent>,
-    active: Option<ActiveSchedule>,
-    id_seq: u64,
-    last_schedule_request: Option<ScheduleRequest>,
+fn launch_exit_label(exit_code: Option<i32>) -> String {
+    exit_code
+        .map(|code| format!("exit code {code}"))
+        .unwrap_or_else(|| "terminated without exit code".to_string())
 }
 
-#[derive(Debug, Default)]
-struct RuntimeState {
-    allow_exit_once: bool,
+fn launch_exit_event_reason(exit_code: Option<i32>, stderr_tail: &str) -> String {
+    let exit = launch_exit_label(exit_code);
+    if stderr_tail.is_empty() {
+        return format!("{exit}; stderr empty");
+    }
+    format!("{exit}; stderr tail: {}", history_output_tail(stderr_tail))
 }
 
-#[derive(Debug, Clone)]
-struct ShutdownDispatchReport {
-    command_line: String,
-    abort_hint: Option<String>,
-    dry_run: bool,
+fn history_output_tail(output: &str) -> String {
+    let char_count = output.chars().count();
+    if char_count > LAUNCH_STDERR_HISTORY_CHARS {
+        let skipped = output
+            .chars()
+            .skip(char_count - LAUNCH_STDERR_HISTORY_CHARS)
+            .collect::<String>();
+        format!("...{skipped}")
+    } else {
+        output.to_string()
+    }
 }
 
-impl ShutdownDispatchReport {
-    fn log_line(&self) -> String {
-        let prefix = if self.dry_run {
-            "DRY_RUN_SHUTDOWN_COMMAND"
-        } else {
-            "SHUTDOWN_COMMAND_SENT"
-        };
+// The working directory may only exist by the time the command runs.
+fn normalize_command(command: &LaunchCommand, field: &str) -> Result<LaunchCommand, String> {
+    let program = normalize_selector_text(Some(&command.program))
+        .ok_or(format!("{field}.program must not be empty"))?;
+    Ok(LaunchCommand {
+        program,
+        args: command.args.clone(),
+        working_dir: normalize_selector_text(command.working_dir.as_ref()),
+    })
+}
 
-        match &self.abort_hint {
-            Some(abort) => format!("{prefix}: {} (abort: {abort})", self.command_line),
-            None => format!("{prefix}: {}", self.command_line),
+fn validate_schedule_action(action: Option<&ScheduleAction>) -> Result<ScheduleAction, String> {
+    match action {
+        None | Some(ScheduleAction::Shutdown) => Ok(ScheduleAction::Shutdown),
+        Some(ScheduleAction::RunCommand {
+            command,
+            timeout_sec,
+        }) => {
+            if !(1..=RUN_COMMAND_TIMEOUT_MAX_SEC).contains(timeout_sec) {
+                return Err(format!(
+                    "action.timeoutSec must be within 1..={RUN_COMMAND_TIMEOUT_MAX_SEC}"
+                ));
+            }
+            Ok(ScheduleAction::RunCommand {
+                command: normalize_command(command, "action.command")?,
+                timeout_sec: *timeout_sec,
+            })
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-struct QuitGuardPayload {
-    source: String,
-    status: ScheduleStatus,
+fn validate_shutdown_hooks(hooks: &[ShutdownHook]) -> Result<Vec<ShutdownHook>, String> {
+    if hooks.len() > SHUTDOWN_HOOK_LIMIT {
+        return Err(format!("at most {SHUTDOWN_HOOK_LIMIT} pre-shutdown hooks can be configured"));
+    }
+    hooks
+        .iter()
+        .enumerate()
+        .map(|(index, hook)| validate_shutdown_hook(index, hook))
+        .collect()
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum QuitGuardAction {
-    CancelAndQuit,
-    KeepBackground,
-    Return,
+fn validate_shutdown_hook(index: usize, hook: &ShutdownHook) -> Result<ShutdownHook, String> {
+    let field = format!("preShutdownHooks[{index}]");
+    let name = normalize_selector_text(Some(&hook.name))
+        .ok_or(format!("{field}.name must not be empty"))?;
+    if !(1..=SHUTDOWN_HOOK_TIMEOUT_MAX_SEC).contains(&hook.timeout_sec) {
+        return Err(format!(
+            "{field}.timeoutSec must be within 1..={SHUTDOWN_HOOK_TIMEOUT_MAX_SEC}"
+        ));
+    }
+    Ok(ShutdownHook {
+        name,
+        command: normalize_command(&hook.command, &format!("{field}.command"))?,
+        timeout_sec: hook.timeout_sec,
+        abort_on_failure: hook.abort_on_failure,
+    })
 }
 
-impl Default for SchedulerStore {
-    fn default() -> Self {
-        Self::from_persisted(PersistedState::default())
+// One bad hook must not drop the others with it.
+fn sanitize_persisted_hooks(hooks: &[ShutdownHook]) -> (Vec<ShutdownHook>, Vec<String>) {
+    let mut kept = Vec::new();
+    let mut dropped = Vec::new();
+    for (index, hook) in hooks.iter().enumerate() {
+        match validate_shutdown_hook(index, hook) {
+            Ok(_) if kept.len() >= SHUTDOWN_HOOK_LIMIT => dropped.push(format!(
+                "preShutdownHooks[{index}] exceeds the limit of {SHUTDOWN_HOOK_LIMIT} hooks"
+            )),
+            Ok(hook) => kept.push(hook),
+            Err(error) => dropped.push(error),
+        }
     }
+    (kept, dropped)
 }
 
-impl SchedulerStore {
-    fn from_persisted(persisted: PersistedState) -> Self {
-        let settings = AppSettings {
-            default_pre_alerts: normalize_alerts(&persisted.settings.default_pre_alerts),
-            final_warning_sec: normalize_final_warning_sec(persisted.settings.final_warning_sec),
-            simulate_only: persisted.settings.simulate_only,
-        };
-        Self {
-            settings: settings.clone(),
-            history: persisted.history,
-            active: persisted
-                .active
-                .map(|active| sanitize_active_from_persist(active, settings.final_warning_sec)),
-            id_seq: persisted.id_seq,
-            last_schedule_request: persisted.last_schedule_request,
-        }
+fn validate_graceful_close(close: &GracefulClose) -> Result<Option<GracefulClose>, String> {
+    if close.selectors.is_empty() {
+        return Ok(None);
+    }
+    if close.selectors.len() > GRACEFUL_CLOSE_SELECTOR_LIMIT {
+        return Err(format!(
+            "gracefulClose accepts at most {GRACEFUL_CLOSE_SELECTOR_LIMIT} selectors"
+        ));
+    }
+    if !(1..=GRACEFUL_CLOSE_TIMEOUT_MAX_SEC).contains(&close.timeout_sec) {
+        return Err(format!(
+            "gracefulClose.timeoutSec must be within 1..={GRACEFUL_CLOSE_TIMEOUT_MAX_SEC}"
+        ));
     }
+    let selectors = close
+        .selectors
+        .iter()
+        .enumerate()
+        .map(|(index, selector)| {
+            normalize_and_validate_process_selector(Some(selector))
+                .map_err(|error| format!("gracefulClose.selectors[{index}]: {error}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(GracefulClose {
+        selectors,
+        timeout_sec: close.timeout_sec,
+        on_timeout: close.on_timeout,
+    }))
+}
 
-    fn to_persisted(&self) -> PersistedState {
-        PersistedState {
+// Drops bad selectors one by one and clamps the timeout.
+fn sanitize_persisted_graceful_close(
+    close: Option<&GracefulClose>,
+) -> (Option<GracefulClose>, Vec<String>) {
+    let Some(close) = close.filter(|close| !close.selectors.is_empty()) else {
+        return (None, Vec::new());
+    };
+    let mut dropped = Vec::new();
+    let mut selectors = Vec::new();
+    for (index, selector) in close.selectors.iter().enumerate() {
+        match normalize_and_validate_process_selector(Some(selector)) {
+            Ok(_) if selectors.len() >= GRACEFUL_CLOSE_SELECTOR_LIMIT => dropped.push(format!(
+                "gracefulClose.selectors[{index}] exceeds the limit of \
+                 {GRACEFUL_CLOSE_SELECTOR_LIMIT} selectors; removed from settings"
+            )),
+            Ok(selector) => selectors.push(selector),
+            Err(error) => dropped.push(format!(
+                "gracefulClose.selectors[{index}]: {error}; removed from settings"
+            )),
+        }
+    }
+    let timeout_sec = close.timeout_sec.clamp(1, GRACEFUL_CLOSE_TIMEOUT_MAX_SEC);
+    if timeout_sec != close.timeout_sec {
+        dropped.push(format!(
+            "gracefulClose.timeoutSec must be within 1..={GRACEFUL_CLOSE_TIMEOUT_MAX_SEC}; \
+             clamped to {timeout_sec}"
+        ));
+    }
+    let close = (!selectors.is_empty()).then_some(GracefulClose {
+        selectors,
+        timeout_sec,
+        on_timeout: close.on_timeout,
+    });
+    (close, dropped)
+}
+
+fn persisted_settings_need_sanitizing(settings: &AppSettings) -> bool {
+    !sanitize_persisted_hooks(&settings.pre_shutdown_hooks).1.is_empty()
+        || !sanitize_persisted_graceful_close(settings.graceful_close.as_ref())
+            .1
+            .is_empty()
+}
+
+#[derive(Debug, Default)]
+struct GracefulCloseReport {
+    closed: Vec<String>,
+    refused: Vec<String>,
+    killed: Vec<String>,
+}
+
+impl GracefulCloseReport {
+    fn event_reason(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, entries) in [
+            ("closed", &self.closed),
+            ("refused", &self.refused),
+            ("killed", &self.killed),
+        ] {
+            if !entries.is_empty() {
+                parts.push(format!("{label}: {}", entries.join(", ")));
+            }
+        }
+        parts.join("; ")
+    }
+}
+
+fn shutdown_hook_event(
+    hook: &ShutdownHook,
+    run: &Result<TimedRun, String>,
+    dry_run: bool,
+) -> (&'static str, String) {
+    let prefix = if dry_run { "DRY_RUN " } else { "" };
+    let (result, outcome) = timed_run_outcome(run, hook.timeout_sec);
+    (result, format!("{prefix}{}: {outcome}", hook.name))
+}
+
+fn timed_run_outcome(run: &Result<TimedRun, String>, timeout_sec: u64) -> (&'static str, String) {
+    let run = match run {
+        Ok(run) => run,
+        Err(error) => return ("error", error.clone()),
+    };
+    let mut outcome = if run.timed_out {
+        format!("timed out after {timeout_sec}s")
+    } else {
+        format!(
+            "{} after {:.1}s",
+            launch_exit_label(run.exit_code),
+            run.elapsed.as_secs_f64()
+        )
+    };
+    for (label, output) in [("stdout", &run.stdout_tail), ("stderr", &run.stderr_tail)] {
+        if !output.is_empty() {
+            outcome.push_str(&format!("; {label}: {}", history_output_tail(output)));
+        }
+    }
+    let result = if !run.timed_out && run.exit_code == Some(0) {
+        "ok"
+    } else {
+        "error"
+    };
+    (result, outcome)
+}
+
+fn record_process_instance_count(active: &mut ActiveSchedule, instance_count: u32) -> Option<String> {
+    let previous = active.process_instance_count.replace(instance_count);
+    let threshold = active.process_instance_threshold?;
+    // The first sample only establishes the baseline; there is nothing to cross from yet.
+    let was_at_or_above = previous? >= threshold;
+    let is_at_or_above = instance_count >= threshold;
+    if was_at_or_above == is_at_or_above {
+        return None;
+    }
+
+    if is_at_or_above {
+        Some(format!(
+            "instance count rose to {instance_count} (threshold {threshold}); target still running"
+        ))
+    } else {
+        Some(format!(
+            "instance count fell to {instance_count} (below threshold {threshold})"
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedState {
+    version: u8,
+    settings: AppSettings,
+    history: Vec<ExecutionEvent>,
+    active: Option<ActiveSchedule>,
+    id_seq: u64,
+    #[serde(default)]
+    last_schedule_request: Option<ScheduleRequest>,
+    #[serde(default)]
+    queue: Vec<QueuedSchedule>,
+    #[serde(default)]
+    presets: Vec<SchedulePreset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SchedulePreset {
+    id: String,
+    name: String,
+    request: ScheduleRequest,
+    created_at_ms: i64,
+    updated_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum DstResolution {
+    Unambiguous,
+    // Clocks fall back; the earlier instant is used.
+    EarliestOfRepeated,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreAlertPreview {
+    threshold_sec: u64,
+    fires_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SchedulePreview {
+    mode: ScheduleMode,
+    summary: String,
+    computed_at_ms: i64,
+    trigger_at_ms: Option<i64>,
+    pre_alerts: Vec<PreAlertPreview>,
+    final_warning_starts_at_ms: Option<i64>,
+    final_warning_duration_sec: u64,
+    shutdown_at_ms: Option<i64>,
+    not_before_ms: Option<i64>,
+    deadline_ms: Option<i64>,
+    target_tz_offset_minutes: Option<i32>,
+    dst_resolution: Option<DstResolution>,
+    warnings: Vec<String>,
+}
+
+// Built, and its countdown started, only when promoted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueuedSchedule {
+    id: String,
+    summary: String,
+    queued_at_ms: i64,
+    #[serde(default)]
+    behind_schedule_id: Option<String>,
+    request: ScheduleRequest,
+}
+
+impl Default for PersistedState {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            settings: AppSettings::default(),
+            history: Vec::new(),
+            active: None,
+            id_seq: 0,
+            last_schedule_request: None,
+            queue: Vec::new(),
+            presets: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SchedulerStore {
+    settings: AppSettings,
+    history: Vec<ExecutionEvent>,
+    active: Option<ActiveSchedule>,
+    id_seq: u64,
+    last_schedule_request: Option<ScheduleRequest>,
+    queue: Vec<QueuedSchedule>,
+    presets: Vec<SchedulePreset>,
+    // Never persisted.
+    cancelled: Option<CancelledSchedule>,
+}
+
+#[derive(Debug, Clone)]
+struct CancelledSchedule {
+    schedule: ActiveSchedule,
+    cancelled_at_ms: i64,
+}
+
+#[derive(Debug, Default)]
+struct RuntimeState {
+    allow_exit_once: bool,
+    tray_paused: bool,
+    tray_snooze_blocked: bool,
+    tray_undo_available: bool,
+}
+
+struct TrayMenuItems {
+    postpone: MenuItem<tauri::Wry>,
+    undo_cancel: MenuItem<tauri::Wry>,
+}
+
+struct TrayMenu(Mutex<Option<TrayMenuItems>>);
+
+#[derive(Debug, Clone)]
+struct ShutdownDispatchReport {
+    command_line: String,
+    abort_hint: Option<String>,
+    dry_run: bool,
+}
+
+impl ShutdownDispatchReport {
+    fn log_line(&self) -> String {
+        let prefix = if self.dry_run {
+            "DRY_RUN_SHUTDOWN_COMMAND"
+        } else {
+            "SHUTDOWN_COMMAND_SENT"
+        };
+
+        match &self.abort_hint {
+            Some(abort) => format!("{prefix}: {} (abort: {abort})", self.command_line),
+            None => format!("{prefix}: {}", self.command_line),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QuitGuardPayload {
+    source: String,
+    status: ScheduleStatus,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum QuitGuardAction {
+    CancelAndQuit,
+    KeepBackground,
+    Return,
+}
+
+impl Default for SchedulerStore {
+    fn default() -> Self {
+        Self::from_persisted(PersistedState::default())
+    }
+}
+
+impl SchedulerStore {
+    fn from_persisted(persisted: PersistedState) -> Self {
+        let (pre_shutdown_hooks, dropped_hooks) =
+            sanitize_persisted_hooks(&persisted.settings.pre_shutdown_hooks);
+        let (graceful_close, close_changes) =
+            sanitize_persisted_graceful_close(persisted.settings.graceful_close.as_ref());
+        let settings = AppSettings {
+            default_pre_alerts: normalize_alerts(&persisted.settings.default_pre_alerts),
+            final_warning_sec: normalize_final_warning_sec(persisted.settings.final_warning_sec),
+            simulate_only: persisted.settings.simulate_only,
+            max_postpone_count: persisted.settings.max_postpone_count.filter(|count| *count > 0),
+            max_postponed_total_sec: persisted
+                .settings
+                .max_postponed_total_sec
+                .filter(|total| *total > 0),
+            postpone_deadline_local_time: persisted
+                .settings
+                .postpone_deadline_local_time
+                .filter(|deadline| NaiveTime::parse_from_str(deadline, "%H:%M").is_ok()),
+            undo_cancel_window_sec: persisted
+                .settings
+                .undo_cancel_window_sec
+                .min(UNDO_CANCEL_WINDOW_MAX_SEC),
+            pre_shutdown_hooks,
+            graceful_close,
+        };
+        let mut store = Self {
+            settings: settings.clone(),
+            history: persisted.history,
+            active: persisted
+                .active
+                .map(|active| sanitize_active_from_persist(active, settings.final_warning_sec)),
+            id_seq: persisted.id_seq,
+            last_schedule_request: persisted.last_schedule_request,
+            queue: persisted.queue,
+            presets: persisted.presets,
+            cancelled: None,
+        };
+        for reason in dropped_hooks {
+            push_event(
+                &mut store,
+                None,
+                "pre_shutdown_hook_dropped",
+                "error",
+                Some(format!("{reason}; removed from settings")),
+            );
+        }
+        for reason in close_changes {
+            push_event(&mut store, None, "graceful_close_sanitized", "error", Some(reason));
+        }
+        store
+    }
+
+    fn to_persisted(&self) -> PersistedState {
+        PersistedState {
             version: 1,
             settings: self.settings.clone(),
             history: self.history.clone(),
@@ -286,6 +1089,8 @@ impl SchedulerStore {
                 .map(sanitize_active_for_persist),
             id_seq: self.id_seq,
             last_schedule_request: self.last_schedule_request.clone(),
+            queue: self.queue.clone(),
+            presets: self.presets.clone(),
         }
     }
 }
@@ -295,6 +1100,8 @@ struct AppState {
     store: Mutex<SchedulerStore>,
     runtime: Mutex<RuntimeState>,
     scanner: Arc<Mutex<ProcessScanner>>,
+    launcher: Mutex<LaunchSupervisor>,
+    watchers: Mutex<ConditionWatchers>,
 }
 
 struct LoadStoreOutcome {
@@ -311,6 +1118,10 @@ impl AppState {
             settings: store.settings.clone(),
             history: store.history.clone(),
             now_ms: now_ms(),
+            battery: lock_watchers(&self.watchers).power.read().ok().flatten(),
+            undo_cancel_until_ms: undo_cancel_until_ms(&store, now_ms()),
+            queue: store.queue.clone(),
+            presets: store.presets.clone(),
         }
     }
 
@@ -353,6 +1164,20 @@ fn lock_scanner(scanner: &Arc<Mutex<ProcessScanner>>) -> MutexGuard<'_, ProcessS
     }
 }
 
+fn lock_launcher(launcher: &Mutex<LaunchSupervisor>) -> MutexGuard<'_, LaunchSupervisor> {
+    match launcher.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn lock_watchers(watchers: &Mutex<ConditionWatchers>) -> MutexGuard<'_, ConditionWatchers> {
+    match watchers.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
 fn now_ms() -> i64 {
     let duration = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -384,13 +1209,38 @@ fn validate_final_warning_sec(value: u64) -> Result<u64, String> {
     }
 }
 
+fn final_warning_sec_for_mode(mode: &ScheduleMode, final_warning_sec: u64) -> u64 {
+    let final_warning_sec = normalize_final_warning_sec(final_warning_sec);
+    match mode {
+        // Short, but still long enough to cancel.
+        ScheduleMode::ThermalHigh => final_warning_sec.min(THERMAL_FINAL_WARNING_SEC),
+        _ => final_warning_sec,
+    }
+}
+
 fn compute_shutdown_at_ms(active: &ActiveSchedule) -> Option<i64> {
     match active.status {
+        ScheduleStatus::Armed if active.paused_at_ms.is_some() => None,
+        ScheduleStatus::Armed if active.post_delay_started_at_ms.is_some() => active
+            .trigger_at_ms
+            .and_then(|trigger| trigger.checked_add((active.final_warning_duration_sec as i64) * 1000)),
         ScheduleStatus::Armed => match active.mode {
-            ScheduleMode::Countdown | ScheduleMode::SpecificTime => active
+            ScheduleMode::Countdown
+            | ScheduleMode::SpecificTime
+            | ScheduleMode::UptimeLimit => active
                 .trigger_at_ms
                 .and_then(|trigger| trigger.checked_add((active.final_warning_duration_sec as i64) * 1000)),
-            ScheduleMode::ProcessExit => None,
+            ScheduleMode::ProcessExit
+            | ScheduleMode::LaunchAndWatch
+            | ScheduleMode::SystemdUnit
+            | ScheduleMode::NetworkIdle
+            | ScheduleMode::PortClosed
+            | ScheduleMode::LogPattern
+            | ScheduleMode::MarkerFile
+            | ScheduleMode::HttpPoll
+            | ScheduleMode::MediaIdle
+            | ScheduleMode::BatteryLow
+            | ScheduleMode::ThermalHigh => None,
         },
         ScheduleStatus::FinalWarning | ScheduleStatus::ShuttingDown => active
             .final_warning_started_at_ms
@@ -398,1849 +1248,6793 @@ fn compute_shutdown_at_ms(active: &ActiveSchedule) -> Option<i64> {
     }
 }
 
-fn sync_shutdown_at_ms(active: &mut ActiveSchedule) -> bool {
-    let computed = compute_shutdown_at_ms(active);
-    if active.shutdown_at_ms != computed {
-        active.shutdown_at_ms = computed;
-        true
-    } else {
-        false
-    }
+fn remaining_trigger_ms(active: &ActiveSchedule, now: i64) -> i64 {
+    let reference = active.paused_at_ms.unwrap_or(now);
+    active
+        .trigger_at_ms
+        .map(|trigger_at_ms| (trigger_at_ms - reference).max(0))
+        .unwrap_or(0)
 }
 
-fn sanitize_active_for_persist(mut active: ActiveSchedule) -> ActiveSchedule {
-    active.final_warning_duration_sec = normalize_final_warning_sec(active.final_warning_duration_sec);
-    if matches!(active.status, ScheduleStatus::ShuttingDown) {
-        active.status = ScheduleStatus::FinalWarning;
+fn pause_active(active: &mut ActiveSchedule, now: i64) -> Result<(), String> {
+    if active.paused_at_ms.is_some() {
+        return Err("schedule is already paused".to_string());
     }
-    let _ = sync_shutdown_at_ms(&mut active);
-    active
+    if active.status != ScheduleStatus::Armed {
+        return Err("only an armed schedule can be paused".to_string());
+    }
+    let has_timer = matches!(active.mode, ScheduleMode::Countdown)
+        || active.post_delay_started_at_ms.is_some();
+    if !has_timer || active.trigger_at_ms.is_none() {
+        return Err("only countdowns and post-condition delays can be paused".to_string());
+    }
+
+    active.paused_at_ms = Some(now);
+    let _ = sync_shutdown_at_ms(active);
+    Ok(())
 }
 
-fn sanitize_active_from_persist(
-    mut active: ActiveSchedule,
-    fallback_final_warning_sec: u64,
-) -> ActiveSchedule {
-    if matches!(active.status, ScheduleStatus::ShuttingDown) {
-        active.status = ScheduleStatus::FinalWarning;
-        active.shutdown_initiated_at_ms = None;
-    }
-    let was_invalid = !(FINAL_WARNING_MIN_SEC..=FINAL_WARNING_MAX_SEC)
-        .contains(&active.final_warning_duration_sec);
-    active.final_warning_duration_sec = if was_invalid {
-        normalize_final_warning_sec(fallback_final_warning_sec)
-    } else {
-        active.final_warning_duration_sec
+fn resume_active(active: &mut ActiveSchedule, now: i64) -> Result<i64, String> {
+    let Some(paused_at_ms) = active.paused_at_ms.take() else {
+        return Err("schedule is not paused".to_string());
     };
-    let _ = sync_shutdown_at_ms(&mut active);
-    active
+    let paused_for_ms = (now - paused_at_ms).max(0);
+    active.trigger_at_ms = active
+        .trigger_at_ms
+        .map(|trigger_at_ms| trigger_at_ms.saturating_add(paused_for_ms));
+    let _ = sync_shutdown_at_ms(active);
+    Ok(paused_for_ms)
 }
 
-fn pre_alert_notification_body(threshold_sec: u64) -> String {
-    if threshold_sec % 60 == 0 {
-        let minutes = threshold_sec / 60;
-        format!(
-            "자동 종료까지 {minutes}분 남았습니다. 지금 취소하거나 미룰 수 있습니다. (앱/트레이)"
-        )
-    } else {
-        format!(
-            "자동 종료까지 {threshold_sec}초 남았습니다. 지금 취소하거나 미룰 수 있습니다. (앱/트레이)"
-        )
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PostponeTarget {
+    Minutes(u64),
+    Until(i64),
 }
 
-fn final_warning_notification_body(final_warning_sec: u64) -> String {
-    format!(
-        "종료 {final_warning_sec}초 전입니다. 지금 취소하지 않으면 종료가 진행됩니다. 앱/트레이에서 취소 또는 미루기가 가능합니다."
-    )
-}
+fn postpone_active(
+    active: &mut ActiveSchedule,
+    target: PostponeTarget,
+    now: i64,
+) -> Result<u64, String> {
+    if is_shutdown_execution_started(active) {
+        return Err("shutdown has already started; cannot postpone".to_string());
+    }
+    if active.paused_at_ms.is_some() {
+        let _ = resume_active(active, now)?;
+    }
 
-fn process_exit_final_warning_notification_body(final_warning_sec: u64) -> String {
-    format!(
-        "프로세스 종료가 감지되어 최종 경고가 시작되었습니다. 종료 {final_warning_sec}초 전입니다. 앱/트레이에서 취소 또는 미루기가 가능합니다."
-    )
-}
-
-fn is_shutdown_execution_started(schedule: &ActiveSchedule) -> bool {
-    matches!(schedule.status, ScheduleStatus::ShuttingDown)
-        || schedule.shutdown_initiated_at_ms.is_some()
-}
-
-fn try_mark_shutdown_initiated(schedule: &mut ActiveSchedule, now: i64) -> bool {
-    if is_shutdown_execution_started(schedule) {
-        return false;
-    }
-
-    if !matches!(schedule.status, ScheduleStatus::FinalWarning) {
-        return false;
-    }
-
-    let Some(started_at_ms) = schedule.final_warning_started_at_ms else {
-        return false;
+    let has_timer =
+        active.post_delay_started_at_ms.is_some() || !is_condition_mode(&active.mode);
+    // A running final warning is postponed from now; otherwise the pending trigger or snooze moves.
+    let base_ms = if active.status != ScheduleStatus::Armed {
+        now
+    } else if has_timer {
+        active.trigger_at_ms.unwrap_or(now).max(now)
+    } else {
+        active.snooze_until_ms.unwrap_or(now).max(now)
+    };
+    let next_ms = match target {
+        PostponeTarget::Minutes(minutes) => {
+            if minutes == 0 || minutes > 24 * 60 {
+                return Err("minutes must be within 1..=1440".to_string());
+            }
+            base_ms + (minutes as i64) * 60 * 1000
+        }
+        PostponeTarget::Until(until_ms) => {
+            if until_ms <= base_ms {
+                return Err("postpone target must be later than the current schedule".to_string());
+            }
+            until_ms
+        }
     };
 
-    let elapsed_ms = now - started_at_ms;
-    if elapsed_ms < (schedule.final_warning_duration_sec as i64) * 1000 {
-        return false;
+    if has_timer {
+        active.trigger_at_ms = Some(next_ms);
+    } else {
+        active.snooze_until_ms = Some(next_ms);
+        active.process_missing_since_ms = None;
+        active.condition_met_since_ms = None;
     }
+    active.status = ScheduleStatus::Armed;
+    active.final_warning_started_at_ms = None;
+    active.shutdown_initiated_at_ms = None;
+    let fired_alerts = active.fired_alerts.clone();
+    settle_fired_alerts(active, &fired_alerts, now);
+    let _ = sync_shutdown_at_ms(active);
 
-    schedule.status = ScheduleStatus::ShuttingDown;
-    schedule.shutdown_initiated_at_ms = Some(now);
-    true
+    // Counted as requested: the final warning a snooze cuts short is not extra delay.
+    let delay_sec = ((next_ms - base_ms) / 1000) as u64;
+    active.postpone_count = active.postpone_count.saturating_add(1);
+    active.postponed_total_sec = active.postponed_total_sec.saturating_add(delay_sec);
+    Ok(delay_sec)
 }
 
-fn normalize_alerts(alerts: &[u64]) -> Vec<u64> {
-    let mut values = alerts
+// Moving a trigger must never announce "10 minutes remaining" with 5 left.
+fn settle_fired_alerts(active: &mut ActiveSchedule, keep_fired: &[u64], now: i64) {
+    let remaining_sec = active
+        .trigger_at_ms
+        .map(|_| remaining_trigger_ms(active, now) / 1000);
+    active.fired_alerts = active
+        .pre_alerts
         .iter()
         .copied()
-        .filter(|seconds| *seconds > 0 && *seconds <= 24 * 60 * 60)
-        .collect::<Vec<u64>>();
-
-    values.sort_unstable();
-    values.dedup();
-    values.reverse();
+        .filter(|threshold| {
+            let passed = remaining_sec
+                .map(|remaining_sec| *threshold as i64 > remaining_sec)
+                .unwrap_or(false);
+            passed || keep_fired.contains(threshold)
+        })
+        .collect();
+}
 
-    if values.is_empty() {
-        vec![600, 300, 60]
-    } else {
-        values
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum PostponeErrorCode {
+    CountLimit,
+    DelayLimit,
+    Deadline,
+    Rejected,
 }
 
-fn state_companion_path(path: &Path, suffix: &str) -> PathBuf {
-    let file_name = path
-        .file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or(STATE_FILE_NAME);
-    path.with_file_name(format!("{file_name}{suffix}"))
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PostponeError {
+    code: PostponeErrorCode,
+    message: String,
 }
 
-fn backup_state_path(path: &Path) -> PathBuf {
-    state_companion_path(path, ".bak")
+impl PostponeError {
+    fn new(code: PostponeErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn is_limit(&self) -> bool {
+        self.code != PostponeErrorCode::Rejected
+    }
 }
 
-fn next_corrupt_state_path(path: &Path) -> PathBuf {
-    let file_name = path
-        .file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or(STATE_FILE_NAME);
-    let base = format!("{file_name}.corrupt-{}", now_ms());
-    let mut candidate = path.with_file_name(base.clone());
-    let mut suffix = 1;
-    while candidate.exists() {
-        candidate = path.with_file_name(format!("{base}-{suffix}"));
-        suffix += 1;
+impl From<String> for PostponeError {
+    fn from(message: String) -> Self {
+        Self::new(PostponeErrorCode::Rejected, message)
     }
-    candidate
 }
 
-fn load_store(path: &Path) -> Result<LoadStoreOutcome, String> {
-    let content = match fs::read_to_string(path) {
-        Ok(content) => content,
-        Err(error) if error.kind() == io::ErrorKind::NotFound => {
-            return Ok(LoadStoreOutcome {
-                store: SchedulerStore::default(),
-                needs_persist: false,
-                startup_notice: None,
-            });
+fn postpone_deadline_ms(settings: &AppSettings, active: &ActiveSchedule) -> Option<i64> {
+    settings
+        .postpone_deadline_local_time
+        .as_deref()
+        .and_then(|deadline| compute_local_target_after_ms(deadline, active.armed_at_ms).ok())
+}
+
+fn postpone_block_reason(
+    settings: &AppSettings,
+    active: &ActiveSchedule,
+    now: i64,
+) -> Option<PostponeError> {
+    if let Some(limit) = settings.max_postpone_count {
+        if active.postpone_count >= limit {
+            return Some(PostponeError::new(
+                PostponeErrorCode::CountLimit,
+                format!("snooze limit reached ({} of {limit} used)", active.postpone_count),
+            ));
         }
-        Err(error) => {
-            return Err(format!("failed to read state file: {error}"));
+    }
+    if let Some(limit_sec) = settings.max_postponed_total_sec {
+        if active.postponed_total_sec >= limit_sec {
+            return Some(PostponeError::new(
+                PostponeErrorCode::DelayLimit,
+                format!("maximum total delay of {}m already used", limit_sec / 60),
+            ));
         }
-    };
-
-    match serde_json::from_str::<PersistedState>(&content) {
-        Ok(from_disk) => Ok(LoadStoreOutcome {
-            store: SchedulerStore::from_persisted(from_disk),
-            needs_persist: false,
-            startup_notice: None,
-        }),
-        Err(parse_error) => {
-            let corrupt_path = next_corrupt_state_path(path);
-            fs::rename(path, &corrupt_path).map_err(|error| {
+    }
+    if let Some(deadline_ms) = postpone_deadline_ms(settings, active) {
+        if now >= deadline_ms {
+            return Some(PostponeError::new(
+                PostponeErrorCode::Deadline,
                 format!(
-                    "failed to quarantine corrupted state file to {}: {error}",
-                    corrupt_path.display()
-                )
-            })?;
-
-            let backup_path = backup_state_path(path);
-            let mut reason = format!(
-                "failed to parse state file: {parse_error}; quarantined at {}",
-                corrupt_path.display()
-            );
-            let mut store = SchedulerStore::default();
-            let mut recovered_from_backup = false;
-
-            if backup_path.exists() {
-                match fs::read_to_string(&backup_path) {
-                    Ok(backup_content) => match serde_json::from_str::<PersistedState>(&backup_content)
-                    {
-                        Ok(backup_state) => {
-                            store = SchedulerStore::from_persisted(backup_state);
-                            recovered_from_backup = true;
-                        }
-                        Err(error) => {
-                            reason.push_str(&format!(
-                                "; backup parse failed ({}): {error}",
-                                backup_path.display()
-                            ));
-                        }
-                    },
-                    Err(error) => {
-                        reason.push_str(&format!(
-                            "; backup read failed ({}): {error}",
-                            backup_path.display()
-                        ));
-                    }
-                }
-            } else {
-                reason.push_str("; backup not found");
-            }
-
-            push_event(
-                &mut store,
-                None,
-                "state_parse_failed",
-                "error",
-                Some(reason),
-            );
-
-            if recovered_from_backup {
-                push_event(
-                    &mut store,
-                    None,
-                    "state_restored_from_backup",
-                    "ok",
-                    Some(format!("restored state from {}", backup_path.display())),
-                );
-            }
-
-            let startup_notice = if recovered_from_backup {
-                Some(
-                    "상태 파일 손상을 감지해 마지막 정상 백업(.bak)으로 복구했습니다. 현재 스케줄을 확인해 주세요."
-                        .to_string(),
-                )
-            } else {
-                Some(
-                    "상태 파일 손상을 감지해 기본 상태로 복구했습니다. 기존 스케줄은 안전을 위해 복원되지 않았습니다."
-                        .to_string(),
-                )
-            };
-
-            Ok(LoadStoreOutcome {
-                store,
-                needs_persist: true,
-                startup_notice,
-            })
+                    "snoozing is not allowed after {}",
+                    format_local_timestamp_ms(deadline_ms)
+                ),
+            ));
         }
     }
+    None
 }
 
-fn persist_store(path: &Path, store: &SchedulerStore) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|error| format!("failed to create state directory: {error}"))?;
-    }
-
-    let serialized = serde_json::to_string_pretty(&store.to_persisted())
-        .map_err(|error| format!("failed to serialize state: {error}"))?;
-    let temp_path = state_companion_path(path, ".tmp");
-    let backup_path = backup_state_path(path);
-
-    fs::write(&temp_path, serialized)
-        .map_err(|error| format!("failed to write temp state file: {error}"))?;
-    if let Ok(file) = fs::OpenOptions::new().write(true).open(&temp_path) {
-        let _ = file.sync_all();
+fn check_postpone_limits(
+    settings: &AppSettings,
+    before: &ActiveSchedule,
+    after: &ActiveSchedule,
+    now: i64,
+) -> Result<(), PostponeError> {
+    if let Some(error) = postpone_block_reason(settings, before, now) {
+        return Err(error);
     }
-
-    let had_existing_state = path.exists();
-    if had_existing_state {
-        if backup_path.exists() {
-            fs::remove_file(&backup_path)
-                .map_err(|error| format!("failed to remove previous backup file: {error}"))?;
+    if let Some(limit_sec) = settings.max_postponed_total_sec {
+        if after.postponed_total_sec > limit_sec {
+            let left_sec = limit_sec.saturating_sub(before.postponed_total_sec);
+            return Err(PostponeError::new(
+                PostponeErrorCode::DelayLimit,
+                format!(
+                    "this would exceed the maximum total delay of {}m ({}m {}s left)",
+                    limit_sec / 60,
+                    left_sec / 60,
+                    left_sec % 60
+                ),
+            ));
         }
-        fs::rename(path, &backup_path)
-            .map_err(|error| format!("failed to move state file to backup: {error}"))?;
     }
-
-    if let Err(error) = fs::rename(&temp_path, path) {
-        let _ = fs::remove_file(&temp_path);
-        if had_existing_state && !path.exists() && backup_path.exists() {
-            let _ = fs::rename(&backup_path, path);
+    if let Some(deadline_ms) = postpone_deadline_ms(settings, before) {
+        let resumes_at_ms = after.shutdown_at_ms.or(after.snooze_until_ms);
+        if resumes_at_ms.is_some_and(|resumes_at_ms| resumes_at_ms > deadline_ms) {
+            return Err(PostponeError::new(
+                PostponeErrorCode::Deadline,
+                format!(
+                    "cannot postpone past {}",
+                    format_local_timestamp_ms(deadline_ms)
+                ),
+            ));
         }
-        return Err(format!("failed to replace state file atomically: {error}"));
     }
-
     Ok(())
 }
 
-fn push_event(
-    store: &mut SchedulerStore,
-    schedule_id: Option<String>,
-    event_type: &str,
-    result: &str,
-    reason: Option<String>,
-) {
-    store.history.push(ExecutionEvent {
-        schedule_id,
-        event_type: event_type.to_string(),
-        timestamp_ms: now_ms(),
-        result: result.to_string(),
-        reason,
-    });
-
-    if store.history.len() > HISTORY_LIMIT {
-        let overflow = store.history.len() - HISTORY_LIMIT;
-        store.history.drain(0..overflow);
+fn sync_shutdown_at_ms(active: &mut ActiveSchedule) -> bool {
+    let computed = compute_shutdown_at_ms(active);
+    if active.shutdown_at_ms != computed {
+        active.shutdown_at_ms = computed;
+        true
+    } else {
+        false
     }
 }
 
-fn enforce_no_resume_in_mvp(store: &mut SchedulerStore) -> bool {
-    let Some(active) = store.active.take() else {
-        return false;
-    };
+fn sanitize_active_for_persist(mut active: ActiveSchedule) -> ActiveSchedule {
+    active.final_warning_duration_sec = normalize_final_warning_sec(active.final_warning_duration_sec);
+    if matches!(active.status, ScheduleStatus::ShuttingDown) {
+        active.status = ScheduleStatus::FinalWarning;
+    }
+    let _ = sync_shutdown_at_ms(&mut active);
+    active
+}
 
-    push_event(
-        store,
-        Some(active.id),
-        "resume_not_supported",
-        "ok",
-        Some(
-            "MVP 정책(NO_RESUME_IN_MVP)에 따라 앱 시작 시 이전 활성 스케줄 자동 복구를 지원하지 않아 해제되었습니다."
-                .to_string(),
-        ),
-    );
-
-    true
-}
-
-fn compute_next_local_target_ms(target_local_time: &str) -> Result<i64, String> {
-    let parsed = NaiveTime::parse_from_str(target_local_time, "%H:%M")
-        .map_err(|_| "target time must match HH:MM format".to_string())?;
-
-    let now = Local::now();
-    let today = now.date_naive();
-
-    let mut candidate = today.and_time(parsed);
-    let mut target = match Local.from_local_datetime(&candidate) {
-        LocalResult::Single(value) => value,
-        LocalResult::Ambiguous(earliest, _) => earliest,
-        LocalResult::None => return Err("unable to resolve local target time".to_string()),
+fn sanitize_active_from_persist(
+    mut active: ActiveSchedule,
+    fallback_final_warning_sec: u64,
+) -> ActiveSchedule {
+    if matches!(active.status, ScheduleStatus::ShuttingDown) {
+        active.status = ScheduleStatus::FinalWarning;
+        active.shutdown_initiated_at_ms = None;
+    }
+    let was_invalid = !(FINAL_WARNING_MIN_SEC..=FINAL_WARNING_MAX_SEC)
+        .contains(&active.final_warning_duration_sec);
+    active.final_warning_duration_sec = if was_invalid {
+        final_warning_sec_for_mode(&active.mode, fallback_final_warning_sec)
+    } else {
+        active.final_warning_duration_sec
     };
+    let _ = sync_shutdown_at_ms(&mut active);
+    active
+}
 
-    if target <= now {
-        let tomorrow = today
-            .checked_add_days(Days::new(1))
-            .ok_or("failed to compute tomorrow date".to_string())?;
-        candidate = tomorrow.and_time(parsed);
-        target = match Local.from_local_datetime(&candidate) {
-            LocalResult::Single(value) => value,
-            LocalResult::Ambiguous(earliest, _) => earliest,
-            LocalResult::None => return Err("unable to resolve next local target time".to_string()),
-        };
+fn pre_alert_notification_body(threshold_sec: u64) -> String {
+    if threshold_sec % 60 == 0 {
+        let minutes = threshold_sec / 60;
+        format!(
+            "자동 종료까지 {minutes}분 남았습니다. 지금 취소하거나 미룰 수 있습니다. (앱/트레이)"
+        )
+    } else {
+        format!(
+            "자동 종료까지 {threshold_sec}초 남았습니다. 지금 취소하거나 미룰 수 있습니다. (앱/트레이)"
+        )
     }
-
-    Ok(target.timestamp_millis())
 }
 
-fn build_active_schedule(
-    store: &mut SchedulerStore,
-    request: ScheduleRequest,
-) -> Result<ActiveSchedule, String> {
-    let now = now_ms();
-    let mode = request.mode;
-    let pre_alerts = normalize_alerts(
-        &request
-            .pre_alerts
-            .unwrap_or_else(|| store.settings.default_pre_alerts.clone()),
-    );
-    let process_stable_sec = request.process_stable_sec.unwrap_or(10).clamp(5, 600);
+fn final_warning_notification_body(final_warning_sec: u64) -> String {
+    format!(
+        "종료 {final_warning_sec}초 전입니다. 지금 취소하지 않으면 종료가 진행됩니다. 앱/트레이에서 취소 또는 미루기가 가능합니다."
+    )
+}
 
-    let (
-        trigger_at_ms,
-        target_local_time,
-        target_tz_offset_minutes,
-        summary,
-        process_selector,
-    ) = match mode {
-        ScheduleMode::Countdown => {
-            let duration = request
-                .duration_sec
-                .ok_or("durationSec is required for countdown mode".to_string())?;
-            if duration == 0 {
-                return Err("durationSec must be greater than zero".to_string());
-            }
-            let trigger = now
-                .checked_add((duration as i64) * 1000)
-                .ok_or("duration is too large".to_string())?;
-            (
-                Some(trigger),
-                None,
-                None,
-                format!("Countdown {}m {}s", duration / 60, duration % 60),
-                None,
-            )
-        }
-        ScheduleMode::SpecificTime => {
-            let target = request
-                .target_local_time
-                .ok_or("targetLocalTime is required for specificTime mode".to_string())?;
-            let trigger = compute_next_local_target_ms(&target)?;
-            (
-                Some(trigger),
-                Some(target.clone()),
-                Some(Local::now().offset().local_minus_utc() / 60),
-                format!("Shutdown at local time {target}"),
-                None,
-            )
-        }
-        ScheduleMode::ProcessExit => {
-            let selector = normalize_and_validate_process_selector(request.process_selector.as_ref())
-                .map_err(|error| {
-                    if error == "process selector is missing" {
-                        "processSelector is required for processExit mode".to_string()
-                    } else {
-                        error
-                    }
-                })?;
+fn process_exit_final_warning_notification_body(final_warning_sec: u64) -> String {
+    format!(
+        "프로세스 종료가 감지되어 최종 경고가 시작되었습니다. 종료 {final_warning_sec}초 전입니다. 앱/트레이에서 취소 또는 미루기가 가능합니다."
+    )
+}
 
-            let descriptor = selector
-                .name
-                .clone()
-                .or_else(|| selector.pid.map(|pid| format!("PID {pid}")))
-                .ok_or("process selector is empty".to_string())?;
-            (
-                None,
-                None,
-                None,
-                format!("Shutdown when {descriptor} exits (stable {process_stable_sec}s)"),
-                Some(selector),
-            )
-        }
+fn condition_final_warning_notification_body(mode: &ScheduleMode, final_warning_sec: u64) -> String {
+    let cause = match mode {
+        ScheduleMode::LaunchAndWatch => "실행한 명령이 끝나",
+        ScheduleMode::SystemdUnit => "systemd 유닛 작업이 끝나",
+        ScheduleMode::NetworkIdle => "감시 대상의 네트워크 연결이 모두 끊겨",
+        ScheduleMode::PortClosed => "감시 중인 포트가 닫혀",
+        ScheduleMode::LogPattern => "로그에 완료 문구가 기록되어",
+        ScheduleMode::MarkerFile => "표시 파일 상태가 바뀌어",
+        ScheduleMode::HttpPoll => "상태 엔드포인트가 완료를 알려",
+        ScheduleMode::MediaIdle => "미디어 재생이 멈춰",
+        ScheduleMode::BatteryLow => "배터리 잔량이 기준 아래로 떨어져",
+        ScheduleMode::ThermalHigh => "온도가 기준을 계속 넘어",
+        _ => "감시 조건이 충족되어",
     };
+    format!(
+        "{cause} 최종 경고가 시작되었습니다. 종료 {final_warning_sec}초 전입니다. 앱/트레이에서 취소 또는 미루기가 가능합니다."
+    )
+}
 
-    store.id_seq += 1;
-    let id = format!("sch-{}-{}", now, store.id_seq);
-
-    let mut next = ActiveSchedule {
-        id,
-        mode,
-        summary,
-        armed_at_ms: now,
-        trigger_at_ms,
-        target_local_time,
-        target_tz_offset_minutes,
-        pre_alerts,
-        fired_alerts: Vec::new(),
-        process_selector,
-        process_tree_pids: Vec::new(),
-        process_stable_sec,
-        process_missing_since_ms: None,
-        snooze_until_ms: None,
-        process_match_degraded_logged: false,
-        status: ScheduleStatus::Armed,
-        final_warning_started_at_ms: None,
-        final_warning_duration_sec: normalize_final_warning_sec(store.settings.final_warning_sec),
-        shutdown_at_ms: None,
-        shutdown_initiated_at_ms: None,
+fn post_condition_delay_notification_body(delay_sec: u64) -> String {
+    let delay = if delay_sec % 60 == 0 {
+        format!("{}분", delay_sec / 60)
+    } else {
+        format!("{delay_sec}초")
     };
-    let _ = sync_shutdown_at_ms(&mut next);
-    Ok(next)
+    format!(
+        "감시 조건이 충족되었습니다. {delay} 후 최종 경고가 시작됩니다. 앱/트레이에서 취소 또는 미루기가 가능합니다."
+    )
 }
 
-fn resolve_state_path(app: &AppHandle) -> PathBuf {
-    let mut dir = app
-        .path()
-        .app_data_dir()
-        .unwrap_or_else(|_| PathBuf::from("."));
-    dir.push(STATE_FILE_NAME);
-    dir
+fn deadline_final_warning_notification_body(final_warning_sec: u64) -> String {
+    format!(
+        "감시 조건과 관계없이 마감 시각에 도달해 최종 경고가 시작되었습니다. 종료 {final_warning_sec}초 전입니다. 앱/트레이에서 취소 또는 미루기가 가능합니다."
+    )
 }
 
-fn send_desktop_notification(app: &AppHandle, title: &str, body: &str) {
-    let _ = app.notification().builder().title(title).body(body).show();
+fn is_condition_mode(mode: &ScheduleMode) -> bool {
+    matches!(
+        mode,
+        ScheduleMode::ProcessExit
+            | ScheduleMode::LaunchAndWatch
+            | ScheduleMode::SystemdUnit
+            | ScheduleMode::NetworkIdle
+            | ScheduleMode::PortClosed
+            | ScheduleMode::LogPattern
+            | ScheduleMode::MarkerFile
+            | ScheduleMode::HttpPoll
+            | ScheduleMode::MediaIdle
+            | ScheduleMode::BatteryLow
+            | ScheduleMode::ThermalHigh
+    )
 }
 
-fn normalize_selector_text(value: Option<&String>) -> Option<String> {
-    value
-        .map(|item| item.trim())
-        .filter(|item| !item.is_empty())
-        .map(|item| item.to_string())
+fn condition_watch_label(mode: &ScheduleMode) -> &'static str {
+    match mode {
+        ScheduleMode::LaunchAndWatch => "실행한 명령 종료 감시",
+        ScheduleMode::SystemdUnit => "systemd 유닛 감시",
+        ScheduleMode::NetworkIdle => "네트워크 연결 종료 감시",
+        ScheduleMode::PortClosed => "포트 닫힘 감시",
+        ScheduleMode::LogPattern => "로그 완료 문구 감시",
+        ScheduleMode::MarkerFile => "표시 파일 감시",
+        ScheduleMode::HttpPoll => "상태 엔드포인트 감시",
+        ScheduleMode::MediaIdle => "미디어 재생 감시",
+        ScheduleMode::BatteryLow => "배터리 잔량 감시",
+        ScheduleMode::ThermalHigh => "온도 감시",
+        _ => "프로세스 종료 감시",
+    }
 }
 
-fn no_fail_open_process_exit_reason(error: &str) -> String {
-    format!("NO_FAIL_OPEN_PROCESS_EXIT: process-exit selector invalid ({error}); cancelled for safety")
+fn not_before_pending(active: &ActiveSchedule, now: i64) -> bool {
+    active
+        .not_before_ms
+        .map(|not_before_ms| now < not_before_ms)
+        .unwrap_or(false)
 }
 
-fn reset_process_exit_on_selector_failure(active: &mut ActiveSchedule) {
-    active.process_missing_since_ms = None;
-    active.process_tree_pids.clear();
-    active.final_warning_started_at_ms = None;
-    active.shutdown_initiated_at_ms = None;
+// Past the deadline a final warning no longer follows the watched condition.
+fn deadline_reached(active: &ActiveSchedule, now: i64) -> bool {
+    active
+        .deadline_ms
+        .map(|deadline_ms| now >= deadline_ms)
+        .unwrap_or(false)
 }
 
-fn apply_process_exit_fail_safe_cancel(
-    store: &mut SchedulerStore,
-    schedule_id: &str,
-    reason: String,
-) {
-    push_event(
-        store,
-        Some(schedule_id.to_string()),
-        "failed",
-        "error",
-        Some(reason),
-    );
-    if store
-        .active
-        .as_ref()
-        .map(|active| active.id.as_str())
-        == Some(schedule_id)
+fn enter_deadline_final_warning(active: &mut ActiveSchedule, now: i64) -> bool {
+    // A paused schedule must never enter final warning, not even at its deadline.
+    if active.status != ScheduleStatus::Armed
+        || active.paused_at_ms.is_some()
+        || !deadline_reached(active, now)
     {
-        store.active = None;
+        return false;
     }
+    active.status = ScheduleStatus::FinalWarning;
+    active.final_warning_started_at_ms = Some(now);
+    active.process_missing_since_ms = None;
+    active.condition_met_since_ms = None;
+    active.shutdown_initiated_at_ms = None;
+    true
 }
 
-fn normalize_and_validate_process_selector(
-    selector: Option<&ProcessSelector>,
-) -> Result<ProcessSelector, String> {
-    let Some(selector) = selector else {
-        return Err("process selector is missing".to_string());
+fn start_post_condition_delay(active: &mut ActiveSchedule, now: i64) -> bool {
+    let Some(delay_sec) = active.post_condition_delay_sec else {
+        return false;
     };
+    active.post_delay_started_at_ms = Some(now);
+    active.trigger_at_ms = Some(now.saturating_add((delay_sec as i64) * 1000));
+    active.fired_alerts.clear();
+    active.condition_met_since_ms = None;
+    active.process_missing_since_ms = None;
+    true
+}
 
-    let mut normalized = selector.clone();
-    normalized.name = normalize_selector_text(normalized.name.as_ref());
-    normalized.executable = normalize_selector_text(normalized.executable.as_ref());
-    normalized.cmdline_contains = normalize_selector_text(normalized.cmdline_contains.as_ref());
-
-    if normalized.pid.is_none() && normalized.name.is_none() {
-        return Err("process selector is empty".to_string());
-    }
-
-    let shell_like = normalized
-        .name
-        .as_deref()
-        .map(is_shell_like_process_name)
-        .unwrap_or(false);
-    let has_advanced = normalized.executable.is_some() || normalized.cmdline_contains.is_some();
-    if shell_like && !has_advanced {
-        return Err(
-            "shell 계열 프로세스는 오탐 방지를 위해 executable 또는 cmdlineContains가 필요합니다."
-                .to_string(),
-        );
+fn condition_stable_window_ms(active: &ActiveSchedule) -> i64 {
+    match active.mode {
+        ScheduleMode::LaunchAndWatch | ScheduleMode::LogPattern => 0,
+        _ => (active.process_stable_sec as i64) * 1000,
     }
-
-    Ok(normalized)
 }
 
-fn normalize_selector_path(value: Option<&String>) -> Option<String> {
-    normalize_selector_text(value).map(|path| path.replace('\\', "/").to_lowercase())
-}
+fn advance_condition(
+    active: &mut ActiveSchedule,
+    probe: ConditionProbe,
+    now: i64,
+    held: bool,
+) -> ConditionTransition {
+    match probe {
+        ConditionProbe::Skip { reason } => ConditionTransition::Skipped { reason },
+        ConditionProbe::Failed { reason } => ConditionTransition::Failed { reason },
+        // Neither reverts final warning nor confirms the condition.
+        ConditionProbe::Unavailable => match active.status {
+            ScheduleStatus::Armed if active.condition_met_since_ms.take().is_some() => {
+                ConditionTransition::WindowReset
+            }
+            _ => ConditionTransition::Unchanged,
+        },
+        ConditionProbe::Pending => match active.status {
+            ScheduleStatus::FinalWarning => {
+                active.status = ScheduleStatus::Armed;
+                active.final_warning_started_at_ms = None;
+                active.condition_met_since_ms = None;
+                active.shutdown_initiated_at_ms = None;
+                let detail = active
+                    .condition_detail
+                    .clone()
+                    .unwrap_or_else(|| "condition no longer met".to_string());
+                ConditionTransition::Reverted {
+                    reason: format!("watched condition is pending again ({detail})"),
+                }
+            }
+            _ if active.condition_met_since_ms.take().is_some() => ConditionTransition::WindowReset,
+            _ => ConditionTransition::Unchanged,
+        },
+        ConditionProbe::Met { reason } => {
+            if !matches!(active.status, ScheduleStatus::Armed) {
+                return ConditionTransition::Unchanged;
+            }
 
-fn is_shell_like_process_name(name: &str) -> bool {
-    let normalized = name.to_lowercase();
-    normalized.contains("powershell")
-        || normalized.contains("pwsh")
-        || normalized == "bash"
-        || normalized.ends_with("/bash")
-        || normalized == "zsh"
-        || normalized.ends_with("/zsh")
-        || normalized == "sh"
-        || normalized.ends_with("/sh")
+            let opened = active.condition_met_since_ms.is_none();
+            let met_since = *active.condition_met_since_ms.get_or_insert(now);
+            let snoozed = active
+                .snooze_until_ms
+                .map(|snooze_until_ms| now < snooze_until_ms)
+                .unwrap_or(false);
+            let elapsed = now - met_since >= condition_stable_window_ms(active);
+            if elapsed && !snoozed && not_before_pending(active, now) {
+                ConditionTransition::Deferred
+            } else if elapsed && !snoozed && start_post_condition_delay(active, now) {
+                ConditionTransition::DelayStarted { reason }
+            } else if elapsed && !snoozed && held {
+                ConditionTransition::Held
+            } else if elapsed && !snoozed {
+                active.status = ScheduleStatus::FinalWarning;
+                active.final_warning_started_at_ms = Some(now);
+                active.condition_met_since_ms = None;
+                active.shutdown_initiated_at_ms = None;
+                ConditionTransition::FinalWarning { reason }
+            } else if opened {
+                ConditionTransition::WindowOpened
+            } else {
+                ConditionTransition::Unchanged
+            }
+        }
+    }
 }
 
-fn run_shutdown_command(settings: &AppSettings) -> Result<ShutdownDispatchReport, String> {
-    let force_simulate = std::env::var("AUTOSD_FORCE_SIMULATE_ONLY")
-        .map(|value| {
-            let normalized = value.trim().to_ascii_lowercase();
-            !matches!(normalized.as_str(), "" | "0" | "false" | "off")
-        })
-        .unwrap_or(false)
-        || std::env::var("CI")
-            .map(|value| value.trim().eq_ignore_ascii_case("true"))
-            .unwrap_or(false);
-
-    #[cfg(target_os = "windows")]
-    let dispatch = ShutdownDispatchReport {
-        command_line: format!("shutdown /s /t {WINDOWS_ABORTABLE_SHUTDOWN_SEC}"),
-        abort_hint: Some("shutdown /a".to_string()),
-        dry_run: settings.simulate_only || force_simulate,
+fn missing_watch_reason(mode: &ScheduleMode) -> String {
+    let watch = match mode {
+        ScheduleMode::SystemdUnit => "systemd unit watch",
+        ScheduleMode::PortClosed => "port watch",
+        ScheduleMode::LogPattern => "log watch",
+        ScheduleMode::MarkerFile => "marker watch",
+        ScheduleMode::HttpPoll => "http watch",
+        ScheduleMode::BatteryLow => "battery threshold",
+        ScheduleMode::ThermalHigh => "thermal watch",
+        _ => "watch configuration",
     };
+    format!("NO_FAIL_OPEN_WATCH_MISSING: {watch} is missing; cancelled for safety")
+}
 
-    #[cfg(target_os = "macos")]
-    let dispatch = ShutdownDispatchReport {
-        command_line: "osascript -e \"tell application \\\"System Events\\\" to shut down\""
-            .to_string(),
-        abort_hint: None,
-        dry_run: settings.simulate_only || force_simulate,
-    };
+fn launch_failed_reason(error: &str) -> String {
+    format!("LAUNCH_FAILED: {error}; cancelled for safety")
+}
 
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    let dispatch = ShutdownDispatchReport {
-        command_line: "shutdown command unsupported on this OS".to_string(),
-        abort_hint: None,
-        dry_run: settings.simulate_only || force_simulate,
-    };
+fn launch_condition_probe(
+    active: &mut ActiveSchedule,
+    poll: Option<&LaunchPoll>,
+    now: i64,
+    pending_events: &mut Vec<(String, Option<String>)>,
+) -> ConditionProbe {
+    match poll {
+        Some(LaunchPoll::Running) => return ConditionProbe::Pending,
+        Some(LaunchPoll::Failed(error)) => {
+            return ConditionProbe::Failed {
+                reason: launch_failed_reason(error),
+            };
+        }
+        Some(LaunchPoll::Exited(exit)) => {
+            active.launch_exit_code = exit.exit_code;
+            active.launch_exited_at_ms = Some(now);
+            pending_events.push((
+                "launch_exited".to_string(),
+                Some(launch_exit_event_reason(exit.exit_code, &exit.stderr_tail)),
+            ));
+        }
+        None => {}
+    }
 
-    if dispatch.dry_run {
-        return Ok(dispatch);
+    if active.launch_exited_at_ms.is_none() {
+        return ConditionProbe::Pending;
     }
 
-    #[cfg(target_os = "windows")]
-    {
-        let timeout_arg = WINDOWS_ABORTABLE_SHUTDOWN_SEC.to_string();
-        let status = Command::new("shutdown")
-            .args(["/s", "/t", timeout_arg.as_str()])
-            .status()
-            .map_err(|error| format!("failed to run windows shutdown command: {error}"))?;
-        if status.success() {
-            return Ok(dispatch);
+    let policy = active.launch_exit_policy.unwrap_or(LaunchExitPolicy::Always);
+    let exit = launch_exit_label(active.launch_exit_code);
+    active.condition_detail = Some(exit.clone());
+    if launch_exit_policy_allows(policy, active.launch_exit_code == Some(0)) {
+        ConditionProbe::Met {
+            reason: format!("launched command finished ({exit}); entered shutdown waiting mode"),
+        }
+    } else {
+        ConditionProbe::Skip {
+            reason: format!(
+                "launch exit policy {} not met ({exit}); shutdown skipped",
+                launch_exit_policy_label(policy)
+            ),
         }
-        return Err(format!("windows shutdown failed with status: {status}"));
     }
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        let status = Command::new("osascript")
-            .args(["-e", "tell application \"System Events\" to shut down"])
-            .status()
-            .map_err(|error| format!("failed to run macOS shutdown command: {error}"))?;
-        if status.success() {
-            return Ok(dispatch);
+fn systemd_unit_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<Option<UnitState>, String>,
+) -> ConditionProbe {
+    let Some(watch) = active.systemd_unit.clone() else {
+        return ConditionProbe::Failed {
+            reason: missing_watch_reason(&ScheduleMode::SystemdUnit),
+        };
+    };
+    let unit = watch.unit.as_str();
+
+    match observed {
+        Err(error) => {
+            active.condition_detail = Some(format!("unit state unavailable: {error}"));
+            ConditionProbe::Unavailable
+        }
+        Ok(None) => {
+            active.condition_detail = Some("not loaded".to_string());
+            if !active.unit_seen_active {
+                ConditionProbe::Pending
+            } else if watch.require_success {
+                ConditionProbe::Skip {
+                    reason: format!(
+                        "systemd unit {unit} was unloaded before its result could be verified; shutdown skipped"
+                    ),
+                }
+            } else {
+                ConditionProbe::Met {
+                    reason: format!("systemd unit {unit} finished and was unloaded; entered shutdown waiting mode"),
+                }
+            }
+        }
+        Ok(Some(state)) => {
+            active.condition_detail = Some(state.label());
+            match state.phase() {
+                UnitPhase::Running => {
+                    active.unit_seen_active = true;
+                    ConditionProbe::Pending
+                }
+                _ if !active.unit_seen_active => ConditionProbe::Pending,
+                UnitPhase::Failed if watch.require_success => ConditionProbe::Skip {
+                    reason: format!(
+                        "systemd unit {unit} failed ({}); shutdown skipped because success is required",
+                        state.label()
+                    ),
+                },
+                UnitPhase::Inactive | UnitPhase::Failed => ConditionProbe::Met {
+                    reason: format!(
+                        "systemd unit {unit} reached {}; entered shutdown waiting mode",
+                        state.label()
+                    ),
+                },
+            }
         }
-        return Err(format!("macOS shutdown failed with status: {status}"));
     }
+}
 
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    {
-        Err("unsupported OS for shutdown in this MVP".to_string())
+fn network_idle_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<NetworkActivity, String>,
+) -> ConditionProbe {
+    match observed {
+        Err(error) => {
+            active.condition_detail = Some(format!("connections unavailable: {error}"));
+            ConditionProbe::Unavailable
+        }
+        Ok(activity) => {
+            let had_match = !active.process_tree_pids.is_empty();
+            active.process_tree_pids = activity.matched_pids.clone();
+            active.network_connection_count = Some(activity.established);
+            if activity.matched_pids.is_empty() {
+                active.condition_detail = Some("no matching process".to_string());
+                // A selector that never matched is most likely a typo.
+                return if had_match {
+                    ConditionProbe::Met {
+                        reason: "target processes exited; entered shutdown waiting mode"
+                            .to_string(),
+                    }
+                } else {
+                    ConditionProbe::Failed {
+                        reason: "NO_FAIL_OPEN_NETWORK_IDLE: no running process matches the selector; cancelled for safety"
+                            .to_string(),
+                    }
+                };
+            }
+
+            active.condition_detail = Some(format!(
+                "{} established connection(s) across {} process(es)",
+                activity.established,
+                activity.matched_pids.len()
+            ));
+            if activity.established > 0 {
+                ConditionProbe::Pending
+            } else {
+                ConditionProbe::Met {
+                    reason: "target processes hold no established connections; entered shutdown waiting mode"
+                        .to_string(),
+                }
+            }
+        }
     }
 }
 
-fn cancel_active_schedule_internal(
-    app: &AppHandle,
-    reason: &str,
-    emit_notification: bool,
-) -> Result<(), String> {
-    let state = app.state::<AppState>();
-    let mut store = lock_store(&state.store);
-    let Some(active) = store.active.as_ref() else {
-        return Ok(());
+fn port_watch_label(watch: &PortWatch) -> String {
+    let protocol = match watch.protocol {
+        PortProtocol::Tcp => "tcp",
+        PortProtocol::Udp => "udp",
     };
+    format!("{protocol}/{}", watch.port)
+}
 
-    if is_shutdown_execution_started(active) {
-        return Err("shutdown has already started; cannot cancel".to_string());
+fn port_closed_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<bool, String>,
+    pending_events: &mut Vec<(String, Option<String>)>,
+) -> ConditionProbe {
+    let Some(watch) = active.port_watch.clone() else {
+        return ConditionProbe::Failed {
+            reason: missing_watch_reason(&ScheduleMode::PortClosed),
+        };
+    };
+    let label = port_watch_label(&watch);
+
+    let open = match observed {
+        Ok(open) => *open,
+        Err(error) => {
+            active.condition_detail = Some(format!("port state unavailable: {error}"));
+            return ConditionProbe::Unavailable;
+        }
+    };
+
+    if active.port_open != Some(open) {
+        if active.port_open.is_some() {
+            let event_type = if open { "port_reopened" } else { "port_closed" };
+            pending_events.push((event_type.to_string(), Some(format!("port {label}"))));
+        }
+        active.port_open = Some(open);
     }
 
-    let schedule_id = active.id.clone();
+    if open {
+        active.condition_detail = Some(format!("{label} open"));
+        ConditionProbe::Pending
+    } else {
+        active.condition_detail = Some(format!("{label} closed"));
+        ConditionProbe::Met {
+            reason: format!("port {label} stopped listening; entered shutdown waiting mode"),
+        }
+    }
+}
 
-    store.active = None;
-    push_event(
-        &mut store,
-        Some(schedule_id),
-        "cancelled",
-        "ok",
-        Some(reason.to_string()),
-    );
-    state.persist_locked(&store)?;
+fn log_pattern_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<Option<String>, String>,
+) -> ConditionProbe {
+    match observed {
+        Ok(Some(line)) => {
+            let line = if line.chars().count() > LOG_MATCHED_LINE_CHARS {
+                let kept = line.chars().take(LOG_MATCHED_LINE_CHARS).collect::<String>();
+                format!("{kept}...")
+            } else {
+                line.clone()
+            };
+            active.log_matched_line = Some(line);
+        }
+        Ok(None) => {}
+        Err(error) => {
+            active.condition_detail = Some(format!("log unavailable: {error}"));
+        }
+    }
 
-    if emit_notification {
-        send_desktop_notification(app, "Auto Shutdown Scheduler", reason);
+    match active.log_matched_line.as_ref() {
+        Some(line) => {
+            active.condition_detail = Some("pattern matched".to_string());
+            ConditionProbe::Met {
+                reason: format!("log line matched: {line}; entered shutdown waiting mode"),
+            }
+        }
+        None if observed.is_err() => ConditionProbe::Unavailable,
+        None => {
+            active.condition_detail = Some("waiting for a matching line".to_string());
+            ConditionProbe::Pending
+        }
     }
+}
 
-    Ok(())
+fn marker_condition_label(condition: MarkerCondition) -> &'static str {
+    match condition {
+        MarkerCondition::Appears => "appears",
+        MarkerCondition::Disappears => "disappears",
+    }
 }
 
-fn postpone_schedule_internal(app: &AppHandle, minutes: u64, reason: &str) -> Result<(), String> {
-    if minutes == 0 || minutes > 24 * 60 {
-        return Err("minutes must be within 1..=1440".to_string());
+fn marker_file_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<bool, String>,
+) -> ConditionProbe {
+    let Some(watch) = active.marker_watch.clone() else {
+        return ConditionProbe::Failed {
+            reason: missing_watch_reason(&ScheduleMode::MarkerFile),
+        };
+    };
+
+    let present = match observed {
+        Ok(present) => *present,
+        Err(error) => {
+            active.condition_detail = Some(format!("marker unavailable: {error}"));
+            return ConditionProbe::Unavailable;
+        }
+    };
+    active.condition_detail = Some(if present { "present" } else { "absent" }.to_string());
+
+    let wanted = matches!(watch.condition, MarkerCondition::Appears);
+    if present == wanted {
+        ConditionProbe::Met {
+            reason: format!(
+                "marker {} {}; entered shutdown waiting mode",
+                watch.path,
+                if present { "appeared" } else { "disappeared" }
+            ),
+        }
+    } else {
+        ConditionProbe::Pending
     }
+}
 
-    let state = app.state::<AppState>();
-    let mut store = lock_store(&state.store);
-    let Some(active) = store.active.as_mut() else {
-        return Err("no active schedule to postpone".to_string());
+fn http_poll_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<bool, String>,
+) -> ConditionProbe {
+    let Some(watch) = active.http_watch.clone() else {
+        return ConditionProbe::Failed {
+            reason: missing_watch_reason(&ScheduleMode::HttpPoll),
+        };
     };
 
-    if is_shutdown_execution_started(active) {
-        return Err("shutdown has already started; cannot postpone".to_string());
+    // An endpoint that stops answering is not proof of completion.
+    match observed {
+        Ok(true) => {
+            active.condition_detail = Some(format!("{} holds", watch.expression));
+            ConditionProbe::Met {
+                reason: format!(
+                    "{} reported {}; entered shutdown waiting mode",
+                    watch.url, watch.expression
+                ),
+            }
+        }
+        Ok(false) => {
+            active.condition_detail = Some(format!("{} does not hold yet", watch.expression));
+            ConditionProbe::Pending
+        }
+        Err(error) => {
+            active.condition_detail = Some(format!("endpoint unavailable: {error}"));
+            ConditionProbe::Unavailable
+        }
     }
+}
 
-    let schedule_id = active.id.clone();
-    let now = now_ms();
-    let postpone_sec = minutes * 60;
+fn media_idle_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<PlaybackState, String>,
+) -> ConditionProbe {
+    let state = match observed {
+        Ok(state) => state,
+        Err(error) => {
+            active.condition_detail = Some(format!("playback state unavailable: {error}"));
+            return ConditionProbe::Unavailable;
+        }
+    };
 
-    if matches!(active.mode, ScheduleMode::ProcessExit) {
-        active.status = ScheduleStatus::Armed;
-        active.final_warning_started_at_ms = None;
-        active.process_missing_since_ms = None;
-        active.snooze_until_ms = Some(now + (postpone_sec as i64) * 1000);
-        active.shutdown_initiated_at_ms = None;
+    if !state.playing.is_empty() {
+        active.condition_detail = Some(format!("playing: {}", state.playing.join(", ")));
+        return ConditionProbe::Pending;
+    }
+
+    active.condition_detail = Some(if state.players.is_empty() {
+        "no media players".to_string()
     } else {
-        active.mode = ScheduleMode::Countdown;
-        active.summary = format!("Snoozed for {minutes} minutes");
-        active.trigger_at_ms = Some(now + (postpone_sec as i64) * 1000);
-        active.target_local_time = None;
-        active.target_tz_offset_minutes = None;
-        active.status = ScheduleStatus::Armed;
-        active.final_warning_started_at_ms = None;
-        active.fired_alerts.clear();
-        active.process_missing_since_ms = None;
-        active.process_selector = None;
-        active.process_tree_pids.clear();
-        active.snooze_until_ms = None;
-        active.process_match_degraded_logged = false;
-        active.shutdown_initiated_at_ms = None;
+        format!("paused or stopped: {}", state.players.join(", "))
+    });
+    ConditionProbe::Met {
+        reason: "no media player is playing; entered shutdown waiting mode".to_string(),
     }
-    let _ = sync_shutdown_at_ms(active);
+}
 
-    push_event(
-        &mut store,
-        Some(schedule_id),
-        "postponed",
-        "ok",
-        Some(reason.to_string()),
-    );
-    state.persist_locked(&store)?;
+fn battery_low_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<Option<BatteryStatus>, String>,
+) -> ConditionProbe {
+    let Some(threshold) = active.battery_threshold_percent else {
+        return ConditionProbe::Failed {
+            reason: missing_watch_reason(&ScheduleMode::BatteryLow),
+        };
+    };
 
-    send_desktop_notification(
-        app,
-        "Auto Shutdown Scheduler",
-        &format!("Schedule postponed by {minutes} minutes."),
-    );
+    let status = match observed {
+        Ok(Some(status)) => status,
+        Ok(None) => {
+            active.condition_detail = Some("no battery detected".to_string());
+            return ConditionProbe::Pending;
+        }
+        Err(error) => {
+            active.condition_detail = Some(format!("battery unavailable: {error}"));
+            return ConditionProbe::Unavailable;
+        }
+    };
 
-    Ok(())
+    let source = if status.on_ac { "on AC" } else { "on battery" };
+    active.condition_detail = Some(format!("{}% {source}", status.percent));
+    if !status.on_ac && status.percent < threshold {
+        ConditionProbe::Met {
+            reason: format!(
+                "battery at {}% (below {threshold}%); entered shutdown waiting mode",
+                status.percent
+            ),
+        }
+    } else {
+        ConditionProbe::Pending
+    }
 }
 
-fn execute_active_shutdown(app: &AppHandle, schedule_id: String) {
-    let state = app.state::<AppState>();
-    let settings = {
-        let mut store = lock_store(&state.store);
-        let now = now_ms();
+fn battery_guard_holds(
+    battery: Option<&Result<Option<BatteryStatus>, String>>,
+) -> Result<bool, String> {
+    match battery {
+        None => Ok(false),
+        Some(Ok(Some(status))) => Ok(status.on_ac),
+        Some(Ok(None)) => Err("no battery detected".to_string()),
+        Some(Err(error)) => Err(error.clone()),
+    }
+}
 
-        let Some(active) = store.active.as_mut() else {
-            return;
+fn thermal_high_condition_probe(
+    active: &mut ActiveSchedule,
+    observed: &Result<Option<ThermalSensor>, String>,
+) -> ConditionProbe {
+    let Some(watch) = active.thermal_watch.clone() else {
+        return ConditionProbe::Failed {
+            reason: missing_watch_reason(&ScheduleMode::ThermalHigh),
         };
+    };
 
-        if active.id != schedule_id {
-            return;
+    let sensor = match observed {
+        Ok(Some(sensor)) => sensor,
+        Ok(None) => {
+            active.condition_detail = Some(format!("sensor {} not found", watch.sensor));
+            return ConditionProbe::Pending;
         }
-
-        if !try_mark_shutdown_initiated(active, now) {
-            return;
+        Err(error) => {
+            active.condition_detail = Some(format!("temperature unavailable: {error}"));
+            return ConditionProbe::Unavailable;
         }
-        let _ = sync_shutdown_at_ms(active);
+    };
 
-        push_event(
-            &mut store,
-            Some(schedule_id.clone()),
-            "shutdown_initiated",
-            "ok",
-            Some("final warning elapsed; shutdown command starting".to_string()),
-        );
-        let settings = store.settings.clone();
-        if let Err(error) = state.persist_locked(&store) {
-            send_desktop_notification(
-                app,
-                "Auto Shutdown Scheduler",
-                &format!("Warning: failed to persist shutdown initiation state: {error}"),
-            );
+    let peak = active
+        .thermal_peak_celsius
+        .map_or(sensor.celsius, |peak| peak.max(sensor.celsius));
+    active.thermal_peak_celsius = Some(peak);
+    active.condition_detail = Some(format!(
+        "{} {:.1}°C (peak {peak:.1}°C)",
+        sensor.label, sensor.celsius
+    ));
+    if sensor.celsius >= watch.threshold_celsius {
+        ConditionProbe::Met {
+            reason: format!(
+                "{} at {:.1}°C (threshold {:.1}°C, peak {peak:.1}°C); entered shutdown waiting mode",
+                sensor.label, sensor.celsius, watch.threshold_celsius
+            ),
         }
-        settings
-    };
+    } else {
+        ConditionProbe::Pending
+    }
+}
 
-    let result = run_shutdown_command(&settings);
+fn apply_condition_observation(
+    active: &mut ActiveSchedule,
+    observation: &ConditionObservation,
+    now: i64,
+    held: bool,
+    pending_events: &mut Vec<(String, Option<String>)>,
+) -> ConditionTransition {
+    let probe = match observation {
+        ConditionObservation::Launch(poll) => {
+            launch_condition_probe(active, poll.as_ref(), now, pending_events)
+        }
+        ConditionObservation::SystemdUnit(observed) => {
+            systemd_unit_condition_probe(active, observed)
+        }
+        ConditionObservation::NetworkIdle(observed) => {
+            network_idle_condition_probe(active, observed)
+        }
+        ConditionObservation::PortOpen(observed) => {
+            port_closed_condition_probe(active, observed, pending_events)
+        }
+        ConditionObservation::LogMatch(observed) => log_pattern_condition_probe(active, observed),
+        ConditionObservation::MarkerPresent(observed) => {
+            marker_file_condition_probe(active, observed)
+        }
+        ConditionObservation::HttpCheck(observed) => http_poll_condition_probe(active, observed),
+        ConditionObservation::Playback(observed) => media_idle_condition_probe(active, observed),
+        ConditionObservation::Battery(observed) => battery_low_condition_probe(active, observed),
+        ConditionObservation::Thermal(observed) => thermal_high_condition_probe(active, observed),
+        ConditionObservation::Invalid(reason) => ConditionProbe::Failed {
+            reason: reason.clone(),
+        },
+    };
+    advance_condition(active, probe, now, held)
+}
 
-    let mut store = lock_store(&state.store);
-    match result {
-        Ok(dispatch) => {
-            let event_reason = Some(dispatch.log_line());
-            push_event(
-                &mut store,
-                Some(schedule_id.clone()),
-                "executed",
-                "ok",
-                event_reason,
-            );
-            if store
-                .active
-                .as_ref()
-                .map(|active| active.id.as_str())
-                == Some(schedule_id.as_str())
-            {
-                store.active = None;
+fn observe_condition(state: &AppState, active: &ActiveSchedule) -> Option<ConditionObservation> {
+    match active.mode {
+        ScheduleMode::LaunchAndWatch => {
+            let poll = if active.launch_exited_at_ms.is_some() {
+                None
+            } else {
+                Some(lock_launcher(&state.launcher).poll(&active.id))
+            };
+            Some(ConditionObservation::Launch(poll))
+        }
+        ScheduleMode::SystemdUnit => {
+            let observed = match active.systemd_unit.as_ref() {
+                Some(watch) => lock_watchers(&state.watchers).units.query(&watch.unit),
+                None => Err("systemd unit watch is missing".to_string()),
+            };
+            Some(ConditionObservation::SystemdUnit(observed))
+        }
+        ScheduleMode::NetworkIdle => {
+            let selector = match normalize_and_validate_process_selector(
+                active.process_selector.as_ref(),
+            ) {
+                Ok(selector) => selector,
+                Err(error) => {
+                    return Some(ConditionObservation::Invalid(no_fail_open_process_exit_reason(
+                        &error,
+                    )));
+                }
+            };
+            // Ask for a full count so every matching instance is returned, not just the first.
+            let matched_pids = lock_scanner(&state.scanner)
+                .is_process_running(&selector, &active.process_tree_pids, Some(1))
+                .matched_pids;
+            let established = lock_watchers(&state.watchers).net.count_established(&matched_pids);
+            Some(ConditionObservation::NetworkIdle(established.map(|established| {
+                NetworkActivity {
+                    matched_pids,
+                    established,
+                }
+            })))
+        }
+        ScheduleMode::PortClosed => {
+            let observed = match active.port_watch.as_ref() {
+                Some(watch) => match watch.probe {
+                    PortProbe::Listening => lock_watchers(&state.watchers)
+                        .net
+                        .is_port_listening(watch.port, watch.protocol),
+                    PortProbe::Connect => Ok(accepts_local_connection(watch.port)),
+                },
+                None => Err("port watch is missing".to_string()),
+            };
+            Some(ConditionObservation::PortOpen(observed))
+        }
+        ScheduleMode::LogPattern => {
+            if active.log_matched_line.is_some() {
+                return Some(ConditionObservation::LogMatch(Ok(None)));
             }
-            let _ = state.persist_locked(&store);
-            if dispatch.dry_run {
-                send_desktop_notification(
-                    app,
-                    "Auto Shutdown Scheduler",
-                    &format!(
-                        "Dry run complete. {}",
-                        dispatch.log_line()
-                    ),
-                );
+            let Some(watch) = active.log_watch.as_ref() else {
+                return Some(ConditionObservation::Invalid(missing_watch_reason(
+                    &ScheduleMode::LogPattern,
+                )));
+            };
+
+            let mut watchers = lock_watchers(&state.watchers);
+            if watchers.log.as_ref().map(LogTailer::schedule_id) != Some(active.id.as_str()) {
+                match LogTailer::new(&active.id, watch, active.log_start) {
+                    Ok(tailer) => watchers.log = Some(tailer),
+                    Err(error) => {
+                        return Some(ConditionObservation::Invalid(format!(
+                            "LOG_WATCH_FAILED: {error}; cancelled for safety"
+                        )));
+                    }
+                }
             }
+            let observed = match watchers.log.as_mut() {
+                Some(tailer) => tailer.poll(),
+                None => Err("log tailer unavailable".to_string()),
+            };
+            Some(ConditionObservation::LogMatch(observed))
         }
-        Err(error) => {
-            push_event(
-                &mut store,
-                Some(schedule_id.clone()),
-                "failed",
-                "error",
-                Some(error.clone()),
-            );
-            if store
-                .active
+        ScheduleMode::MarkerFile => {
+            let observed = match active.marker_watch.as_ref() {
+                Some(watch) => marker_present(&watch.path),
+                None => Err("marker watch is missing".to_string()),
+            };
+            Some(ConditionObservation::MarkerPresent(observed))
+        }
+        ScheduleMode::HttpPoll => {
+            let observed = match active.http_watch.as_ref() {
+                Some(watch) => parse_loopback_url(&watch.url).and_then(|url| {
+                    let expression = parse_json_expression(&watch.expression)?;
+                    expression.evaluate(&fetch_json(&url)?)
+                }),
+                None => Err("http watch is missing".to_string()),
+            };
+            Some(ConditionObservation::HttpCheck(observed))
+        }
+        ScheduleMode::MediaIdle => {
+            let filter = active
+                .media_watch
                 .as_ref()
-                .map(|active| active.id.as_str())
-                == Some(schedule_id.as_str())
-            {
-                store.active = None;
-            }
-            let _ = state.persist_locked(&store);
-            send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
+                .and_then(|watch| watch.player_filter.as_deref());
+            let observed = lock_watchers(&state.watchers).media.query(filter);
+            Some(ConditionObservation::Playback(observed))
+        }
+        ScheduleMode::BatteryLow => Some(ConditionObservation::Battery(
+            lock_watchers(&state.watchers).power.read(),
+        )),
+        ScheduleMode::ThermalHigh => {
+            let observed = match active.thermal_watch.as_ref() {
+                Some(watch) => lock_watchers(&state.watchers).thermal.read(&watch.sensor),
+                None => Err("thermal watch is missing".to_string()),
+            };
+            Some(ConditionObservation::Thermal(observed))
         }
+        ScheduleMode::Countdown
+        | ScheduleMode::SpecificTime
+        | ScheduleMode::ProcessExit
+        | ScheduleMode::UptimeLimit => None,
     }
 }
 
-fn tick_scheduler(app: &AppHandle) {
-    struct PendingNotification {
-        title: String,
-        body: String,
+fn is_shutdown_execution_started(schedule: &ActiveSchedule) -> bool {
+    matches!(schedule.status, ScheduleStatus::ShuttingDown)
+        || schedule.shutdown_initiated_at_ms.is_some()
+}
+
+fn try_mark_shutdown_initiated(schedule: &mut ActiveSchedule, now: i64) -> bool {
+    if is_shutdown_execution_started(schedule) {
+        return false;
     }
 
-    enum ProcessScanState {
-        NotRequested,
-        Invalid {
-            schedule_id: String,
-            status: ScheduleStatus,
-            reason: String,
-        },
-        Ready {
-            schedule_id: String,
-            status: ScheduleStatus,
-            result: ProcessMatchResult,
-        },
+    if !matches!(schedule.status, ScheduleStatus::FinalWarning) {
+        return false;
     }
 
-    let mut notifications = Vec::<PendingNotification>::new();
-    let mut should_execute = None::<String>;
-    let state = app.state::<AppState>();
-    let scan_state = {
-        let store = lock_store(&state.store);
-        let Some(active) = store.active.as_ref() else {
-            return;
-        };
+    let Some(started_at_ms) = schedule.final_warning_started_at_ms else {
+        return false;
+    };
 
-        if !matches!(active.mode, ScheduleMode::ProcessExit)
-            || !matches!(active.status, ScheduleStatus::Armed | ScheduleStatus::FinalWarning)
-        {
-            ProcessScanState::NotRequested
-        } else {
-            match normalize_and_validate_process_selector(active.process_selector.as_ref()) {
-                Ok(selector) => {
-                    let schedule_id = active.id.clone();
-                    let status = active.status.clone();
-                    let tracked_pids = active.process_tree_pids.clone();
+    let elapsed_ms = now - started_at_ms;
+    if elapsed_ms < (schedule.final_warning_duration_sec as i64) * 1000 {
+        return false;
+    }
 
-                    drop(store);
-                    let result = {
-                        let mut scanner = lock_scanner(&state.scanner);
-                        scanner.is_process_running(&selector, &tracked_pids)
-                    };
+    schedule.status = ScheduleStatus::ShuttingDown;
+    schedule.shutdown_initiated_at_ms = Some(now);
+    true
+}
 
-                    ProcessScanState::Ready {
-                        schedule_id,
-                        status,
-                        result,
-                    }
-                }
-                Err(error) => ProcessScanState::Invalid {
-                    schedule_id: active.id.clone(),
-                    status: active.status.clone(),
-                    reason: no_fail_open_process_exit_reason(&error),
-                },
-            }
-        }
-    };
+fn normalize_alerts(alerts: &[u64]) -> Vec<u64> {
+    let mut values = alerts
+        .iter()
+        .copied()
+        .filter(|seconds| *seconds > 0 && *seconds <= 24 * 60 * 60)
+        .collect::<Vec<u64>>();
 
-    {
-        let mut store = lock_store(&state.store);
-        let Some(active) = store.active.as_mut() else {
-            return;
-        };
+    values.sort_unstable();
+    values.dedup();
+    values.reverse();
 
-        let mut changed = false;
-        let mut pending_events = Vec::<(String, Option<String>)>::new();
-        let now = now_ms();
-        let schedule_id = active.id.clone();
-        let mut fail_safe_cancel_reason = None::<String>;
+    if values.is_empty() {
+        vec![600, 300, 60]
+    } else {
+        values
+    }
+}
 
-        match active.status {
-            ScheduleStatus::Armed => match active.mode {
-                ScheduleMode::Countdown | ScheduleMode::SpecificTime => {
-                    if matches!(active.mode, ScheduleMode::SpecificTime) {
-                        if let (Some(target_label), Some(saved_offset)) = (
-                            active.target_local_time.clone(),
-                            active.target_tz_offset_minutes,
-                        ) {
-                            let current_offset = Local::now().offset().local_minus_utc() / 60;
-                            if current_offset != saved_offset {
-                                if let Ok(recomputed_ms) = compute_next_local_target_ms(&target_label)
-                                {
-                                    active.trigger_at_ms = Some(recomputed_ms);
-                                    active.target_tz_offset_minutes = Some(current_offset);
-                                    changed = true;
-                                    pending_events.push((
-                                        "timezone_realigned".to_string(),
-                                        Some("specific-time schedule was realigned after timezone change".to_string()),
-                                    ));
-                                }
-                            }
-                        }
-                    }
+fn state_companion_path(path: &Path, suffix: &str) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(STATE_FILE_NAME);
+    path.with_file_name(format!("{file_name}{suffix}"))
+}
 
-                    if let Some(trigger_at_ms) = active.trigger_at_ms {
-                        let remaining_sec = if trigger_at_ms > now {
-                            ((trigger_at_ms - now) / 1000) as u64
-                        } else {
-                            0
-                        };
+fn backup_state_path(path: &Path) -> PathBuf {
+    state_companion_path(path, ".bak")
+}
 
-                        for threshold_sec in active.pre_alerts.clone() {
-                            if remaining_sec > 0
-                                && remaining_sec <= threshold_sec
-                                && !active.fired_alerts.contains(&threshold_sec)
-                            {
-                                active.fired_alerts.push(threshold_sec);
-                                changed = true;
-                                pending_events.push((
-                                    "alerted".to_string(),
-                                    Some(format!("pre-alert fired at {threshold_sec}s")),
-                                ));
-                                notifications.push(PendingNotification {
-                                    title: "Auto Shutdown Scheduler".to_string(),
-                                    body: pre_alert_notification_body(threshold_sec),
-                                });
-                            }
-                        }
+fn next_corrupt_state_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(STATE_FILE_NAME);
+    let base = format!("{file_name}.corrupt-{}", now_ms());
+    let mut candidate = path.with_file_name(base.clone());
+    let mut suffix = 1;
+    while candidate.exists() {
+        candidate = path.with_file_name(format!("{base}-{suffix}"));
+        suffix += 1;
+    }
+    candidate
+}
 
-                        if remaining_sec == 0 {
-                            active.status = ScheduleStatus::FinalWarning;
-                            active.final_warning_started_at_ms = Some(now);
-                            active.shutdown_initiated_at_ms = None;
-                            changed = true;
-                            pending_events.push((
-                                "final_warning".to_string(),
-                                Some("entered shutdown waiting mode (final warning stage)".to_string()),
-                            ));
-                            notifications.push(PendingNotification {
-                                title: "Auto Shutdown Scheduler".to_string(),
-                                body: final_warning_notification_body(
-                                    active.final_warning_duration_sec,
-                                ),
-                            });
-                        }
-                    }
-                }
-                ScheduleMode::ProcessExit => {
-                    if let Some(snooze_until_ms) = active.snooze_until_ms {
-                        if now >= snooze_until_ms {
-                            active.snooze_until_ms = None;
-                            changed = true;
-                        }
-                    }
-                    match &scan_state {
-                        ProcessScanState::Ready {
-                            schedule_id: scanned_id,
-                            status,
-                            result,
-                        } if scanned_id == &schedule_id && status == &active.status => {
-                            let match_result = result.clone();
-                            active.process_tree_pids = match_result.matched_pids.clone();
-
-                            if match_result.degraded_to_name && !active.process_match_degraded_logged
-                            {
-                                active.process_match_degraded_logged = true;
-                                changed = true;
-                                pending_events.push((
-                                    "process_match_degraded".to_string(),
-                                    Some(
-                                        "advanced process matching unavailable; fell back to name matching"
-                                            .to_string(),
-                                    ),
-                                ));
-                            }
+fn load_store(path: &Path) -> Result<LoadStoreOutcome, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Ok(LoadStoreOutcome {
+                store: SchedulerStore::default(),
+                needs_persist: false,
+                startup_notice: None,
+            });
+        }
+        Err(error) => {
+            return Err(format!("failed to read state file: {error}"));
+        }
+    };
 
-                            if match_result.running {
-                                if active.process_missing_since_ms.is_some() {
-                                    active.process_missing_since_ms = None;
-                                    changed = true;
-                                }
-                            } else {
-                                if active.process_missing_since_ms.is_none() {
-                                    active.process_missing_since_ms = Some(now);
-                                    changed = true;
-                                }
+    match serde_json::from_str::<PersistedState>(&content) {
+        Ok(from_disk) => Ok(LoadStoreOutcome {
+            // Settings entries dropped on load are recorded once, then gone from the file.
+            needs_persist: persisted_settings_need_sanitizing(&from_disk.settings),
+            store: SchedulerStore::from_persisted(from_disk),
+            startup_notice: None,
+        }),
+        Err(parse_error) => {
+            let corrupt_path = next_corrupt_state_path(path);
+            fs::rename(path, &corrupt_path).map_err(|error| {
+                format!(
+                    "failed to quarantine corrupted state file to {}: {error}",
+                    corrupt_path.display()
+                )
+            })?;
 
-                                let missing_for =
-                                    now - active.process_missing_since_ms.unwrap_or(now);
-                                if missing_for >= (active.process_stable_sec as i64) * 1000 {
-                                    let snoozed = active
-                                        .snooze_until_ms
-                                        .map(|snooze_until_ms| now < snooze_until_ms)
-                                        .unwrap_or(false);
+            let backup_path = backup_state_path(path);
+            let mut reason = format!(
+                "failed to parse state file: {parse_error}; quarantined at {}",
+                corrupt_path.display()
+            );
+            let mut store = SchedulerStore::default();
+            let mut recovered_from_backup = false;
 
-                                    if !snoozed {
-                                        active.status = ScheduleStatus::FinalWarning;
-                                        active.final_warning_started_at_ms = Some(now);
-                                        active.process_missing_since_ms = None;
-                                        active.shutdown_initiated_at_ms = None;
-                                        changed = true;
-                                        pending_events.push((
-                                            "final_warning".to_string(),
-                                            Some("target process exited; entered shutdown waiting mode".to_string()),
-                                        ));
-                                        notifications.push(PendingNotification {
-                                            title: "Auto Shutdown Scheduler".to_string(),
-                                            body: process_exit_final_warning_notification_body(
-                                                active.final_warning_duration_sec,
-                                            ),
-                                        });
-                                    }
-                                }
-                            }
+            if backup_path.exists() {
+                match fs::read_to_string(&backup_path) {
+                    Ok(backup_content) => match serde_json::from_str::<PersistedState>(&backup_content)
+                    {
+                        Ok(backup_state) => {
+                            store = SchedulerStore::from_persisted(backup_state);
+                            recovered_from_backup = true;
                         }
-                        ProcessScanState::Invalid {
-                            schedule_id: scanned_id,
-                            status,
-                            reason,
-                        } if scanned_id == &schedule_id && status == &active.status => {
-                            if fail_safe_cancel_reason.is_none() {
-                                fail_safe_cancel_reason = Some(reason.clone());
-                            }
-                            reset_process_exit_on_selector_failure(active);
-                            changed = true;
+                        Err(error) => {
+                            reason.push_str(&format!(
+                                "; backup parse failed ({}): {error}",
+                                backup_path.display()
+                            ));
                         }
-                        ProcessScanState::Ready { .. } | ProcessScanState::Invalid { .. } => {}
-                        ProcessScanState::NotRequested => {}
+                    },
+                    Err(error) => {
+                        reason.push_str(&format!(
+                            "; backup read failed ({}): {error}",
+                            backup_path.display()
+                        ));
                     }
                 }
-            },
-            ScheduleStatus::FinalWarning => {
-                let mut reverted = false;
-                if matches!(active.mode, ScheduleMode::ProcessExit) {
-                    match &scan_state {
-                        ProcessScanState::Ready {
-                            schedule_id: scanned_id,
-                            status,
-                            result,
-                        } if scanned_id == &schedule_id && status == &active.status => {
-                            let match_result = result.clone();
-                            active.process_tree_pids = match_result.matched_pids.clone();
+            } else {
+                reason.push_str("; backup not found");
+            }
 
-                            if match_result.degraded_to_name && !active.process_match_degraded_logged {
-                                active.process_match_degraded_logged = true;
-                                changed = true;
-                                pending_events.push((
-                                    "process_match_degraded".to_string(),
-                                    Some(
-                                        "advanced process matching unavailable; fell back to name matching"
-                                            .to_string(),
-                                    ),
-                                ));
-                            }
+            push_event(
+                &mut store,
+                None,
+                "state_parse_failed",
+                "error",
+                Some(reason),
+            );
 
-                            if match_result.running {
-                                active.status = ScheduleStatus::Armed;
-                                active.final_warning_started_at_ms = None;
-                                active.process_missing_since_ms = None;
-                                active.shutdown_initiated_at_ms = None;
-                                changed = true;
-                                reverted = true;
-                                pending_events.push((
-                                    "final_warning_reverted".to_string(),
-                                    Some(format!(
-                                        "target process detected again ({})",
-                                        process_match_source_label(match_result.source)
-                                    )),
-                                ));
-                                notifications.push(PendingNotification {
-                                    title: "Auto Shutdown Scheduler".to_string(),
-                                    body: "감시 대상이 다시 실행되어 종료를 보류했습니다.".to_string(),
-                                });
-                            }
-                        }
-                        ProcessScanState::Invalid {
-                            schedule_id: scanned_id,
-                            status,
-                            reason,
-                        } if scanned_id == &schedule_id && status == &active.status => {
-                            if fail_safe_cancel_reason.is_none() {
-                                fail_safe_cancel_reason = Some(reason.clone());
-                            }
-                            reset_process_exit_on_selector_failure(active);
-                            changed = true;
-                            reverted = true;
-                        }
-                        ProcessScanState::Ready { .. } | ProcessScanState::Invalid { .. } => {
-                            reverted = true;
-                        }
-                        ProcessScanState::NotRequested => {}
-                    }
-                }
+            if recovered_from_backup {
+                push_event(
+                    &mut store,
+                    None,
+                    "state_restored_from_backup",
+                    "ok",
+                    Some(format!("restored state from {}", backup_path.display())),
+                );
+            }
+
+            let startup_notice = if recovered_from_backup {
+                Some(
+                    "상태 파일 손상을 감지해 마지막 정상 백업(.bak)으로 복구했습니다. 현재 스케줄을 확인해 주세요."
+                        .to_string(),
+                )
+            } else {
+                Some(
+                    "상태 파일 손상을 감지해 기본 상태로 복구했습니다. 기존 스케줄은 안전을 위해 복원되지 않았습니다."
+                        .to_string(),
+                )
+            };
+
+            Ok(LoadStoreOutcome {
+                store,
+                needs_persist: true,
+                startup_notice,
+            })
+        }
+    }
+}
+
+fn persist_store(path: &Path, store: &SchedulerStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create state directory: {error}"))?;
+    }
+
+    let serialized = serde_json::to_string_pretty(&store.to_persisted())
+        .map_err(|error| format!("failed to serialize state: {error}"))?;
+    let temp_path = state_companion_path(path, ".tmp");
+    let backup_path = backup_state_path(path);
+
+    fs::write(&temp_path, serialized)
+        .map_err(|error| format!("failed to write temp state file: {error}"))?;
+    if let Ok(file) = fs::OpenOptions::new().write(true).open(&temp_path) {
+        let _ = file.sync_all();
+    }
+
+    let had_existing_state = path.exists();
+    if had_existing_state {
+        if backup_path.exists() {
+            fs::remove_file(&backup_path)
+                .map_err(|error| format!("failed to remove previous backup file: {error}"))?;
+        }
+        fs::rename(path, &backup_path)
+            .map_err(|error| format!("failed to move state file to backup: {error}"))?;
+    }
+
+    if let Err(error) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        if had_existing_state && !path.exists() && backup_path.exists() {
+            let _ = fs::rename(&backup_path, path);
+        }
+        return Err(format!("failed to replace state file atomically: {error}"));
+    }
+
+    Ok(())
+}
+
+fn push_event(
+    store: &mut SchedulerStore,
+    schedule_id: Option<String>,
+    event_type: &str,
+    result: &str,
+    reason: Option<String>,
+) {
+    store.history.push(ExecutionEvent {
+        schedule_id,
+        event_type: event_type.to_string(),
+        timestamp_ms: now_ms(),
+        result: result.to_string(),
+        reason,
+    });
+
+    if store.history.len() > HISTORY_LIMIT {
+        let overflow = store.history.len() - HISTORY_LIMIT;
+        store.history.drain(0..overflow);
+    }
+}
+
+fn enforce_no_resume_in_mvp(store: &mut SchedulerStore) -> bool {
+    let cleared = clear_schedule_queue(store, "the schedule they waited for was not resumed");
+    let Some(active) = store.active.take() else {
+        return cleared;
+    };
+
+    push_event(
+        store,
+        Some(active.id),
+        "resume_not_supported",
+        "ok",
+        Some(
+            "MVP 정책(NO_RESUME_IN_MVP)에 따라 앱 시작 시 이전 활성 스케줄 자동 복구를 지원하지 않아 해제되었습니다."
+                .to_string(),
+        ),
+    );
+
+    true
+}
+
+fn current_boot_time_ms() -> Option<i64> {
+    let boot_time_sec = sysinfo::System::boot_time();
+    (boot_time_sec > 0).then(|| (boot_time_sec as i64) * 1000)
+}
+
+// Suspended time counts as uptime, so only boot-time shifts move the trigger.
+fn realign_uptime_trigger(
+    active: &mut ActiveSchedule,
+    boot_time_ms: i64,
+    now: i64,
+) -> Result<bool, String> {
+    let Some(limit_sec) = active.uptime_limit_sec else {
+        return Ok(false);
+    };
+    let limit_sec = validate_uptime_limit(limit_sec)?;
+    let trigger = match (active.boot_time_ms, active.trigger_at_ms) {
+        (Some(previous), _) if (previous - boot_time_ms).abs() <= UPTIME_BOOT_DRIFT_MS => {
+            return Ok(false);
+        }
+        // Shift rather than recompute so postpones and pauses already applied are kept.
+        (Some(previous), Some(trigger_at_ms)) => {
+            trigger_at_ms.saturating_add(boot_time_ms - previous)
+        }
+        _ => boot_time_ms.saturating_add((limit_sec as i64) * 1000),
+    };
+    active.boot_time_ms = Some(boot_time_ms);
+    active.trigger_at_ms = Some(trigger.max(now));
+    Ok(true)
+}
+
+fn validate_uptime_limit(limit_sec: u64) -> Result<u64, String> {
+    if !(1..=UPTIME_LIMIT_MAX_SEC).contains(&limit_sec) {
+        return Err(format!("uptimeLimitSec must be within 1..={UPTIME_LIMIT_MAX_SEC}"));
+    }
+    Ok(limit_sec)
+}
+
+fn compute_next_local_target_ms(target_local_time: &str) -> Result<i64, String> {
+    compute_local_target_after_ms(target_local_time, now_ms())
+}
+
+fn compute_local_target_after_ms(
+    target_local_time: &str,
+    reference_ms: i64,
+) -> Result<i64, String> {
+    let parsed = NaiveTime::parse_from_str(target_local_time, "%H:%M")
+        .map_err(|_| "target time must match HH:MM format".to_string())?;
+
+    let now = Local
+        .timestamp_millis_opt(reference_ms)
+        .single()
+        .ok_or("unable to resolve reference time".to_string())?;
+    let today = now.date_naive();
+
+    let mut candidate = today.and_time(parsed);
+    let mut target = match Local.from_local_datetime(&candidate) {
+        LocalResult::Single(value) => value,
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        LocalResult::None => return Err("unable to resolve local target time".to_string()),
+    };
+
+    if target <= now {
+        let tomorrow = today
+            .checked_add_days(Days::new(1))
+            .ok_or("failed to compute tomorrow date".to_string())?;
+        candidate = tomorrow.and_time(parsed);
+        target = match Local.from_local_datetime(&candidate) {
+            LocalResult::Single(value) => value,
+            LocalResult::Ambiguous(earliest, _) => earliest,
+            LocalResult::None => return Err("unable to resolve next local target time".to_string()),
+        };
+    }
+
+    Ok(target.timestamp_millis())
+}
+
+fn local_dst_resolution(at_ms: i64) -> Option<DstResolution> {
+    let local = Local.timestamp_millis_opt(at_ms).single()?;
+    match Local.from_local_datetime(&local.naive_local()) {
+        LocalResult::Single(_) => Some(DstResolution::Unambiguous),
+        LocalResult::Ambiguous(_, _) => Some(DstResolution::EarliestOfRepeated),
+        LocalResult::None => None,
+    }
+}
+
+fn local_offset_minutes_at(at_ms: i64) -> Option<i32> {
+    Local
+        .timestamp_millis_opt(at_ms)
+        .single()
+        .map(|value| value.offset().local_minus_utc() / 60)
+}
+
+fn format_utc_offset(minutes: i32) -> String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let minutes = minutes.unsigned_abs();
+    format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn resolve_condition_window(
+    mode: &ScheduleMode,
+    not_before_local_time: Option<&String>,
+    deadline_local_time: Option<&String>,
+) -> Result<(Option<i64>, Option<i64>, String), String> {
+    let not_before = normalize_selector_text(not_before_local_time);
+    let deadline = normalize_selector_text(deadline_local_time);
+    if not_before.is_none() && deadline.is_none() {
+        return Ok((None, None, String::new()));
+    }
+    if !is_condition_mode(mode) {
+        return Err("time windows are only available for condition-based schedules".to_string());
+    }
+    if not_before.is_some() && not_before == deadline {
+        return Err("notBeforeLocalTime and deadlineLocalTime must differ".to_string());
+    }
+
+    let resolve = |field: &str, value: &Option<String>| {
+        value
+            .as_deref()
+            .map(|value| {
+                compute_next_local_target_ms(value).map_err(|error| format!("{field}: {error}"))
+            })
+            .transpose()
+    };
+    let mut not_before_ms = resolve("notBeforeLocalTime", &not_before)?;
+    let deadline_ms = resolve("deadlineLocalTime", &deadline)?;
+    if let (Some(not_before_at), Some(deadline_at)) = (not_before_ms, deadline_ms) {
+        // Armed at 23:00 with "not before 22:00, no later than 06:00": the window is already open.
+        if not_before_at > deadline_at {
+            not_before_ms = None;
+        }
+    }
+
+    let suffix = match (not_before, deadline) {
+        (Some(not_before), Some(deadline)) => {
+            format!(" (not before {not_before}, deadline {deadline})")
+        }
+        (Some(not_before), None) => format!(" (not before {not_before})"),
+        (None, Some(deadline)) => format!(" (deadline {deadline})"),
+        (None, None) => String::new(),
+    };
+    Ok((not_before_ms, deadline_ms, suffix))
+}
+
+fn build_active_schedule(
+    store: &mut SchedulerStore,
+    request: ScheduleRequest,
+) -> Result<ActiveSchedule, String> {
+    let now = now_ms();
+    let mode = request.mode;
+    let pre_alerts = normalize_alerts(
+        &request
+            .pre_alerts
+            .unwrap_or_else(|| store.settings.default_pre_alerts.clone()),
+    );
+    let process_stable_sec = request.process_stable_sec.unwrap_or(10).clamp(5, 600);
+    let process_instance_threshold = match (&mode, request.process_instance_threshold) {
+        (ScheduleMode::ProcessExit, Some(threshold)) => {
+            if !(1..=PROCESS_INSTANCE_THRESHOLD_MAX).contains(&threshold) {
+                return Err(format!(
+                    "processInstanceThreshold must be within 1..={PROCESS_INSTANCE_THRESHOLD_MAX}"
+                ));
+            }
+            Some(threshold)
+        }
+        _ => None,
+    };
+    let (launch_command, launch_exit_policy) = match mode {
+        ScheduleMode::LaunchAndWatch => (
+            Some(normalize_and_validate_launch_command(request.launch_command.as_ref())?),
+            Some(request.launch_exit_policy.unwrap_or(LaunchExitPolicy::Always)),
+        ),
+        _ => (None, None),
+    };
+    let systemd_unit = match mode {
+        ScheduleMode::SystemdUnit => {
+            Some(normalize_and_validate_systemd_unit(request.systemd_unit.as_ref())?)
+        }
+        _ => None,
+    };
+    let log_watch = match mode {
+        ScheduleMode::LogPattern => Some(validate_log_watch(request.log_watch.as_ref())?),
+        _ => None,
+    };
+    let log_start = match log_watch.as_ref() {
+        Some(watch) => capture_log_start(watch)?,
+        None => None,
+    };
+    let marker_watch = match mode {
+        ScheduleMode::MarkerFile => Some(validate_marker_watch(request.marker_watch.as_ref())?),
+        _ => None,
+    };
+    let http_watch = match mode {
+        ScheduleMode::HttpPoll => Some(validate_http_watch(request.http_watch.as_ref())?),
+        _ => None,
+    };
+    let media_watch = match mode {
+        ScheduleMode::MediaIdle => {
+            if !cfg!(target_os = "linux") {
+                return Err("media playback watching is only supported on Linux".to_string());
+            }
+            let filter = request
+                .media_watch
+                .as_ref()
+                .and_then(|watch| normalize_selector_text(watch.player_filter.as_ref()));
+            Some(MediaWatch {
+                player_filter: filter,
+            })
+        }
+        _ => None,
+    };
+    let battery_threshold_percent = match mode {
+        ScheduleMode::BatteryLow => {
+            if !cfg!(target_os = "linux") {
+                return Err("battery watching is only supported on Linux".to_string());
+            }
+            let threshold = request
+                .battery_threshold_percent
+                .ok_or("batteryThresholdPercent is required for batteryLow mode".to_string())?;
+            if !(1..=99).contains(&threshold) {
+                return Err("batteryThresholdPercent must be within 1..=99".to_string());
+            }
+            Some(threshold)
+        }
+        _ => None,
+    };
+    if request.require_battery_power {
+        if !cfg!(target_os = "linux") {
+            return Err("the on-battery guard is only supported on Linux".to_string());
+        }
+        match PowerSupplyReader::default().read() {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Err("the on-battery guard needs a battery; none detected".to_string());
+            }
+            Err(error) => {
+                return Err(format!("the on-battery guard cannot read the battery: {error}"));
+            }
+        }
+    }
+    let port_watch = match mode {
+        ScheduleMode::PortClosed => Some(validate_port_watch(request.port_watch.as_ref())?),
+        _ => None,
+    };
+    let thermal_watch = match mode {
+        ScheduleMode::ThermalHigh => Some(validate_thermal_watch(request.thermal_watch.as_ref())?),
+        _ => None,
+    };
+    let (not_before_ms, deadline_ms, window_summary) = resolve_condition_window(
+        &mode,
+        request.not_before_local_time.as_ref(),
+        request.deadline_local_time.as_ref(),
+    )?;
+    let post_condition_delay_sec = match request.post_condition_delay_sec {
+        Some(_) if !is_condition_mode(&mode) => {
+            return Err(
+                "postConditionDelaySec is only available for condition-based schedules".to_string(),
+            );
+        }
+        Some(delay) if !(1..=POST_CONDITION_DELAY_MAX_SEC).contains(&delay) => {
+            return Err(format!(
+                "postConditionDelaySec must be within 1..={POST_CONDITION_DELAY_MAX_SEC}"
+            ));
+        }
+        delay => delay,
+    };
+    let duration_sec = request
+        .duration_sec
+        .filter(|_| matches!(mode, ScheduleMode::Countdown));
+    let (uptime_limit_sec, boot_time_ms) = match mode {
+        ScheduleMode::UptimeLimit => {
+            let limit = request
+                .uptime_limit_sec
+                .ok_or("uptimeLimitSec is required for uptimeLimit mode".to_string())?;
+            let limit = validate_uptime_limit(limit)?;
+            let boot_time_ms =
+                current_boot_time_ms().ok_or("system boot time is unavailable".to_string())?;
+            (Some(limit), Some(boot_time_ms))
+        }
+        _ => (None, None),
+    };
+
+    let (
+        trigger_at_ms,
+        target_local_time,
+        target_tz_offset_minutes,
+        summary,
+        process_selector,
+    ) = match mode {
+        ScheduleMode::UptimeLimit => {
+            let limit = uptime_limit_sec.unwrap_or_default();
+            let trigger = boot_time_ms
+                .unwrap_or_default()
+                .checked_add((limit as i64) * 1000)
+                .ok_or("uptime limit is too large".to_string())?;
+            (
+                Some(trigger.max(now)),
+                None,
+                None,
+                format!("Shutdown at {}h {}m uptime", limit / 3600, (limit % 3600) / 60),
+                None,
+            )
+        }
+        ScheduleMode::Countdown => {
+            let duration = request
+                .duration_sec
+                .ok_or("durationSec is required for countdown mode".to_string())?;
+            if duration == 0 {
+                return Err("durationSec must be greater than zero".to_string());
+            }
+            let trigger = now
+                .checked_add((duration as i64) * 1000)
+                .ok_or("duration is too large".to_string())?;
+            (
+                Some(trigger),
+                None,
+                None,
+                countdown_summary(duration),
+                None,
+            )
+        }
+        ScheduleMode::SpecificTime => {
+            let target = request
+                .target_local_time
+                .ok_or("targetLocalTime is required for specificTime mode".to_string())?;
+            let trigger = compute_next_local_target_ms(&target)?;
+            (
+                Some(trigger),
+                Some(target.clone()),
+                Some(Local::now().offset().local_minus_utc() / 60),
+                format!("Shutdown at local time {target}"),
+                None,
+            )
+        }
+        ScheduleMode::ProcessExit => {
+            let selector = normalize_and_validate_process_selector(request.process_selector.as_ref())
+                .map_err(|error| {
+                    if error == "process selector is missing" {
+                        "processSelector is required for processExit mode".to_string()
+                    } else {
+                        error
+                    }
+                })?;
+
+            let summary =
+                process_exit_summary(&selector, process_instance_threshold, process_stable_sec)?;
+            (None, None, None, summary, Some(selector))
+        }
+        ScheduleMode::LaunchAndWatch => {
+            let (Some(command), Some(policy)) = (launch_command.as_ref(), launch_exit_policy) else {
+                return Err("launchCommand is required for launchAndWatch mode".to_string());
+            };
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown after `{}` finishes (policy {})",
+                    describe_command(command),
+                    launch_exit_policy_label(policy)
+                ),
+                None,
+            )
+        }
+        ScheduleMode::BatteryLow => {
+            let threshold = battery_threshold_percent.unwrap_or_default();
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when battery drops below {threshold}% (stable {process_stable_sec}s)"
+                ),
+                None,
+            )
+        }
+        ScheduleMode::ThermalHigh => {
+            let watch = thermal_watch.as_ref().ok_or("thermal watch is missing".to_string())?;
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when {} stays at or above {}°C (stable {process_stable_sec}s)",
+                    watch.sensor, watch.threshold_celsius
+                ),
+                None,
+            )
+        }
+        ScheduleMode::MediaIdle => {
+            let players = media_watch
+                .as_ref()
+                .and_then(|watch| watch.player_filter.clone())
+                .unwrap_or_else(|| "any player".to_string());
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when media playback stops ({players}, stable {process_stable_sec}s)"
+                ),
+                None,
+            )
+        }
+        ScheduleMode::HttpPoll => {
+            let Some(watch) = http_watch.as_ref() else {
+                return Err("httpWatch is required for httpPoll mode".to_string());
+            };
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when {} reports {} (stable {process_stable_sec}s)",
+                    watch.url, watch.expression
+                ),
+                None,
+            )
+        }
+        ScheduleMode::MarkerFile => {
+            let Some(watch) = marker_watch.as_ref() else {
+                return Err("markerWatch is required for markerFile mode".to_string());
+            };
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when {} {} (stable {process_stable_sec}s)",
+                    watch.path,
+                    marker_condition_label(watch.condition)
+                ),
+                None,
+            )
+        }
+        ScheduleMode::LogPattern => {
+            let Some(watch) = log_watch.as_ref() else {
+                return Err("logWatch is required for logPattern mode".to_string());
+            };
+            let qualifier = if watch.only_new_lines {
+                ", new lines only"
+            } else {
+                ""
+            };
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when {} matches /{}/{qualifier}",
+                    watch.path, watch.pattern
+                ),
+                None,
+            )
+        }
+        ScheduleMode::PortClosed => {
+            let Some(watch) = port_watch.as_ref() else {
+                return Err("portWatch is required for portClosed mode".to_string());
+            };
+            let probe = match watch.probe {
+                PortProbe::Listening => "stops listening",
+                PortProbe::Connect => "stops accepting connections",
+            };
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when localhost {} {probe} (stable {process_stable_sec}s)",
+                    port_watch_label(watch)
+                ),
+                None,
+            )
+        }
+        ScheduleMode::NetworkIdle => {
+            if !cfg!(target_os = "linux") {
+                return Err("network idle watching is only supported on Linux".to_string());
+            }
+            let selector = normalize_and_validate_process_selector(request.process_selector.as_ref())
+                .map_err(|error| {
+                    if error == "process selector is missing" {
+                        "processSelector is required for networkIdle mode".to_string()
+                    } else {
+                        error
+                    }
+                })?;
+            let descriptor = selector
+                .name
+                .clone()
+                .or_else(|| selector.pid.map(|pid| format!("PID {pid}")))
+                .ok_or("process selector is empty".to_string())?;
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when {descriptor} has no open connections (stable {process_stable_sec}s)"
+                ),
+                Some(selector),
+            )
+        }
+        ScheduleMode::SystemdUnit => {
+            let Some(watch) = systemd_unit.as_ref() else {
+                return Err("systemdUnit is required for systemdUnit mode".to_string());
+            };
+            let qualifier = if watch.require_success {
+                ", success only"
+            } else {
+                ""
+            };
+            (
+                None,
+                None,
+                None,
+                format!(
+                    "Shutdown when {} finishes (stable {process_stable_sec}s{qualifier})",
+                    watch.unit
+                ),
+                None,
+            )
+        }
+    };
+
+    let delay_summary = post_condition_delay_sec
+        .map(|delay| format!(" + delay {}m {}s", delay / 60, delay % 60))
+        .unwrap_or_default();
+    let action = validate_schedule_action(request.action.as_ref())?;
+    let action_summary = match &action {
+        ScheduleAction::Shutdown => String::new(),
+        ScheduleAction::RunCommand { command, .. } => {
+            format!("; run `{}` instead of shutdown", describe_command(command))
+        }
+    };
+    let summary = format!("{summary}{delay_summary}{window_summary}{action_summary}");
+
+    store.id_seq += 1;
+    let id = format!("sch-{}-{}", now, store.id_seq);
+    let final_warning_duration_sec =
+        final_warning_sec_for_mode(&mode, store.settings.final_warning_sec);
+
+    let mut next = ActiveSchedule {
+        id,
+        mode,
+        summary,
+        armed_at_ms: now,
+        trigger_at_ms,
+        target_local_time,
+        target_tz_offset_minutes,
+        pre_alerts,
+        fired_alerts: Vec::new(),
+        process_selector,
+        process_tree_pids: Vec::new(),
+        process_stable_sec,
+        process_missing_since_ms: None,
+        process_instance_threshold,
+        process_instance_count: None,
+        launch_command,
+        launch_exit_policy,
+        launch_pid: None,
+        launch_exit_code: None,
+        launch_exited_at_ms: None,
+        systemd_unit,
+        unit_seen_active: false,
+        network_connection_count: None,
+        port_watch,
+        port_open: None,
+        log_watch,
+        log_matched_line: None,
+        log_start,
+        marker_watch,
+        http_watch,
+        media_watch,
+        battery_threshold_percent,
+        require_battery_power: request.require_battery_power,
+        battery_guard_holding: false,
+        thermal_watch,
+        thermal_peak_celsius: None,
+        uptime_limit_sec,
+        boot_time_ms,
+        duration_sec,
+        not_before_ms,
+        deadline_ms,
+        final_warning_deferred: false,
+        post_condition_delay_sec,
+        post_delay_started_at_ms: None,
+        paused_at_ms: None,
+        postpone_count: 0,
+        postponed_total_sec: 0,
+        action,
+        condition_met_since_ms: None,
+        condition_detail: None,
+        snooze_until_ms: None,
+        process_match_degraded_logged: false,
+        status: ScheduleStatus::Armed,
+        final_warning_started_at_ms: None,
+        final_warning_duration_sec,
+        shutdown_at_ms: None,
+        shutdown_initiated_at_ms: None,
+    };
+    let _ = sync_shutdown_at_ms(&mut next);
+    Ok(next)
+}
+
+fn process_exit_summary(
+    selector: &ProcessSelector,
+    instance_threshold: Option<u32>,
+    stable_sec: u64,
+) -> Result<String, String> {
+    let descriptor = selector
+        .name
+        .clone()
+        .or_else(|| selector.pid.map(|pid| format!("PID {pid}")))
+        .ok_or("process selector is empty".to_string())?;
+    Ok(match instance_threshold {
+        Some(threshold) if threshold > 1 => format!(
+            "Shutdown when fewer than {threshold} {descriptor} instances remain (stable {stable_sec}s)"
+        ),
+        _ => format!("Shutdown when {descriptor} exits (stable {stable_sec}s)"),
+    })
+}
+
+fn resolve_state_path(app: &AppHandle) -> PathBuf {
+    let mut dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."));
+    dir.push(STATE_FILE_NAME);
+    dir
+}
+
+fn send_desktop_notification(app: &AppHandle, title: &str, body: &str) {
+    let _ = app.notification().builder().title(title).body(body).show();
+}
+
+fn normalize_selector_text(value: Option<&String>) -> Option<String> {
+    value
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+}
+
+fn no_fail_open_process_exit_reason(error: &str) -> String {
+    format!("NO_FAIL_OPEN_PROCESS_EXIT: process-exit selector invalid ({error}); cancelled for safety")
+}
+
+fn reset_process_exit_on_selector_failure(active: &mut ActiveSchedule) {
+    active.process_missing_since_ms = None;
+    active.process_tree_pids.clear();
+    active.process_instance_count = None;
+    active.final_warning_started_at_ms = None;
+    active.shutdown_initiated_at_ms = None;
+}
+
+fn apply_process_exit_fail_safe_cancel(
+    store: &mut SchedulerStore,
+    schedule_id: &str,
+    reason: String,
+) {
+    push_event(
+        store,
+        Some(schedule_id.to_string()),
+        "failed",
+        "error",
+        Some(reason),
+    );
+    if store
+        .active
+        .as_ref()
+        .map(|active| active.id.as_str())
+        == Some(schedule_id)
+    {
+        store.active = None;
+    }
+}
+
+fn normalize_and_validate_process_selector(
+    selector: Option<&ProcessSelector>,
+) -> Result<ProcessSelector, String> {
+    let Some(selector) = selector else {
+        return Err("process selector is missing".to_string());
+    };
+
+    let mut normalized = selector.clone();
+    normalized.name = normalize_selector_text(normalized.name.as_ref());
+    normalized.executable = normalize_selector_text(normalized.executable.as_ref());
+    normalized.cmdline_contains = normalize_selector_text(normalized.cmdline_contains.as_ref());
+
+    if normalized.pid.is_none() && normalized.name.is_none() {
+        return Err("process selector is empty".to_string());
+    }
+
+    let shell_like = normalized
+        .name
+        .as_deref()
+        .map(is_shell_like_process_name)
+        .unwrap_or(false);
+    let has_advanced = normalized.executable.is_some() || normalized.cmdline_contains.is_some();
+    if shell_like && !has_advanced {
+        return Err(
+            "shell 계열 프로세스는 오탐 방지를 위해 executable 또는 cmdlineContains가 필요합니다."
+                .to_string(),
+        );
+    }
+
+    Ok(normalized)
+}
+
+fn normalize_and_validate_launch_command(
+    command: Option<&LaunchCommand>,
+) -> Result<LaunchCommand, String> {
+    let Some(command) = command else {
+        return Err("launchCommand is required for launchAndWatch mode".to_string());
+    };
+
+    let program = normalize_selector_text(Some(&command.program))
+        .ok_or("launchCommand.program must not be empty".to_string())?;
+    let working_dir = normalize_selector_text(command.working_dir.as_ref());
+    if let Some(dir) = working_dir.as_ref() {
+        if !Path::new(dir).is_dir() {
+            return Err(format!("launchCommand.workingDir does not exist: {dir}"));
+        }
+    }
+
+    Ok(LaunchCommand {
+        program,
+        args: command.args.clone(),
+        working_dir,
+    })
+}
+
+fn normalize_and_validate_systemd_unit(
+    watch: Option<&SystemdUnitWatch>,
+) -> Result<SystemdUnitWatch, String> {
+    if !cfg!(target_os = "linux") {
+        return Err("systemd unit watching is only supported on Linux".to_string());
+    }
+    let Some(watch) = watch else {
+        return Err("systemdUnit is required for systemdUnit mode".to_string());
+    };
+
+    let unit = normalize_selector_text(Some(&watch.unit))
+        .ok_or("systemdUnit.unit must not be empty".to_string())?;
+    if unit.chars().any(|item| item.is_whitespace() || item == '/') {
+        return Err(format!("systemdUnit.unit is not a valid unit name: {unit}"));
+    }
+    let unit = if unit.contains('.') {
+        unit
+    } else {
+        format!("{unit}.service")
+    };
+
+    Ok(SystemdUnitWatch {
+        unit,
+        require_success: watch.require_success,
+    })
+}
+
+fn validate_log_watch(watch: Option<&LogWatch>) -> Result<LogWatch, String> {
+    let Some(watch) = watch else {
+        return Err("logWatch is required for logPattern mode".to_string());
+    };
+    let path = normalize_selector_text(Some(&watch.path))
+        .ok_or("logWatch.path must not be empty".to_string())?;
+    if Path::new(&path).is_dir() {
+        return Err(format!("logWatch.path is a directory: {path}"));
+    }
+    if watch.pattern.trim().is_empty() {
+        return Err("logWatch.pattern must not be empty".to_string());
+    }
+    if watch.pattern.chars().count() > LOG_PATTERN_MAX_CHARS {
+        return Err(format!(
+            "logWatch.pattern must be at most {LOG_PATTERN_MAX_CHARS} characters"
+        ));
+    }
+    regex::Regex::new(&watch.pattern)
+        .map_err(|error| format!("logWatch.pattern is not a valid regex: {error}"))?;
+
+    Ok(LogWatch {
+        path,
+        pattern: watch.pattern.clone(),
+        only_new_lines: watch.only_new_lines,
+    })
+}
+
+fn validate_marker_watch(watch: Option<&MarkerWatch>) -> Result<MarkerWatch, String> {
+    let Some(watch) = watch else {
+        return Err("markerWatch is required for markerFile mode".to_string());
+    };
+    let path = normalize_selector_text(Some(&watch.path))
+        .ok_or("markerWatch.path must not be empty".to_string())?;
+    let parent = Path::new(&path)
+        .parent()
+        .map(|parent| parent.display().to_string())
+        .unwrap_or_default();
+    if has_glob(&parent) {
+        return Err("markerWatch.path only supports * and ? in the file name".to_string());
+    }
+
+    Ok(MarkerWatch {
+        path,
+        condition: watch.condition,
+    })
+}
+
+fn validate_http_watch(watch: Option<&HttpWatch>) -> Result<HttpWatch, String> {
+    let Some(watch) = watch else {
+        return Err("httpWatch is required for httpPoll mode".to_string());
+    };
+    let url = watch.url.trim().to_string();
+    let expression = watch.expression.trim().to_string();
+    parse_loopback_url(&url)?;
+    parse_json_expression(&expression)?;
+
+    Ok(HttpWatch { url, expression })
+}
+
+fn validate_thermal_watch(watch: Option<&ThermalWatch>) -> Result<ThermalWatch, String> {
+    let Some(watch) = watch else {
+        return Err("thermalWatch is required for thermalHigh mode".to_string());
+    };
+    if !cfg!(target_os = "linux") {
+        return Err("temperature watching is only supported on Linux".to_string());
+    }
+    let sensor = normalize_selector_text(Some(&watch.sensor))
+        .ok_or("thermalWatch.sensor must not be empty".to_string())?;
+    if !(40.0..=120.0).contains(&watch.threshold_celsius) {
+        return Err("thermalWatch.thresholdCelsius must be within 40..=120".to_string());
+    }
+
+    Ok(ThermalWatch {
+        sensor,
+        threshold_celsius: watch.threshold_celsius,
+    })
+}
+
+fn validate_port_watch(watch: Option<&PortWatch>) -> Result<PortWatch, String> {
+    let Some(watch) = watch else {
+        return Err("portWatch is required for portClosed mode".to_string());
+    };
+    if watch.port == 0 {
+        return Err("portWatch.port must be between 1 and 65535".to_string());
+    }
+    match (watch.probe, watch.protocol) {
+        (PortProbe::Connect, PortProtocol::Udp) => {
+            Err("connect probing is only available for tcp ports".to_string())
+        }
+        (PortProbe::Listening, _) if !cfg!(target_os = "linux") => {
+            Err("listening-port probing is only supported on Linux; use connect".to_string())
+        }
+        _ => Ok(watch.clone()),
+    }
+}
+
+fn normalize_selector_path(value: Option<&String>) -> Option<String> {
+    normalize_selector_text(value).map(|path| path.replace('\\', "/").to_lowercase())
+}
+
+fn is_shell_like_process_name(name: &str) -> bool {
+    let normalized = name.to_lowercase();
+    normalized.contains("powershell")
+        || normalized.contains("pwsh")
+        || normalized == "bash"
+        || normalized.ends_with("/bash")
+        || normalized == "zsh"
+        || normalized.ends_with("/zsh")
+        || normalized == "sh"
+        || normalized.ends_with("/sh")
+}
+
+fn force_simulate_from_env() -> bool {
+    std::env::var("AUTOSD_FORCE_SIMULATE_ONLY")
+        .map(|value| {
+            let normalized = value.trim().to_ascii_lowercase();
+            !matches!(normalized.as_str(), "" | "0" | "false" | "off")
+        })
+        .unwrap_or(false)
+        || std::env::var("CI")
+            .map(|value| value.trim().eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+}
+
+fn run_graceful_close(
+    app: &AppHandle,
+    schedule_id: &str,
+    settings: &AppSettings,
+) -> Option<String> {
+    let close = settings.graceful_close.as_ref()?;
+    let state = app.state::<AppState>();
+    let record = |result: &str, reason: String| {
+        let mut store = lock_store(&state.store);
+        push_event(
+            &mut store,
+            Some(schedule_id.to_string()),
+            "graceful_close",
+            result,
+            Some(reason),
+        );
+        let _ = state.persist_locked(&store);
+    };
+
+    let mut scanner = ProcessScanner::new();
+    let own_pid = std::process::id();
+    let mut pids = close
+        .selectors
+        .iter()
+        .flat_map(|selector| scanner.close_targets(selector))
+        .filter(|pid| *pid != own_pid)
+        .collect::<Vec<_>>();
+    pids.sort_unstable();
+    pids.dedup();
+    let labels = pids
+        .iter()
+        .map(|pid| {
+            let name = scanner.process_name(*pid).unwrap_or_else(|| "?".to_string());
+            (*pid, format!("{name} ({pid})"))
+        })
+        .collect::<HashMap<_, _>>();
+    let label = |pid: &u32| labels[pid].clone();
+
+    if pids.is_empty() {
+        record("ok", "no matching processes to close".to_string());
+        return None;
+    }
+    if settings.simulate_only || force_simulate_from_env() {
+        let names = pids.iter().map(label).collect::<Vec<_>>().join(", ");
+        record("ok", format!("DRY_RUN would ask to close: {names}"));
+        return None;
+    }
+
+    // Pids that would not take the request or outlived the timeout, with the reason why.
+    let mut lingering = Vec::new();
+    let mut waiting = Vec::new();
+    for pid in pids {
+        match scanner.request_close(pid) {
+            Ok(()) => waiting.push(pid),
+            Err(error) => lingering.push((pid, error)),
+        }
+    }
+    let deadline = Instant::now() + Duration::from_secs(close.timeout_sec);
+    let mut remaining = scanner.alive_pids(&waiting);
+    while !remaining.is_empty() && Instant::now() < deadline {
+        thread::sleep(GRACEFUL_CLOSE_POLL_INTERVAL);
+        remaining = scanner.alive_pids(&remaining);
+    }
+    let mut report = GracefulCloseReport {
+        closed: waiting
+            .iter()
+            .filter(|pid| !remaining.contains(pid))
+            .map(label)
+            .collect(),
+        ..GracefulCloseReport::default()
+    };
+    lingering.extend(remaining.into_iter().map(|pid| (pid, "still running".to_string())));
+    let lingering_pids = lingering.iter().map(|(pid, _)| *pid).collect::<Vec<_>>();
+    let still_alive = scanner.alive_pids(&lingering_pids);
+    lingering.retain(|(pid, _)| {
+        let exited = !still_alive.contains(pid);
+        if exited {
+            report.closed.push(label(pid));
+        }
+        !exited
+    });
+
+    for (pid, why) in &lingering {
+        if close.on_timeout == LingeringProcessPolicy::Escalate && scanner.force_kill(*pid) {
+            report.killed.push(label(pid));
+        } else {
+            report.refused.push(format!("{} [{why}]", label(pid)));
+        }
+    }
+
+    let result = if report.refused.is_empty() { "ok" } else { "error" };
+    record(result, report.event_reason());
+    match close.on_timeout {
+        LingeringProcessPolicy::Abort if !lingering.is_empty() => Some(format!(
+            "{} process(es) did not close gracefully; shutdown aborted",
+            lingering.len()
+        )),
+        _ => None,
+    }
+}
+
+// Dry runs execute the hooks too, so the sequence can be rehearsed.
+fn run_pre_shutdown_hooks(
+    app: &AppHandle,
+    schedule_id: &str,
+    settings: &AppSettings,
+) -> Option<String> {
+    let state = app.state::<AppState>();
+    let dry_run = settings.simulate_only || force_simulate_from_env();
+    for hook in &settings.pre_shutdown_hooks {
+        let run = run_with_timeout(&hook.command, Duration::from_secs(hook.timeout_sec));
+        let (result, reason) = shutdown_hook_event(hook, &run, dry_run);
+        let mut store = lock_store(&state.store);
+        push_event(
+            &mut store,
+            Some(schedule_id.to_string()),
+            "pre_shutdown_hook",
+            result,
+            Some(reason),
+        );
+        let _ = state.persist_locked(&store);
+        if result != "ok" && hook.abort_on_failure {
+            return Some(hook.name.clone());
+        }
+    }
+    None
+}
+
+fn run_shutdown_command(settings: &AppSettings) -> Result<ShutdownDispatchReport, String> {
+    let force_simulate = force_simulate_from_env();
+
+    #[cfg(target_os = "windows")]
+    let dispatch = ShutdownDispatchReport {
+        command_line: format!("shutdown /s /t {WINDOWS_ABORTABLE_SHUTDOWN_SEC}"),
+        abort_hint: Some("shutdown /a".to_string()),
+        dry_run: settings.simulate_only || force_simulate,
+    };
+
+    #[cfg(target_os = "macos")]
+    let dispatch = ShutdownDispatchReport {
+        command_line: "osascript -e \"tell application \\\"System Events\\\" to shut down\""
+            .to_string(),
+        abort_hint: None,
+        dry_run: settings.simulate_only || force_simulate,
+    };
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let dispatch = ShutdownDispatchReport {
+        command_line: "shutdown command unsupported on this OS".to_string(),
+        abort_hint: None,
+        dry_run: settings.simulate_only || force_simulate,
+    };
+
+    if dispatch.dry_run {
+        return Ok(dispatch);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let timeout_arg = WINDOWS_ABORTABLE_SHUTDOWN_SEC.to_string();
+        let status = Command::new("shutdown")
+            .args(["/s", "/t", timeout_arg.as_str()])
+            .status()
+            .map_err(|error| format!("failed to run windows shutdown command: {error}"))?;
+        if status.success() {
+            return Ok(dispatch);
+        }
+        return Err(format!("windows shutdown failed with status: {status}"));
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let status = Command::new("osascript")
+            .args(["-e", "tell application \"System Events\" to shut down"])
+            .status()
+            .map_err(|error| format!("failed to run macOS shutdown command: {error}"))?;
+        if status.success() {
+            return Ok(dispatch);
+        }
+        return Err(format!("macOS shutdown failed with status: {status}"));
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        Err("unsupported OS for shutdown in this MVP".to_string())
+    }
+}
+
+fn cancel_active_schedule_internal(
+    app: &AppHandle,
+    reason: &str,
+    emit_notification: bool,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut store = lock_store(&state.store);
+    let Some(active) = store.active.as_ref() else {
+        return Ok(());
+    };
+
+    if is_shutdown_execution_started(active) {
+        return Err("shutdown has already started; cannot cancel".to_string());
+    }
+
+    let schedule_id = active.id.clone();
+
+    let undo_window_sec = store.settings.undo_cancel_window_sec;
+    let cancelled = store.active.take();
+    store.cancelled = cancelled
+        .filter(|_| undo_window_sec > 0)
+        .map(|schedule| CancelledSchedule {
+            schedule,
+            cancelled_at_ms: now_ms(),
+        });
+    push_event(
+        &mut store,
+        Some(schedule_id),
+        "cancelled",
+        "ok",
+        Some(reason.to_string()),
+    );
+    clear_schedule_queue(&mut store, "the schedule they waited for was cancelled");
+    state.persist_locked(&store)?;
+    drop(store);
+    refresh_tray_indicator(app);
+
+    if emit_notification {
+        let body = if undo_window_sec > 0 {
+            format!("{reason} (undo available for {undo_window_sec}s)")
+        } else {
+            reason.to_string()
+        };
+        send_desktop_notification(app, "Auto Shutdown Scheduler", &body);
+    }
+
+    Ok(())
+}
+
+fn undo_cancel_until_ms(store: &SchedulerStore, now: i64) -> Option<i64> {
+    let cancelled = store.cancelled.as_ref()?;
+    let until_ms = cancelled
+        .cancelled_at_ms
+        .saturating_add((store.settings.undo_cancel_window_sec as i64) * 1000);
+    (store.active.is_none() && now < until_ms).then_some(until_ms)
+}
+
+// A schedule cancelled during final warning gets a fresh warning.
+fn restore_cancelled_schedule(store: &mut SchedulerStore, now: i64) -> Result<String, String> {
+    if store.active.is_some() {
+        return Err("another schedule is already active; cancel it first".to_string());
+    }
+    if undo_cancel_until_ms(store, now).is_none() {
+        store.cancelled = None;
+        return Err("there is no cancelled schedule to restore".to_string());
+    }
+    let Some(CancelledSchedule {
+        schedule: mut restored,
+        cancelled_at_ms,
+    }) = store.cancelled.take()
+    else {
+        return Err("there is no cancelled schedule to restore".to_string());
+    };
+
+    if restored.status == ScheduleStatus::FinalWarning {
+        restored.final_warning_started_at_ms = Some(now);
+    }
+    restored.shutdown_initiated_at_ms = None;
+    let _ = sync_shutdown_at_ms(&mut restored);
+    let schedule_id = restored.id.clone();
+    let summary = restored.summary.clone();
+    store.active = Some(restored);
+    push_event(
+        store,
+        Some(schedule_id),
+        "cancel_undone",
+        "ok",
+        Some(format!(
+            "{summary} (restored {}s after cancel)",
+            (now - cancelled_at_ms).max(0) / 1000
+        )),
+    );
+    Ok(summary)
+}
+
+fn undo_cancel_internal(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut store = lock_store(&state.store);
+    let summary = restore_cancelled_schedule(&mut store, now_ms())?;
+    state.persist_locked(&store)?;
+    drop(store);
+    refresh_tray_indicator(app);
+
+    send_desktop_notification(
+        app,
+        "Auto Shutdown Scheduler",
+        &format!("Schedule restored: {summary}"),
+    );
+    Ok(())
+}
+
+fn set_schedule_paused_internal(app: &AppHandle, pause: bool, reason: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut store = lock_store(&state.store);
+    let Some(active) = store.active.as_mut() else {
+        return Err("no active schedule to pause or resume".to_string());
+    };
+
+    let schedule_id = active.id.clone();
+    let now = now_ms();
+    let (event_type, detail) = if pause {
+        pause_active(active, now)?;
+        let remaining_sec = remaining_trigger_ms(active, now) / 1000;
+        ("paused", format!("{reason} ({remaining_sec}s remaining)"))
+    } else {
+        let paused_for_sec = resume_active(active, now)? / 1000;
+        let remaining_sec = remaining_trigger_ms(active, now) / 1000;
+        (
+            "resumed",
+            format!("{reason} (paused {paused_for_sec}s, {remaining_sec}s remaining)"),
+        )
+    };
+
+    push_event(&mut store, Some(schedule_id), event_type, "ok", Some(detail));
+    state.persist_locked(&store)?;
+    drop(store);
+    refresh_tray_indicator(app);
+    Ok(())
+}
+
+fn refresh_tray_indicator(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let now = now_ms();
+    let (paused, snooze_blocked, undo_available) = {
+        let store = lock_store(&state.store);
+        let undo_available = undo_cancel_until_ms(&store, now).is_some();
+        match store.active.as_ref() {
+            Some(active) => (
+                active.paused_at_ms.is_some(),
+                postpone_block_reason(&store.settings, active, now).is_some(),
+                undo_available,
+            ),
+            None => (false, false, undo_available),
+        }
+    };
+
+    let mut runtime = lock_runtime(&state.runtime);
+    let paused_changed = runtime.tray_paused != paused;
+    let snooze_changed = runtime.tray_snooze_blocked != snooze_blocked;
+    let undo_changed = runtime.tray_undo_available != undo_available;
+    runtime.tray_paused = paused;
+    runtime.tray_snooze_blocked = snooze_blocked;
+    runtime.tray_undo_available = undo_available;
+    drop(runtime);
+
+    if paused_changed {
+        if let Some(tray) = app.tray_by_id(TRAY_ID) {
+            let _ = tray.set_tooltip(Some(tray_tooltip(paused)));
+        }
+    }
+    if !snooze_changed && !undo_changed {
+        return;
+    }
+    let Some(menu) = app.try_state::<TrayMenu>() else {
+        return;
+    };
+    let items = menu.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let Some(items) = items.as_ref() else {
+        return;
+    };
+    if snooze_changed {
+        let label = if snooze_blocked {
+            "Snooze limit reached"
+        } else {
+            "Snooze 10m"
+        };
+        let _ = items.postpone.set_text(label);
+        let _ = items.postpone.set_enabled(!snooze_blocked);
+    }
+    if undo_changed {
+        let _ = items.undo_cancel.set_enabled(undo_available);
+    }
+}
+
+fn tray_tooltip(paused: bool) -> String {
+    if paused {
+        format!("{TRAY_TOOLTIP} · 일시정지됨")
+    } else {
+        TRAY_TOOLTIP.to_string()
+    }
+}
+
+fn postpone_schedule_internal(
+    app: &AppHandle,
+    target: PostponeTarget,
+    reason: &str,
+) -> Result<(), PostponeError> {
+    let state = app.state::<AppState>();
+    let mut store = lock_store(&state.store);
+    let Some(active) = store.active.as_ref() else {
+        return Err("no active schedule to postpone".to_string().into());
+    };
+
+    let schedule_id = active.id.clone();
+    let now = now_ms();
+    let mut next = active.clone();
+    let outcome = postpone_active(&mut next, target, now)
+        .map_err(PostponeError::from)
+        .and_then(|delay_sec| {
+            check_postpone_limits(&store.settings, active, &next, now).map(|_| delay_sec)
+        });
+    let delay_sec = match outcome {
+        Ok(delay_sec) => delay_sec,
+        Err(error) => {
+            if error.is_limit() {
+                push_event(
+                    &mut store,
+                    Some(schedule_id),
+                    "postpone_refused",
+                    "error",
+                    Some(format!("{reason}: {}", error.message)),
+                );
+                let _ = state.persist_locked(&store);
+                drop(store);
+                refresh_tray_indicator(app);
+                send_desktop_notification(
+                    app,
+                    "Auto Shutdown Scheduler",
+                    &format!("Snooze refused: {}.", error.message),
+                );
+            }
+            return Err(error);
+        }
+    };
+
+    let detail = format!(
+        "{reason} (+{delay_sec}s, postponed {}x, {}s total)",
+        next.postpone_count, next.postponed_total_sec
+    );
+    store.active = Some(next);
+    push_event(&mut store, Some(schedule_id), "postponed", "ok", Some(detail));
+    state.persist_locked(&store)?;
+    drop(store);
+    refresh_tray_indicator(app);
+
+    let body = match target {
+        PostponeTarget::Minutes(minutes) => format!("Schedule postponed by {minutes} minutes."),
+        PostponeTarget::Until(until_ms) => format!(
+            "Schedule postponed until {}.",
+            format_local_timestamp_ms(until_ms)
+        ),
+    };
+    send_desktop_notification(app, "Auto Shutdown Scheduler", &body);
+
+    Ok(())
+}
+
+fn execute_active_shutdown(app: &AppHandle, schedule_id: String) {
+    let state = app.state::<AppState>();
+    let settings = {
+        let mut store = lock_store(&state.store);
+        let now = now_ms();
+
+        let Some(active) = store.active.as_mut() else {
+            return;
+        };
+
+        if active.id != schedule_id {
+            return;
+        }
+
+        if !try_mark_shutdown_initiated(active, now) {
+            return;
+        }
+        let _ = sync_shutdown_at_ms(active);
+
+        let action = active.action.clone();
+        let starting = match action {
+            ScheduleAction::Shutdown => "shutdown command starting",
+            ScheduleAction::RunCommand { .. } => "action command starting",
+        };
+        let reason = match active.thermal_peak_celsius {
+            Some(peak) => format!("final warning elapsed; {starting} (peak {peak:.1}°C)"),
+            None => format!("final warning elapsed; {starting}"),
+        };
+        push_event(
+            &mut store,
+            Some(schedule_id.clone()),
+            "shutdown_initiated",
+            "ok",
+            Some(reason),
+        );
+        let settings = store.settings.clone();
+        if let Err(error) = state.persist_locked(&store) {
+            send_desktop_notification(
+                app,
+                "Auto Shutdown Scheduler",
+                &format!("Warning: failed to persist shutdown initiation state: {error}"),
+            );
+        }
+        if let ScheduleAction::RunCommand {
+            command,
+            timeout_sec,
+        } = action
+        {
+            drop(store);
+            execute_run_command_action(app, schedule_id, command, timeout_sec, &settings);
+            return;
+        }
+        settings
+    };
+
+    // Closing processes and hooks can take minutes; keep them off the scheduler thread.
+    let app = app.clone();
+    thread::spawn(move || {
+        let abort_reason = run_graceful_close(&app, &schedule_id, &settings).or_else(|| {
+            run_pre_shutdown_hooks(&app, &schedule_id, &settings)
+                .map(|hook_name| format!("pre-shutdown hook {hook_name} failed; shutdown aborted"))
+        });
+        complete_active_shutdown(&app, &schedule_id, &settings, abort_reason);
+    });
+}
+
+fn complete_active_shutdown(
+    app: &AppHandle,
+    schedule_id: &str,
+    settings: &AppSettings,
+    abort_reason: Option<String>,
+) {
+    let state = app.state::<AppState>();
+    if let Some(reason) = abort_reason {
+        let mut store = lock_store(&state.store);
+        push_event(
+            &mut store,
+            Some(schedule_id.to_string()),
+            "failed",
+            "error",
+            Some(reason.clone()),
+        );
+        if store
+            .active
+            .as_ref()
+            .map(|active| active.id.as_str())
+            == Some(schedule_id)
+        {
+            store.active = None;
+        }
+        let _ = state.persist_locked(&store);
+        drop(store);
+        send_desktop_notification(app, "Auto Shutdown Scheduler", &reason);
+        return;
+    }
+
+    let result = run_shutdown_command(settings);
+
+    let mut store = lock_store(&state.store);
+    match result {
+        Ok(dispatch) => {
+            let event_reason = Some(dispatch.log_line());
+            push_event(
+                &mut store,
+                Some(schedule_id.to_string()),
+                "executed",
+                "ok",
+                event_reason,
+            );
+            if store
+                .active
+                .as_ref()
+                .map(|active| active.id.as_str())
+                == Some(schedule_id)
+            {
+                store.active = None;
+            }
+            let _ = state.persist_locked(&store);
+            if dispatch.dry_run {
+                send_desktop_notification(
+                    app,
+                    "Auto Shutdown Scheduler",
+                    &format!(
+                        "Dry run complete. {}",
+                        dispatch.log_line()
+                    ),
+                );
+            }
+        }
+        Err(error) => {
+            push_event(
+                &mut store,
+                Some(schedule_id.to_string()),
+                "failed",
+                "error",
+                Some(error.clone()),
+            );
+            if store
+                .active
+                .as_ref()
+                .map(|active| active.id.as_str())
+                == Some(schedule_id)
+            {
+                store.active = None;
+            }
+            let _ = state.persist_locked(&store);
+            send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
+        }
+    }
+}
+
+// Executed once the command starts; the worker records its exit.
+fn execute_run_command_action(
+    app: &AppHandle,
+    schedule_id: String,
+    command: LaunchCommand,
+    timeout_sec: u64,
+    settings: &AppSettings,
+) {
+    let command_line = describe_command(&command);
+    let state = app.state::<AppState>();
+    let dry_run = settings.simulate_only || force_simulate_from_env();
+    let reason = if dry_run {
+        format!("DRY_RUN_ACTION_COMMAND: {command_line}")
+    } else {
+        format!("ACTION_COMMAND_STARTED: {command_line}")
+    };
+    {
+        let mut store = lock_store(&state.store);
+        push_event(
+            &mut store,
+            Some(schedule_id.clone()),
+            "executed",
+            "ok",
+            Some(reason.clone()),
+        );
+        if store
+            .active
+            .as_ref()
+            .map(|active| active.id.as_str())
+            == Some(schedule_id.as_str())
+        {
+            store.active = None;
+        }
+        let _ = state.persist_locked(&store);
+    }
+    if dry_run {
+        send_desktop_notification(app, "Auto Shutdown Scheduler", &reason);
+        return;
+    }
+
+    let app = app.clone();
+    thread::spawn(move || {
+        let run = run_with_timeout(&command, Duration::from_secs(timeout_sec));
+        let (result, outcome) = timed_run_outcome(&run, timeout_sec);
+        let reason = format!("ACTION_COMMAND_RUN: {command_line}: {outcome}");
+        let state = app.state::<AppState>();
+        let mut store = lock_store(&state.store);
+        push_event(
+            &mut store,
+            Some(schedule_id),
+            "action_command_finished",
+            result,
+            Some(reason.clone()),
+        );
+        let _ = state.persist_locked(&store);
+        drop(store);
+        send_desktop_notification(&app, "Auto Shutdown Scheduler", &reason);
+    });
+}
+
+fn tick_scheduler(app: &AppHandle) {
+    struct PendingNotification {
+        title: String,
+        body: String,
+    }
+
+    enum ProcessScanState {
+        NotRequested,
+        Invalid {
+            schedule_id: String,
+            status: ScheduleStatus,
+            reason: String,
+        },
+        Ready {
+            schedule_id: String,
+            status: ScheduleStatus,
+            result: ProcessMatchResult,
+        },
+    }
+
+    enum ConditionScanState {
+        NotRequested,
+        Ready {
+            schedule_id: String,
+            status: ScheduleStatus,
+            observation: ConditionObservation,
+        },
+    }
+
+    struct ScheduleStop {
+        event_type: &'static str,
+        result: &'static str,
+        reason: String,
+        notification: String,
+    }
+
+    let mut notifications = Vec::<PendingNotification>::new();
+    let mut should_execute = None::<String>;
+    let state = app.state::<AppState>();
+    refresh_tray_indicator(app);
+    let condition_state = {
+        let store = lock_store(&state.store);
+        let active_id = store.active.as_ref().map(|active| active.id.as_str());
+        // A cancelled launch stays tracked while the cancel can still be undone.
+        let undoable_id = undo_cancel_until_ms(&store, now_ms())
+            .and(store.cancelled.as_ref())
+            .map(|cancelled| cancelled.schedule.id.as_str());
+        let keep_ids = active_id.into_iter().chain(undoable_id).collect::<Vec<_>>();
+        lock_launcher(&state.launcher).release_all_except(&keep_ids);
+        {
+            let mut watchers = lock_watchers(&state.watchers);
+            if watchers.log.as_ref().map(LogTailer::schedule_id) != active_id {
+                watchers.log = None;
+            }
+        }
+        match store.active.as_ref() {
+            Some(active)
+                if (active.status == ScheduleStatus::Armed
+                    && active.post_delay_started_at_ms.is_none())
+                    || (active.status == ScheduleStatus::FinalWarning
+                        && !deadline_reached(active, now_ms())) =>
+            {
+                let active = active.clone();
+                drop(store);
+                match observe_condition(&state, &active) {
+                    Some(observation) => ConditionScanState::Ready {
+                        schedule_id: active.id,
+                        status: active.status,
+                        observation,
+                    },
+                    None => ConditionScanState::NotRequested,
+                }
+            }
+            _ => ConditionScanState::NotRequested,
+        }
+    };
+    let boot_time_ms = {
+        let store = lock_store(&state.store);
+        let uptime_armed = store
+            .active
+            .as_ref()
+            .map(|active| {
+                matches!(active.mode, ScheduleMode::UptimeLimit)
+                    && active.status == ScheduleStatus::Armed
+            })
+            .unwrap_or(false);
+        drop(store);
+        if uptime_armed {
+            current_boot_time_ms()
+        } else {
+            None
+        }
+    };
+    let battery_guard = {
+        let store = lock_store(&state.store);
+        let guarded = store
+            .active
+            .as_ref()
+            .map(|active| active.require_battery_power && active.status == ScheduleStatus::Armed)
+            .unwrap_or(false);
+        drop(store);
+        guarded.then(|| lock_watchers(&state.watchers).power.read())
+    };
+    let scan_state = {
+        let store = lock_store(&state.store);
+        let Some(active) = store.active.as_ref() else {
+            return;
+        };
+
+        if !matches!(active.mode, ScheduleMode::ProcessExit)
+            || !matches!(active.status, ScheduleStatus::Armed | ScheduleStatus::FinalWarning)
+            || (active.status == ScheduleStatus::FinalWarning && deadline_reached(active, now_ms()))
+            || active.post_delay_started_at_ms.is_some()
+        {
+            ProcessScanState::NotRequested
+        } else {
+            match normalize_and_validate_process_selector(active.process_selector.as_ref()) {
+                Ok(selector) => {
+                    let schedule_id = active.id.clone();
+                    let status = active.status.clone();
+                    let tracked_pids = active.process_tree_pids.clone();
+                    let instance_threshold = active.process_instance_threshold;
+
+                    drop(store);
+                    let result = {
+                        let mut scanner = lock_scanner(&state.scanner);
+                        scanner.is_process_running(&selector, &tracked_pids, instance_threshold)
+                    };
+
+                    ProcessScanState::Ready {
+                        schedule_id,
+                        status,
+                        result,
+                    }
+                }
+                Err(error) => ProcessScanState::Invalid {
+                    schedule_id: active.id.clone(),
+                    status: active.status.clone(),
+                    reason: no_fail_open_process_exit_reason(&error),
+                },
+            }
+        }
+    };
+
+    {
+        let mut store = lock_store(&state.store);
+        let Some(active) = store.active.as_mut() else {
+            return;
+        };
+
+        let mut changed = false;
+        let mut pending_events = Vec::<(String, Option<String>)>::new();
+        let now = now_ms();
+        let schedule_id = active.id.clone();
+        let mut fail_safe_cancel_reason = None::<String>;
+        let mut schedule_stop = None::<ScheduleStop>;
+        let mut condition_transition = None::<ConditionTransition>;
+        // An unreadable battery cancels rather than guessing AC or battery.
+        let guard_holds = match battery_guard_holds(battery_guard.as_ref()) {
+            Ok(holds) => active.require_battery_power && holds,
+            Err(error) => {
+                fail_safe_cancel_reason = Some(format!(
+                    "NO_FAIL_OPEN_BATTERY_GUARD: {error}; cancelled for safety"
+                ));
+                true
+            }
+        };
+        let mut final_warning_held = false;
+
+        let snoozed = active
+            .snooze_until_ms
+            .map(|snooze_until_ms| now < snooze_until_ms)
+            .unwrap_or(false);
+        let paused = active.paused_at_ms.is_some();
+        let was_armed = active.status == ScheduleStatus::Armed;
+        let mut deadline_forced = false;
+        let notifications_before = notifications.len();
+        if active.status == ScheduleStatus::Armed
+            && deadline_reached(active, now)
+            && !snoozed
+            && !paused
+            && guard_holds
+        {
+            final_warning_held = true;
+        } else if !snoozed && enter_deadline_final_warning(active, now) {
+            changed = true;
+            deadline_forced = true;
+            pending_events.push((
+                "deadline_reached".to_string(),
+                Some("deadline reached; entered final warning regardless of condition".to_string()),
+            ));
+            notifications.push(PendingNotification {
+                title: "Auto Shutdown Scheduler".to_string(),
+                body: deadline_final_warning_notification_body(active.final_warning_duration_sec),
+            });
+        }
+
+        match active.status {
+            // A paused schedule must never reach final warning; its timer resumes on resume.
+            ScheduleStatus::Armed if paused => {}
+            ScheduleStatus::Armed if active.post_delay_started_at_ms.is_some() => {
+                let trigger_at_ms = active.trigger_at_ms.unwrap_or(now);
+                let remaining_sec = if trigger_at_ms > now {
+                    ((trigger_at_ms - now) / 1000) as u64
+                } else {
+                    0
+                };
+
+                for threshold_sec in active.pre_alerts.clone() {
+                    if remaining_sec > 0
+                        && remaining_sec <= threshold_sec
+                        && !active.fired_alerts.contains(&threshold_sec)
+                    {
+                        active.fired_alerts.push(threshold_sec);
+                        changed = true;
+                        pending_events.push((
+                            "alerted".to_string(),
+                            Some(format!("pre-alert fired at {threshold_sec}s")),
+                        ));
+                        notifications.push(PendingNotification {
+                            title: "Auto Shutdown Scheduler".to_string(),
+                            body: pre_alert_notification_body(threshold_sec),
+                        });
+                    }
+                }
+
+                if remaining_sec == 0 && guard_holds {
+                    final_warning_held = true;
+                } else if remaining_sec == 0 {
+                    active.status = ScheduleStatus::FinalWarning;
+                    active.final_warning_started_at_ms = Some(now);
+                    active.post_delay_started_at_ms = None;
+                    active.trigger_at_ms = None;
+                    active.shutdown_initiated_at_ms = None;
+                    changed = true;
+                    pending_events.push((
+                        "final_warning".to_string(),
+                        Some("post-condition delay elapsed; entered shutdown waiting mode".to_string()),
+                    ));
+                    notifications.push(PendingNotification {
+                        title: "Auto Shutdown Scheduler".to_string(),
+                        body: final_warning_notification_body(active.final_warning_duration_sec),
+                    });
+                }
+            }
+            ScheduleStatus::Armed => match active.mode {
+                ScheduleMode::Countdown
+                | ScheduleMode::SpecificTime
+                | ScheduleMode::UptimeLimit => {
+                    if let Some(boot_time_ms) = boot_time_ms {
+                        let previous = active.boot_time_ms;
+                        match realign_uptime_trigger(active, boot_time_ms, now) {
+                            Ok(true) => {
+                                changed = true;
+                                if previous.is_some() {
+                                    pending_events.push((
+                                        "uptime_realigned".to_string(),
+                                        Some("uptime limit realigned to the current boot time".to_string()),
+                                    ));
+                                }
+                            }
+                            Ok(false) => {}
+                            Err(error) => {
+                                fail_safe_cancel_reason = Some(format!(
+                                    "NO_FAIL_OPEN_UPTIME_LIMIT: {error}; cancelled for safety"
+                                ));
+                            }
+                        }
+                    }
+
+                    if matches!(active.mode, ScheduleMode::SpecificTime) {
+                        if let (Some(target_label), Some(saved_offset)) = (
+                            active.target_local_time.clone(),
+                            active.target_tz_offset_minutes,
+                        ) {
+                            let current_offset = Local::now().offset().local_minus_utc() / 60;
+                            if current_offset != saved_offset {
+                                if let Ok(recomputed_ms) = compute_next_local_target_ms(&target_label)
+                                {
+                                    active.trigger_at_ms = Some(recomputed_ms);
+                                    active.target_tz_offset_minutes = Some(current_offset);
+                                    changed = true;
+                                    pending_events.push((
+                                        "timezone_realigned".to_string(),
+                                        Some("specific-time schedule was realigned after timezone change".to_string()),
+                                    ));
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(trigger_at_ms) = active.trigger_at_ms {
+                        let remaining_sec = if trigger_at_ms > now {
+                            ((trigger_at_ms - now) / 1000) as u64
+                        } else {
+                            0
+                        };
+
+                        for threshold_sec in active.pre_alerts.clone() {
+                            if remaining_sec > 0
+                                && remaining_sec <= threshold_sec
+                                && !active.fired_alerts.contains(&threshold_sec)
+                            {
+                                active.fired_alerts.push(threshold_sec);
+                                changed = true;
+                                pending_events.push((
+                                    "alerted".to_string(),
+                                    Some(format!("pre-alert fired at {threshold_sec}s")),
+                                ));
+                                notifications.push(PendingNotification {
+                                    title: "Auto Shutdown Scheduler".to_string(),
+                                    body: pre_alert_notification_body(threshold_sec),
+                                });
+                            }
+                        }
+
+                        if remaining_sec == 0 && guard_holds {
+                            final_warning_held = true;
+                        } else if remaining_sec == 0 {
+                            active.status = ScheduleStatus::FinalWarning;
+                            active.final_warning_started_at_ms = Some(now);
+                            active.shutdown_initiated_at_ms = None;
+                            changed = true;
+                            pending_events.push((
+                                "final_warning".to_string(),
+                                Some("entered shutdown waiting mode (final warning stage)".to_string()),
+                            ));
+                            notifications.push(PendingNotification {
+                                title: "Auto Shutdown Scheduler".to_string(),
+                                body: final_warning_notification_body(
+                                    active.final_warning_duration_sec,
+                                ),
+                            });
+                        }
+                    }
+                }
+                ScheduleMode::ProcessExit => {
+                    if let Some(snooze_until_ms) = active.snooze_until_ms {
+                        if now >= snooze_until_ms {
+                            active.snooze_until_ms = None;
+                            changed = true;
+                        }
+                    }
+                    match &scan_state {
+                        ProcessScanState::Ready {
+                            schedule_id: scanned_id,
+                            status,
+                            result,
+                        } if scanned_id == &schedule_id && status == &active.status => {
+                            let match_result = result.clone();
+                            active.process_tree_pids = match_result.matched_pids.clone();
+                            if let Some(reason) =
+                                record_process_instance_count(active, match_result.instance_count)
+                            {
+                                changed = true;
+                                pending_events
+                                    .push(("process_threshold_crossed".to_string(), Some(reason)));
+                            }
+
+                            if match_result.degraded_to_name && !active.process_match_degraded_logged
+                            {
+                                active.process_match_degraded_logged = true;
+                                changed = true;
+                                pending_events.push((
+                                    "process_match_degraded".to_string(),
+                                    Some(
+                                        "advanced process matching unavailable; fell back to name matching"
+                                            .to_string(),
+                                    ),
+                                ));
+                            }
+
+                            if match_result.running {
+                                if active.process_missing_since_ms.is_some() {
+                                    active.process_missing_since_ms = None;
+                                    changed = true;
+                                }
+                            } else {
+                                if active.process_missing_since_ms.is_none() {
+                                    active.process_missing_since_ms = Some(now);
+                                    changed = true;
+                                }
+
+                                let missing_for =
+                                    now - active.process_missing_since_ms.unwrap_or(now);
+                                if missing_for >= (active.process_stable_sec as i64) * 1000 {
+                                    let snoozed = active
+                                        .snooze_until_ms
+                                        .map(|snooze_until_ms| now < snooze_until_ms)
+                                        .unwrap_or(false);
+
+                                    if !snoozed && not_before_pending(active, now) {
+                                        condition_transition = Some(ConditionTransition::Deferred);
+                                    } else if !snoozed && start_post_condition_delay(active, now) {
+                                        condition_transition = Some(ConditionTransition::DelayStarted {
+                                            reason: "target process exited".to_string(),
+                                        });
+                                    } else if !snoozed && guard_holds {
+                                        final_warning_held = true;
+                                    } else if !snoozed {
+                                        active.status = ScheduleStatus::FinalWarning;
+                                        active.final_warning_started_at_ms = Some(now);
+                                        active.process_missing_since_ms = None;
+                                        active.shutdown_initiated_at_ms = None;
+                                        changed = true;
+                                        pending_events.push((
+                                            "final_warning".to_string(),
+                                            Some("target process exited; entered shutdown waiting mode".to_string()),
+                                        ));
+                                        notifications.push(PendingNotification {
+                                            title: "Auto Shutdown Scheduler".to_string(),
+                                            body: process_exit_final_warning_notification_body(
+                                                active.final_warning_duration_sec,
+                                            ),
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                        ProcessScanState::Invalid {
+                            schedule_id: scanned_id,
+                            status,
+                            reason,
+                        } if scanned_id == &schedule_id && status == &active.status => {
+                            if fail_safe_cancel_reason.is_none() {
+                                fail_safe_cancel_reason = Some(reason.clone());
+                            }
+                            reset_process_exit_on_selector_failure(active);
+                            changed = true;
+                        }
+                        ProcessScanState::Ready { .. } | ProcessScanState::Invalid { .. } => {}
+                        ProcessScanState::NotRequested => {}
+                    }
+                }
+                ScheduleMode::LaunchAndWatch
+                | ScheduleMode::SystemdUnit
+                | ScheduleMode::NetworkIdle
+                | ScheduleMode::PortClosed
+                | ScheduleMode::LogPattern
+                | ScheduleMode::MarkerFile
+                | ScheduleMode::HttpPoll
+                | ScheduleMode::MediaIdle
+                | ScheduleMode::BatteryLow
+                | ScheduleMode::ThermalHigh => {
+                    if let Some(snooze_until_ms) = active.snooze_until_ms {
+                        if now >= snooze_until_ms {
+                            active.snooze_until_ms = None;
+                            changed = true;
+                        }
+                    }
+                    if let ConditionScanState::Ready {
+                        schedule_id: scanned_id,
+                        status,
+                        observation,
+                    } = &condition_state
+                    {
+                        if scanned_id == &schedule_id && status == &active.status {
+                            condition_transition = Some(apply_condition_observation(
+                                active,
+                                observation,
+                                now,
+                                guard_holds,
+                                &mut pending_events,
+                            ));
+                        }
+                    }
+                }
+            },
+            ScheduleStatus::FinalWarning => {
+                let mut reverted = false;
+                if matches!(active.mode, ScheduleMode::ProcessExit) {
+                    match &scan_state {
+                        ProcessScanState::Ready {
+                            schedule_id: scanned_id,
+                            status,
+                            result,
+                        } if scanned_id == &schedule_id && status == &active.status => {
+                            let match_result = result.clone();
+                            active.process_tree_pids = match_result.matched_pids.clone();
+                            if let Some(reason) =
+                                record_process_instance_count(active, match_result.instance_count)
+                            {
+                                changed = true;
+                                pending_events
+                                    .push(("process_threshold_crossed".to_string(), Some(reason)));
+                            }
+
+                            if match_result.degraded_to_name && !active.process_match_degraded_logged {
+                                active.process_match_degraded_logged = true;
+                                changed = true;
+                                pending_events.push((
+                                    "process_match_degraded".to_string(),
+                                    Some(
+                                        "advanced process matching unavailable; fell back to name matching"
+                                            .to_string(),
+                                    ),
+                                ));
+                            }
+
+                            if match_result.running {
+                                active.status = ScheduleStatus::Armed;
+                                active.final_warning_started_at_ms = None;
+                                active.process_missing_since_ms = None;
+                                active.shutdown_initiated_at_ms = None;
+                                changed = true;
+                                reverted = true;
+                                pending_events.push((
+                                    "final_warning_reverted".to_string(),
+                                    Some(format!(
+                                        "target process detected again ({})",
+                                        process_match_source_label(match_result.source)
+                                    )),
+                                ));
+                                notifications.push(PendingNotification {
+                                    title: "Auto Shutdown Scheduler".to_string(),
+                                    body: "감시 대상이 다시 실행되어 종료를 보류했습니다.".to_string(),
+                                });
+                            }
+                        }
+                        ProcessScanState::Invalid {
+                            schedule_id: scanned_id,
+                            status,
+                            reason,
+                        } if scanned_id == &schedule_id && status == &active.status => {
+                            if fail_safe_cancel_reason.is_none() {
+                                fail_safe_cancel_reason = Some(reason.clone());
+                            }
+                            reset_process_exit_on_selector_failure(active);
+                            changed = true;
+                            reverted = true;
+                        }
+                        ProcessScanState::Ready { .. } | ProcessScanState::Invalid { .. } => {
+                            reverted = true;
+                        }
+                        ProcessScanState::NotRequested => {}
+                    }
+                }
+                match &condition_state {
+                    ConditionScanState::Ready {
+                        schedule_id: scanned_id,
+                        status,
+                        observation,
+                    } if scanned_id == &schedule_id && status == &active.status => {
+                        let transition = apply_condition_observation(
+                            active,
+                            observation,
+                            now,
+                            guard_holds,
+                            &mut pending_events,
+                        );
+                        if matches!(
+                            transition,
+                            ConditionTransition::Reverted { .. }
+                                | ConditionTransition::Skipped { .. }
+                                | ConditionTransition::Failed { .. }
+                        ) {
+                            reverted = true;
+                        }
+                        condition_transition = Some(transition);
+                    }
+                    ConditionScanState::Ready { .. } => {
+                        reverted = true;
+                    }
+                    ConditionScanState::NotRequested => {}
+                }
+
+                if !reverted {
+                    if let Some(started_at_ms) = active.final_warning_started_at_ms {
+                        let elapsed = now - started_at_ms;
+                        if elapsed >= (active.final_warning_duration_sec as i64) * 1000 {
+                            should_execute = Some(schedule_id.clone());
+                        }
+                    } else {
+                        active.final_warning_started_at_ms = Some(now);
+                        changed = true;
+                    }
+                }
+            }
+            ScheduleStatus::ShuttingDown => {}
+        }
+
+        if final_warning_held || matches!(condition_transition, Some(ConditionTransition::Held)) {
+            if !active.battery_guard_holding {
+                active.battery_guard_holding = true;
+                changed = true;
+                pending_events.push((
+                    "final_warning_held".to_string(),
+                    Some("on AC power; final warning deferred until running on battery".to_string()),
+                ));
+            }
+        } else if active.battery_guard_holding && !guard_holds {
+            active.battery_guard_holding = false;
+            changed = true;
+        }
+
+        match condition_transition {
+            None | Some(ConditionTransition::Unchanged) | Some(ConditionTransition::Held) => {}
+            Some(ConditionTransition::DelayStarted { reason }) => {
+                changed = true;
+                let delay_sec = active.post_condition_delay_sec.unwrap_or_default();
+                let cause = reason.trim_end_matches("; entered shutdown waiting mode");
+                pending_events.push((
+                    "post_condition_delay_started".to_string(),
+                    Some(format!("{cause}; final warning in {delay_sec}s")),
+                ));
+                notifications.push(PendingNotification {
+                    title: "Auto Shutdown Scheduler".to_string(),
+                    body: post_condition_delay_notification_body(delay_sec),
+                });
+            }
+            Some(ConditionTransition::Deferred) => {
+                if !active.final_warning_deferred {
+                    active.final_warning_deferred = true;
+                    changed = true;
+                    pending_events.push((
+                        "final_warning_deferred".to_string(),
+                        Some("condition met before not-before time; final warning deferred".to_string()),
+                    ));
+                }
+            }
+            Some(ConditionTransition::WindowOpened | ConditionTransition::WindowReset) => {
+                changed = true;
+            }
+            Some(ConditionTransition::FinalWarning { reason }) => {
+                changed = true;
+                pending_events.push(("final_warning".to_string(), Some(reason)));
+                notifications.push(PendingNotification {
+                    title: "Auto Shutdown Scheduler".to_string(),
+                    body: condition_final_warning_notification_body(
+                        &active.mode,
+                        active.final_warning_duration_sec,
+                    ),
+                });
+            }
+            Some(ConditionTransition::Reverted { reason }) => {
+                changed = true;
+                pending_events.push(("final_warning_reverted".to_string(), Some(reason)));
+                notifications.push(PendingNotification {
+                    title: "Auto Shutdown Scheduler".to_string(),
+                    body: "감시 조건이 다시 충족되지 않아 종료를 보류했습니다.".to_string(),
+                });
+            }
+            Some(ConditionTransition::Skipped { reason }) => {
+                schedule_stop = Some(ScheduleStop {
+                    event_type: "cancelled",
+                    result: "ok",
+                    reason,
+                    notification: "종료 조건을 만족하지 않아 자동 종료를 건너뛰었습니다.".to_string(),
+                });
+            }
+            Some(ConditionTransition::Failed { reason }) => {
+                schedule_stop = Some(ScheduleStop {
+                    event_type: "failed",
+                    result: "error",
+                    reason,
+                    notification: "감시 상태를 확인할 수 없어 스케줄을 안전 중단했습니다.".to_string(),
+                });
+            }
+        }
+        if sync_shutdown_at_ms(active) {
+            changed = true;
+        }
+        // A deadline means "shut down by then", so it never hands over to the queue.
+        let finished =
+            was_armed && active.status == ScheduleStatus::FinalWarning && !deadline_forced;
+
+        if finished && !store.queue.is_empty() {
+            changed = true;
+        }
+        let promoted = record_tick_events(&mut store, &schedule_id, finished, pending_events);
+        if let Some((next_id, summary)) = promoted {
+            notifications.truncate(notifications_before);
+            let body = match start_launch_if_requested(&state, &mut store) {
+                Ok(()) => format!("Next queued schedule started: {summary}"),
+                Err(error) => {
+                    let reason = launch_failed_reason(&error);
+                    push_event(&mut store, Some(next_id), "failed", "error", Some(reason));
+                    store.active = None;
+                    "대기열의 다음 스케줄을 시작하지 못했습니다.".to_string()
+                }
+            };
+            notifications.push(PendingNotification {
+                title: "Auto Shutdown Scheduler".to_string(),
+                body,
+            });
+        }
+
+        if let Some(reason) = fail_safe_cancel_reason {
+            should_execute = None;
+            apply_process_exit_fail_safe_cancel(&mut store, &schedule_id, reason);
+            notifications.push(PendingNotification {
+                title: "Auto Shutdown Scheduler".to_string(),
+                body: "프로세스 종료 감시 설정이 손상되어 스케줄을 안전 중단했습니다. 감시 대상을 다시 선택해 주세요."
+                    .to_string(),
+            });
+            changed = true;
+        }
+
+        if let Some(stop) = schedule_stop {
+            should_execute = None;
+            push_event(
+                &mut store,
+                Some(schedule_id.clone()),
+                stop.event_type,
+                stop.result,
+                Some(stop.reason),
+            );
+            if store
+                .active
+                .as_ref()
+                .map(|active| active.id.as_str())
+                == Some(schedule_id.as_str())
+            {
+                store.active = None;
+            }
+            notifications.push(PendingNotification {
+                title: "Auto Shutdown Scheduler".to_string(),
+                body: stop.notification,
+            });
+            changed = true;
+        }
+
+        if changed {
+            let _ = state.persist_locked(&store);
+        }
+    }
+
+    for notification in notifications {
+        send_desktop_notification(app, &notification.title, &notification.body);
+    }
+
+    if let Some(schedule_id) = should_execute {
+        execute_active_shutdown(app, schedule_id);
+    }
+}
+
+fn start_scheduler_loop(app: AppHandle) {
+    scheduler::start_scheduler_loop(app, tick_scheduler);
+}
+
+fn preview_schedule_request(
+    store: &SchedulerStore,
+    request: ScheduleRequest,
+) -> Result<SchedulePreview, String> {
+    if let Some(existing) = store.active.as_ref() {
+        if is_shutdown_execution_started(existing) {
+            return Err("shutdown has already started; cannot replace active schedule".to_string());
+        }
+    }
+
+    let mut preview_store = store.clone();
+    let has_post_delay = request.post_condition_delay_sec.is_some();
+    let active = build_active_schedule(&mut preview_store, request)?;
+    let now = active.armed_at_ms;
+    let mut warnings = Vec::new();
+    if let Some(existing) = store.active.as_ref() {
+        warnings.push(format!("arming replaces the active schedule: {}", existing.summary));
+    }
+    if store.settings.simulate_only {
+        warnings.push("simulate-only is on; the shutdown will only be simulated".to_string());
+    }
+
+    let timed = !is_condition_mode(&active.mode);
+    let mut pre_alerts = Vec::new();
+    if let (true, Some(trigger_at_ms)) = (timed, active.trigger_at_ms) {
+        let lead_sec = ((trigger_at_ms - now).max(0) / 1000) as u64;
+        for &threshold_sec in &active.pre_alerts {
+            if threshold_sec >= lead_sec {
+                warnings.push(format!(
+                    "pre-alert {threshold_sec}s is not shorter than the {lead_sec}s until the trigger and fires immediately"
+                ));
+            }
+            pre_alerts.push(PreAlertPreview {
+                threshold_sec,
+                fires_at_ms: (trigger_at_ms - (threshold_sec as i64) * 1000).max(now),
+            });
+        }
+    } else if !active.pre_alerts.is_empty() && !has_post_delay {
+        warnings.push(
+            "pre-alerts only fire before a timer; this schedule shuts down once its condition holds"
+                .to_string(),
+        );
+    }
+
+    let shutdown_at_ms = compute_shutdown_at_ms(&active);
+    let dst_resolution = match active.mode {
+        ScheduleMode::SpecificTime => active.trigger_at_ms.and_then(local_dst_resolution),
+        _ => None,
+    };
+    if dst_resolution == Some(DstResolution::EarliestOfRepeated) {
+        warnings.push(format!(
+            "{} occurs twice that day because clocks fall back; the earlier one is used",
+            active.target_local_time.as_deref().unwrap_or_default()
+        ));
+    }
+    let offset_now = local_offset_minutes_at(now);
+    let offset_at_shutdown = shutdown_at_ms.and_then(local_offset_minutes_at);
+    if let (Some(before), Some(after)) = (offset_now, offset_at_shutdown) {
+        if before != after {
+            warnings.push(format!(
+                "the local UTC offset changes from {} to {} before shutdown",
+                format_utc_offset(before),
+                format_utc_offset(after)
+            ));
+        }
+    }
+
+    Ok(SchedulePreview {
+        summary: active.summary.clone(),
+        computed_at_ms: now,
+        trigger_at_ms: active.trigger_at_ms.filter(|_| timed),
+        pre_alerts,
+        final_warning_starts_at_ms: shutdown_at_ms
+            .map(|shutdown| shutdown - (active.final_warning_duration_sec as i64) * 1000),
+        final_warning_duration_sec: active.final_warning_duration_sec,
+        shutdown_at_ms,
+        not_before_ms: active.not_before_ms,
+        deadline_ms: active.deadline_ms,
+        target_tz_offset_minutes: active.target_tz_offset_minutes,
+        dst_resolution,
+        warnings,
+        mode: active.mode,
+    })
+}
+
+fn upsert_active_schedule(
+    store: &mut SchedulerStore,
+    request: ScheduleRequest,
+) -> Result<String, String> {
+    if let Some(existing) = store.active.as_ref() {
+        if is_shutdown_execution_started(existing) {
+            return Err("shutdown has already started; cannot replace active schedule".to_string());
+        }
+    }
+
+    let mut preview_store = store.clone();
+    let next_active = build_active_schedule(&mut preview_store, request.clone())?;
+    let schedule_id = next_active.id.clone();
+    let summary = next_active.summary.clone();
+
+    if let Some(existing) = store.active.take() {
+        push_event(
+            store,
+            Some(existing.id),
+            "cancelled",
+            "ok",
+            Some("cancelled(reason=replace)".to_string()),
+        );
+        clear_schedule_queue(store, "the schedule they waited for was replaced");
+    } else {
+        clear_schedule_queue(store, "the schedule they waited for has already ended");
+    }
+
+    store.id_seq = preview_store.id_seq;
+    store.active = Some(next_active);
+    store.cancelled = None;
+    store.last_schedule_request = Some(request);
+    push_event(
+        store,
+        Some(schedule_id),
+        "armed",
+        "ok",
+        Some(summary.clone()),
+    );
+
+    Ok(summary)
+}
+
+fn enqueue_schedule_request(
+    store: &mut SchedulerStore,
+    request: ScheduleRequest,
+    now: i64,
+) -> Result<String, String> {
+    let Some(behind_schedule_id) = store.active.as_ref().map(|active| active.id.clone()) else {
+        return Err("no active schedule to queue behind; arm this schedule directly".to_string());
+    };
+    if store.queue.len() >= SCHEDULE_QUEUE_LIMIT {
+        return Err(format!("the queue already holds {SCHEDULE_QUEUE_LIMIT} schedules"));
+    }
+
+    let mut preview_store = store.clone();
+    let summary = build_active_schedule(&mut preview_store, request.clone())?.summary;
+    store.id_seq += 1;
+    let id = format!("queued-{now}-{}", store.id_seq);
+    store.queue.push(QueuedSchedule {
+        id: id.clone(),
+        summary: summary.clone(),
+        queued_at_ms: now,
+        behind_schedule_id: Some(behind_schedule_id),
+        request,
+    });
+    let position = store.queue.len();
+    push_event(
+        store,
+        Some(id.clone()),
+        "queued",
+        "ok",
+        Some(format!("#{position}: {summary}")),
+    );
+    Ok(id)
+}
+
+fn reorder_schedule_queue_entries(
+    store: &mut SchedulerStore,
+    ids: &[String],
+) -> Result<(), String> {
+    let complete = ids.len() == store.queue.len()
+        && store.queue.iter().all(|queued| ids.contains(&queued.id));
+    if !complete {
+        return Err("reorder must list every queued schedule exactly once".to_string());
+    }
+    store
+        .queue
+        .sort_by_key(|queued| ids.iter().position(|id| id == &queued.id));
+    Ok(())
+}
+
+fn remove_queued_schedule_entry(store: &mut SchedulerStore, id: &str) -> Result<(), String> {
+    let index = store
+        .queue
+        .iter()
+        .position(|queued| queued.id == id)
+        .ok_or(format!("no queued schedule {id}"))?;
+    let removed = store.queue.remove(index);
+    push_event(
+        store,
+        Some(removed.id),
+        "queue_removed",
+        "ok",
+        Some(removed.summary),
+    );
+    Ok(())
+}
+
+fn record_tick_events(
+    store: &mut SchedulerStore,
+    schedule_id: &str,
+    finished: bool,
+    mut pending_events: Vec<(String, Option<String>)>,
+) -> Option<(String, String)> {
+    let promoted = if finished && !store.queue.is_empty() {
+        take_next_queued(store, schedule_id)
+    } else {
+        None
+    };
+    if promoted.is_some() {
+        pending_events.retain(|(event_type, _)| event_type != "final_warning");
+        pending_events.push((
+            "completed".to_string(),
+            Some("finished; starting the next queued schedule".to_string()),
+        ));
+    }
+
+    for (event_type, reason) in pending_events {
+        push_event(store, Some(schedule_id.to_string()), &event_type, "ok", reason);
+    }
+
+    let (next, request) = promoted?;
+    let next_id = next.id.clone();
+    let summary = next.summary.clone();
+    store.active = Some(next);
+    store.last_schedule_request = Some(request);
+    push_event(
+        store,
+        Some(next_id.clone()),
+        "armed",
+        "ok",
+        Some(format!("{summary} (from queue)")),
+    );
+    Some((next_id, summary))
+}
+
+fn clear_schedule_queue(store: &mut SchedulerStore, reason: &str) -> bool {
+    if store.queue.is_empty() {
+        return false;
+    }
+    let dropped = std::mem::take(&mut store.queue);
+    push_event(
+        store,
+        None,
+        "queue_cleared",
+        "ok",
+        Some(format!("{} queued schedule(s) dropped: {reason}", dropped.len())),
+    );
+    true
+}
+
+fn take_next_queued(
+    store: &mut SchedulerStore,
+    finished_id: &str,
+) -> Option<(ActiveSchedule, ScheduleRequest)> {
+    while !store.queue.is_empty() {
+        let queued = store.queue.remove(0);
+        if queued.behind_schedule_id.as_deref() != Some(finished_id) {
+            push_event(
+                store,
+                Some(queued.id),
+                "queue_skipped",
+                "error",
+                Some(format!("{}: queued behind another schedule", queued.summary)),
+            );
+            continue;
+        }
+        match build_active_schedule(store, queued.request.clone()) {
+            Ok(next) => {
+                for waiting in store.queue.iter_mut() {
+                    if waiting.behind_schedule_id.as_deref() == Some(finished_id) {
+                        waiting.behind_schedule_id = Some(next.id.clone());
+                    }
+                }
+                return Some((next, queued.request));
+            }
+            Err(error) => push_event(
+                store,
+                Some(queued.id),
+                "queue_skipped",
+                "error",
+                Some(format!("{}: {error}", queued.summary)),
+            ),
+        }
+    }
+    None
+}
+
+fn save_schedule_preset(
+    store: &mut SchedulerStore,
+    id: Option<&str>,
+    name: &str,
+    request: ScheduleRequest,
+    now: i64,
+) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("preset name is required".to_string());
+    }
+    if name.chars().count() > PRESET_NAME_MAX_CHARS {
+        return Err(format!("preset name must be at most {PRESET_NAME_MAX_CHARS} characters"));
+    }
+    let duplicate = store
+        .presets
+        .iter()
+        .any(|preset| Some(preset.id.as_str()) != id && preset.name.eq_ignore_ascii_case(name));
+    if duplicate {
+        return Err(format!("a preset named \"{name}\" already exists"));
+    }
+    let mut preview_store = store.clone();
+    let summary = build_active_schedule(&mut preview_store, request.clone())?.summary;
+
+    let (preset_id, event_type) = match id {
+        Some(id) => {
+            let preset = store
+                .presets
+                .iter_mut()
+                .find(|preset| preset.id == id)
+                .ok_or(format!("no preset {id}"))?;
+            preset.name = name.to_string();
+            preset.request = request;
+            preset.updated_at_ms = now;
+            (preset.id.clone(), "preset_updated")
+        }
+        None => {
+            if store.presets.len() >= PRESET_LIMIT {
+                return Err(format!("at most {PRESET_LIMIT} presets can be saved"));
+            }
+            store.id_seq += 1;
+            let preset_id = format!("preset-{now}-{}", store.id_seq);
+            store.presets.push(SchedulePreset {
+                id: preset_id.clone(),
+                name: name.to_string(),
+                request,
+                created_at_ms: now,
+                updated_at_ms: now,
+            });
+            (preset_id, "preset_saved")
+        }
+    };
+    push_event(
+        store,
+        None,
+        event_type,
+        "ok",
+        Some(format!("{name}: {summary}")),
+    );
+    Ok(preset_id)
+}
+
+fn delete_schedule_preset(store: &mut SchedulerStore, id: &str) -> Result<(), String> {
+    let index = store
+        .presets
+        .iter()
+        .position(|preset| preset.id == id)
+        .ok_or(format!("no preset {id}"))?;
+    let removed = store.presets.remove(index);
+    push_event(store, None, "preset_deleted", "ok", Some(removed.name));
+    Ok(())
+}
+
+fn countdown_summary(duration_sec: u64) -> String {
+    format!("Countdown {}m {}s", duration_sec / 60, duration_sec % 60)
+}
+
+fn apply_schedule_update(
+    active: &mut ActiveSchedule,
+    update: &ScheduleUpdate,
+    now: i64,
+) -> Result<Vec<String>, String> {
+    if is_shutdown_execution_started(active) {
+        return Err("shutdown has already started; cannot modify active schedule".to_string());
+    }
+    let reshapes_schedule = update.duration_sec.is_some()
+        || update.target_local_time.is_some()
+        || update.process_selector.is_some();
+    if reshapes_schedule && active.status != ScheduleStatus::Armed {
+        return Err("final warning has started; postpone or cancel instead".to_string());
+    }
+
+    let mut next = active.clone();
+    let mut changes = Vec::new();
+    let mut trigger_moved = false;
+
+    if let Some(duration) = update.duration_sec {
+        if !matches!(next.mode, ScheduleMode::Countdown) {
+            return Err("durationSec can only be changed on a countdown".to_string());
+        }
+        if duration == 0 {
+            return Err("durationSec must be greater than zero".to_string());
+        }
+        let trigger_at_ms = next.trigger_at_ms.unwrap_or(next.armed_at_ms);
+        // Schedules persisted before the duration was stored fall back to the trigger offset.
+        let previous = next
+            .duration_sec
+            .unwrap_or_else(|| ((trigger_at_ms - next.armed_at_ms) / 1000).max(0) as u64);
+        // Measured from the original arm time, keeping pauses and postpones.
+        let shift_ms = trigger_at_ms - next.armed_at_ms - (previous as i64) * 1000;
+        let trigger = next
+            .armed_at_ms
+            .checked_add((duration as i64) * 1000)
+            .and_then(|trigger| trigger.checked_add(shift_ms))
+            .ok_or("duration is too large".to_string())?;
+        if trigger <= next.paused_at_ms.unwrap_or(now) {
+            return Err("durationSec has already elapsed since the schedule was armed".to_string());
+        }
+        if duration != previous {
+            changes.push(format!("durationSec: {previous} -> {duration}"));
+            let suffix = next
+                .summary
+                .strip_prefix(countdown_summary(previous).as_str())
+                .unwrap_or_default()
+                .to_string();
+            next.summary = format!("{}{suffix}", countdown_summary(duration));
+            next.duration_sec = Some(duration);
+            next.trigger_at_ms = Some(trigger);
+            trigger_moved = true;
+        }
+    }
+
+    if let Some(target) = update.target_local_time.as_ref() {
+        if !matches!(next.mode, ScheduleMode::SpecificTime) {
+            return Err(
+                "targetLocalTime can only be changed on a specificTime schedule".to_string(),
+            );
+        }
+        let target = target.trim().to_string();
+        let trigger = compute_next_local_target_ms(&target)?;
+        let previous = next.target_local_time.clone().unwrap_or_default();
+        if previous != target || next.trigger_at_ms != Some(trigger) {
+            changes.push(format!("targetLocalTime: {previous} -> {target}"));
+            next.summary = next.summary.replacen(
+                &format!("Shutdown at local time {previous}"),
+                &format!("Shutdown at local time {target}"),
+                1,
+            );
+            next.trigger_at_ms = Some(trigger);
+            next.target_local_time = Some(target);
+            next.target_tz_offset_minutes = Some(Local::now().offset().local_minus_utc() / 60);
+            trigger_moved = true;
+        }
+    }
+
+    if let Some(stable_sec) = update.process_stable_sec {
+        if !is_condition_mode(&next.mode) {
+            return Err("processStableSec only applies to condition-based schedules".to_string());
+        }
+        let stable_sec = stable_sec.clamp(5, 600);
+        let previous = next.process_stable_sec;
+        if previous != stable_sec {
+            changes.push(format!("processStableSec: {previous} -> {stable_sec}"));
+            next.summary = next.summary.replacen(
+                &format!("stable {previous}s"),
+                &format!("stable {stable_sec}s"),
+                1,
+            );
+            next.process_stable_sec = stable_sec;
+        }
+    }
+
+    if let Some(selector) = update.process_selector.as_ref() {
+        if !matches!(next.mode, ScheduleMode::ProcessExit) {
+            return Err("processSelector can only be changed on a processExit schedule".to_string());
+        }
+        let selector = normalize_and_validate_process_selector(Some(selector))?;
+        let previous = next
+            .process_selector
+            .as_ref()
+            .map(describe_process_selector)
+            .unwrap_or_else(|| "(none)".to_string());
+        let described = describe_process_selector(&selector);
+        if previous != described {
+            changes.push(format!("processSelector: {previous} -> {described}"));
+            let threshold = next.process_instance_threshold;
+            let stable_sec = next.process_stable_sec;
+            let previous_summary = next
+                .process_selector
+                .as_ref()
+                .and_then(|previous| process_exit_summary(previous, threshold, stable_sec).ok())
+                .unwrap_or_default();
+            let summary = process_exit_summary(&selector, threshold, stable_sec)?;
+            // Keep any delay/window suffix that follows the mode summary.
+            let suffix = next
+                .summary
+                .strip_prefix(previous_summary.as_str())
+                .unwrap_or_default()
+                .to_string();
+            next.summary = format!("{summary}{suffix}");
+            next.process_selector = Some(selector);
+            next.process_tree_pids.clear();
+            next.process_missing_since_ms = None;
+            next.process_instance_count = None;
+            next.process_match_degraded_logged = false;
+            next.condition_met_since_ms = None;
+            next.condition_detail = None;
+        }
+    }
+
+    if let Some(alerts) = update.pre_alerts.as_ref() {
+        let alerts = normalize_alerts(alerts);
+        if alerts != next.pre_alerts {
+            changes.push(format!("preAlerts: {:?} -> {alerts:?}", next.pre_alerts));
+            next.pre_alerts = alerts;
+        }
+    }
+
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+
+    let keep_fired = if trigger_moved {
+        Vec::new()
+    } else {
+        active.fired_alerts.clone()
+    };
+    settle_fired_alerts(&mut next, &keep_fired, now);
+    let _ = sync_shutdown_at_ms(&mut next);
+    *active = next;
+    Ok(changes)
+}
+
+fn describe_process_selector(selector: &ProcessSelector) -> String {
+    let mut parts = Vec::new();
+    if let Some(pid) = selector.pid {
+        parts.push(format!("pid={pid}"));
+    }
+    if let Some(name) = selector.name.as_ref() {
+        parts.push(format!("name={name}"));
+    }
+    if let Some(executable) = selector.executable.as_ref() {
+        parts.push(format!("executable={executable}"));
+    }
+    if let Some(cmdline) = selector.cmdline_contains.as_ref() {
+        parts.push(format!("cmdline~{cmdline}"));
+    }
+    if parts.is_empty() {
+        "(none)".to_string()
+    } else {
+        parts.join(",")
+    }
+}
+
+fn start_launch_if_requested(state: &AppState, store: &mut SchedulerStore) -> Result<(), String> {
+    let Some(active) = store.active.as_mut() else {
+        return Ok(());
+    };
+    if !matches!(active.mode, ScheduleMode::LaunchAndWatch) || active.launch_pid.is_some() {
+        return Ok(());
+    }
+    let Some(command) = active.launch_command.clone() else {
+        return Err("launchCommand is required for launchAndWatch mode".to_string());
+    };
+
+    let pid = lock_launcher(&state.launcher).spawn(&active.id, &command)?;
+    active.launch_pid = Some(pid);
+    let schedule_id = active.id.clone();
+    push_event(
+        store,
+        Some(schedule_id),
+        "launch_started",
+        "ok",
+        Some(format!("pid {pid}: {}", describe_command(&command))),
+    );
+    Ok(())
+}
+
+fn default_quick_start_request(settings: &AppSettings) -> ScheduleRequest {
+    ScheduleRequest {
+        mode: ScheduleMode::Countdown,
+        duration_sec: Some(60 * 60),
+        target_local_time: None,
+        process_selector: None,
+        pre_alerts: Some(settings.default_pre_alerts.clone()),
+        process_stable_sec: None,
+        process_instance_threshold: None,
+        launch_command: None,
+        launch_exit_policy: None,
+        systemd_unit: None,
+        port_watch: None,
+        log_watch: None,
+        marker_watch: None,
+        http_watch: None,
+        media_watch: None,
+        battery_threshold_percent: None,
+        require_battery_power: false,
+        thermal_watch: None,
+        uptime_limit_sec: None,
+        not_before_local_time: None,
+        deadline_local_time: None,
+        post_condition_delay_sec: None,
+        action: None,
+    }
+}
+
+fn emit_tray_quick_start_request(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let request = {
+        let store = lock_store(&state.store);
+        store
+            .last_schedule_request
+            .clone()
+            .unwrap_or_else(|| default_quick_start_request(&store.settings))
+    };
+    emit_quick_start_confirmation(app, request)
+}
+
+fn emit_tray_preset_request(app: &AppHandle, preset_id: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let request = lock_store(&state.store)
+        .presets
+        .iter()
+        .find(|preset| preset.id == preset_id)
+        .map(|preset| preset.request.clone())
+        .ok_or(format!("preset {preset_id} no longer exists"))?;
+    emit_quick_start_confirmation(app, request)
+}
+
+fn emit_quick_start_confirmation(app: &AppHandle, request: ScheduleRequest) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    app.emit("tray_quick_start_requested", request)
+        .map_err(|error| format!("failed to emit tray quick start request: {error}"))?;
+
+    Ok(())
+}
+
+fn is_quit_guard_status(status: &ScheduleStatus) -> bool {
+    matches!(status, ScheduleStatus::Armed | ScheduleStatus::FinalWarning)
+}
+
+fn active_status_requiring_quit_guard(app: &AppHandle) -> Option<ScheduleStatus> {
+    let state = app.state::<AppState>();
+    let store = lock_store(&state.store);
+    store
+        .active
+        .as_ref()
+        .map(|active| active.status.clone())
+        .filter(is_quit_guard_status)
+}
+
+fn open_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn set_allow_exit_once(state: &tauri::State<AppState>, allow: bool) {
+    let mut runtime = lock_runtime(&state.runtime);
+    runtime.allow_exit_once = allow;
+}
+
+fn consume_allow_exit_once(app: &AppHandle) -> bool {
+    let state = app.state::<AppState>();
+    let mut runtime = lock_runtime(&state.runtime);
+    if runtime.allow_exit_once {
+        runtime.allow_exit_once = false;
+        true
+    } else {
+        false
+    }
+}
+
+fn emit_quit_guard_requested(
+    app: &AppHandle,
+    source: &str,
+    status: ScheduleStatus,
+) -> Result<(), String> {
+    open_main_window(app);
+    app.emit(
+        "quit_guard_requested",
+        QuitGuardPayload {
+            source: source.to_string(),
+            status,
+        },
+    )
+    .map_err(|error| format!("failed to emit quit guard request: {error}"))
+}
+
+fn request_quit_with_guard(app: &AppHandle, source: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if let Some(status) = active_status_requiring_quit_guard(app) {
+        set_allow_exit_once(&state, false);
+        emit_quit_guard_requested(app, source, status)?;
+        return Ok(());
+    }
+
+    set_allow_exit_once(&state, true);
+    app.exit(0);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct QuitResolutionOutcome {
+    should_exit: bool,
+    should_hide_window: bool,
+    store_changed: bool,
+}
+
+fn apply_quit_guard_action(
+    store: &mut SchedulerStore,
+    action: QuitGuardAction,
+) -> Result<QuitResolutionOutcome, String> {
+    match action {
+        QuitGuardAction::CancelAndQuit => {
+            let mut changed = false;
+            if let Some(active) = store.active.as_ref() {
+                if is_shutdown_execution_started(active) {
+                    return Err("shutdown has already started; cannot cancel".to_string());
+                }
+
+                let schedule_id = active.id.clone();
+                store.active = None;
+                push_event(
+                    store,
+                    Some(schedule_id),
+                    "cancelled",
+                    "ok",
+                    Some("사용자가 '스케줄 취소 후 종료'를 선택했습니다.".to_string()),
+                );
+                clear_schedule_queue(store, "the schedule they waited for was cancelled");
+                changed = true;
+            }
+            Ok(QuitResolutionOutcome {
+                should_exit: true,
+                should_hide_window: false,
+                store_changed: changed,
+            })
+        }
+        QuitGuardAction::KeepBackground => Ok(QuitResolutionOutcome {
+            should_exit: false,
+            should_hide_window: true,
+            store_changed: false,
+        }),
+        QuitGuardAction::Return => Ok(QuitResolutionOutcome {
+            should_exit: false,
+            should_hide_window: false,
+            store_changed: false,
+        }),
+    }
+}
+
+fn show_countdown_from_tray(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let store = lock_store(&state.store);
+    let now = now_ms();
+
+    let message = if let Some(active) = store.active.as_ref() {
+        if active.status == ScheduleStatus::ShuttingDown {
+            "종료 명령 실행 중".to_string()
+        } else if active.paused_at_ms.is_some() {
+            let remaining = remaining_trigger_ms(active, now) / 1000;
+            format!("일시정지됨 · {remaining}초 남음")
+        } else if let Some(shutdown_at_ms) = active.shutdown_at_ms {
+            let remaining = ((shutdown_at_ms - now).max(0) / 1000) as u64;
+            format!(
+                "종료 예정 {} · {}초 남음",
+                format_local_timestamp_ms(shutdown_at_ms),
+                remaining
+            )
+        } else if let Some(trigger_at_ms) = active.trigger_at_ms {
+            let remaining = ((trigger_at_ms - now).max(0) / 1000) as u64;
+            format!("자동 종료 대기 중 · {remaining}초 남음")
+        } else {
+            format!("자동 종료 대기 중 · {}", condition_watch_label(&active.mode))
+        }
+    } else {
+        "활성 스케줄 없음".to_string()
+    };
+
+    send_desktop_notification(app, "Auto Shutdown Scheduler", &message);
+}
+
+fn build_tray_menu(
+    app: &AppHandle,
+    presets: &[SchedulePreset],
+) -> tauri::Result<(Menu<tauri::Wry>, TrayMenuItems)> {
+    let quick_start =
+        MenuItemBuilder::with_id("quick_start_last_mode", "Quick Start Last Mode").build(app)?;
+    let preset_items = presets
+        .iter()
+        .map(|preset| {
+            MenuItemBuilder::with_id(format!("{TRAY_PRESET_ID_PREFIX}{}", preset.id), &preset.name)
+                .build(app)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let mut presets_menu = SubmenuBuilder::new(app, "Presets");
+    if preset_items.is_empty() {
+        let placeholder = MenuItemBuilder::with_id("presets_empty", "No presets saved")
+            .enabled(false)
+            .build(app)?;
+        presets_menu = presets_menu.item(&placeholder);
+    }
+    for item in &preset_items {
+        presets_menu = presets_menu.item(item);
+    }
+    let presets_menu = presets_menu.build()?;
+    let show_countdown = MenuItemBuilder::with_id("show_countdown", "Show Countdown").build(app)?;
+    let show_window = MenuItemBuilder::with_id("show", "Open Window").build(app)?;
+    let cancel = MenuItemBuilder::with_id("cancel", "Cancel Schedule").build(app)?;
+    let undo_cancel = MenuItemBuilder::with_id("undo_cancel", "Undo Cancel")
+        .enabled(false)
+        .build(app)?;
+    let postpone = MenuItemBuilder::with_id("postpone_10", "Snooze 10m").build(app)?;
+    let pause_resume =
+        MenuItemBuilder::with_id("pause_resume", "Pause/Resume Countdown").build(app)?;
+    let quit = MenuItemBuilder::with_id("quit", "Quit App").build(app)?;
+
+    let menu = MenuBuilder::new(app)
+        .items(&[
+            &quick_start,
+            &presets_menu,
+            &show_countdown,
+            &show_window,
+            &cancel,
+            &undo_cancel,
+            &postpone,
+            &pause_resume,
+            &quit,
+        ])
+        .build()?;
+    Ok((
+        menu,
+        TrayMenuItems {
+            postpone,
+            undo_cancel,
+        },
+    ))
+}
+
+// Fresh entries start in their default state, so the remembered state is reset.
+fn rebuild_tray_menu(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let presets = lock_store(&state.store).presets.clone();
+    let (Some(tray), Some(tray_menu)) = (app.tray_by_id(TRAY_ID), app.try_state::<TrayMenu>())
+    else {
+        return;
+    };
+    let Ok((menu, items)) = build_tray_menu(app, &presets) else {
+        return;
+    };
+    if tray.set_menu(Some(menu)).is_err() {
+        return;
+    }
+    *tray_menu.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(items);
+    {
+        let mut runtime = lock_runtime(&state.runtime);
+        runtime.tray_snooze_blocked = false;
+        runtime.tray_undo_available = false;
+    }
+    refresh_tray_indicator(app);
+}
+
+fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
+    let presets = {
+        let state = app.state::<AppState>();
+        let store = lock_store(&state.store);
+        store.presets.clone()
+    };
+    let (menu, items) = build_tray_menu(app, &presets)?;
+    app.manage(TrayMenu(Mutex::new(Some(items))));
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip(TRAY_TOOLTIP)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "quick_start_last_mode" => {
+                if let Err(error) = emit_tray_quick_start_request(app) {
+                    send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
+                }
+            }
+            "show_countdown" => {
+                show_countdown_from_tray(app);
+            }
+            "show" => {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+            "cancel" => {
+                let _ = cancel_active_schedule_internal(app, "cancelled from tray menu", true);
+            }
+            "undo_cancel" => {
+                if let Err(error) = undo_cancel_internal(app) {
+                    send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
+                }
+            }
+            "postpone_10" => {
+                // Limit refusals already notify; other failures are reported here.
+                if let Err(error) = postpone_schedule_internal(
+                    app,
+                    PostponeTarget::Minutes(10),
+                    "snoozed 10m from tray menu",
+                ) {
+                    if !error.is_limit() {
+                        send_desktop_notification(app, "Auto Shutdown Scheduler", &error.message);
+                    }
+                }
+            }
+            "pause_resume" => {
+                let paused = {
+                    let state = app.state::<AppState>();
+                    let store = lock_store(&state.store);
+                    store
+                        .active
+                        .as_ref()
+                        .map(|active| active.paused_at_ms.is_some())
+                        .unwrap_or(false)
+                };
+                let (pause, reason) = if paused {
+                    (false, "resumed from tray menu")
+                } else {
+                    (true, "paused from tray menu")
+                };
+                if let Err(error) = set_schedule_paused_internal(app, pause, reason) {
+                    send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
+                }
+            }
+            "quit" => {
+                if let Err(error) = request_quit_with_guard(app, "trayMenu") {
+                    send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
+                }
+            }
+            id => {
+                if let Some(preset_id) = id.strip_prefix(TRAY_PRESET_ID_PREFIX) {
+                    if let Err(error) = emit_tray_preset_request(app, preset_id) {
+                        send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
+                    }
+                }
+            }
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                let app = tray.app_handle();
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+        })
+        .build(app)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_scheduler_snapshot(state: tauri::State<AppState>) -> SchedulerSnapshot {
+    state.snapshot()
+}
+
+#[tauri::command]
+async fn list_processes(state: tauri::State<'_, AppState>) -> Result<Vec<ProcessInfo>, String> {
+    let scanner = Arc::clone(&state.scanner);
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut scanner = lock_scanner(&scanner);
+        Ok(scanner.list_running_processes())
+    })
+    .await
+    .map_err(|error| format!("failed to join list_processes worker: {error}"))?
+}
+
+#[tauri::command]
+async fn list_thermal_sensors() -> Result<Vec<ThermalSensor>, String> {
+    tauri::async_runtime::spawn_blocking(|| ThermalReader::default().list())
+        .await
+        .map_err(|error| format!("failed to join list_thermal_sensors worker: {error}"))?
+}
+
+#[tauri::command]
+fn arm_schedule(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    request: ScheduleRequest,
+) -> Result<SchedulerSnapshot, String> {
+    arm_schedule_internal(&app, request)?;
+    Ok(state.snapshot())
+}
+
+fn arm_schedule_internal(app: &AppHandle, request: ScheduleRequest) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut store = lock_store(&state.store);
+    let previous_store = store.clone();
+    let had_active_before = previous_store.active.is_some();
+    let summary = upsert_active_schedule(&mut store, request)?;
+    if let Err(error) = start_launch_if_requested(&state, &mut store) {
+        let schedule_id = store.active.as_ref().map(|active| active.id.clone());
+        let id_seq = store.id_seq;
+        *store = previous_store;
+        // Keep the sequence so the failed id is never handed out again.
+        store.id_seq = id_seq;
+        push_event(
+            &mut store,
+            schedule_id,
+            "failed",
+            "error",
+            Some(launch_failed_reason(&error)),
+        );
+        let _ = state.persist_locked(&store);
+        return Err(error);
+    }
+    if let Err(error) = state.persist_locked(&store) {
+        if let Some(active) = store.active.as_ref() {
+            lock_launcher(&state.launcher).terminate(&active.id);
+        }
+        *store = previous_store;
+        if had_active_before {
+            let rollback_schedule_id = store.active.as_ref().map(|active| active.id.clone());
+            if let Some(schedule_id) = rollback_schedule_id {
+                push_event(
+                    &mut store,
+                    Some(schedule_id),
+                    "replace_rolled_back",
+                    "error",
+                    Some("새 스케줄 활성화에 실패해 기존 스케줄로 복원했습니다.".to_string()),
+                );
+                let _ = state.persist_locked(&store);
+            }
+        }
+        return Err(format!("상태를 저장하지 못했습니다. 다시 시도해 주세요. ({error})"));
+    }
+    drop(store);
+
+    send_desktop_notification(app, "Auto Shutdown Scheduler", &summary);
+    Ok(())
+}
+
+#[tauri::command]
+fn preview_schedule(
+    state: tauri::State<AppState>,
+    request: ScheduleRequest,
+) -> Result<SchedulePreview, String> {
+    let store = lock_store(&state.store);
+    preview_schedule_request(&store, request)
+}
+
+#[tauri::command]
+fn list_presets(state: tauri::State<AppState>) -> Vec<SchedulePreset> {
+    lock_store(&state.store).presets.clone()
+}
+
+#[tauri::command]
+fn save_preset(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    id: Option<String>,
+    name: String,
+    request: ScheduleRequest,
+) -> Result<SchedulerSnapshot, String> {
+    let mut store = lock_store(&state.store);
+    let previous_store = store.clone();
+    save_schedule_preset(&mut store, id.as_deref(), &name, request, now_ms())?;
+    if let Err(error) = state.persist_locked(&store) {
+        *store = previous_store;
+        return Err(format!("상태를 저장하지 못했습니다. 다시 시도해 주세요. ({error})"));
+    }
+    drop(store);
+    rebuild_tray_menu(&app);
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn delete_preset(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    id: String,
+) -> Result<SchedulerSnapshot, String> {
+    let mut store = lock_store(&state.store);
+    delete_schedule_preset(&mut store, &id)?;
+    state.persist_locked(&store)?;
+    drop(store);
+    rebuild_tray_menu(&app);
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn arm_preset(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    id: String,
+) -> Result<SchedulerSnapshot, String> {
+    let request = lock_store(&state.store)
+        .presets
+        .iter()
+        .find(|preset| preset.id == id)
+        .map(|preset| preset.request.clone())
+        .ok_or(format!("no preset {id}"))?;
+    arm_schedule_internal(&app, request)?;
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn enqueue_schedule(
+    state: tauri::State<AppState>,
+    request: ScheduleRequest,
+) -> Result<SchedulerSnapshot, String> {
+    let mut store = lock_store(&state.store);
+    let previous_store = store.clone();
+    enqueue_schedule_request(&mut store, request, now_ms())?;
+    if let Err(error) = state.persist_locked(&store) {
+        *store = previous_store;
+        return Err(format!("상태를 저장하지 못했습니다. 다시 시도해 주세요. ({error})"));
+    }
+    drop(store);
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn list_schedule_queue(state: tauri::State<AppState>) -> Vec<QueuedSchedule> {
+    lock_store(&state.store).queue.clone()
+}
+
+#[tauri::command]
+fn reorder_schedule_queue(
+    state: tauri::State<AppState>,
+    ids: Vec<String>,
+) -> Result<SchedulerSnapshot, String> {
+    let mut store = lock_store(&state.store);
+    reorder_schedule_queue_entries(&mut store, &ids)?;
+    state.persist_locked(&store)?;
+    drop(store);
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn remove_queued_schedule(
+    state: tauri::State<AppState>,
+    id: String,
+) -> Result<SchedulerSnapshot, String> {
+    let mut store = lock_store(&state.store);
+    remove_queued_schedule_entry(&mut store, &id)?;
+    state.persist_locked(&store)?;
+    drop(store);
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn cancel_schedule(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    reason: Option<String>,
+) -> Result<SchedulerSnapshot, String> {
+    cancel_active_schedule_internal(
+        &app,
+        reason
+            .as_deref()
+            .unwrap_or("cancelled by user from UI"),
+        true,
+    )?;
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn undo_cancel(app: AppHandle, state: tauri::State<AppState>) -> Result<SchedulerSnapshot, String> {
+    undo_cancel_internal(&app)?;
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn postpone_schedule(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    minutes: Option<u64>,
+    until_local_time: Option<String>,
+    reason: Option<String>,
+) -> Result<SchedulerSnapshot, PostponeError> {
+    let target = match (minutes, normalize_selector_text(until_local_time.as_ref())) {
+        (Some(minutes), None) => PostponeTarget::Minutes(minutes),
+        (None, Some(until)) => PostponeTarget::Until(
+            compute_next_local_target_ms(&until)
+                .map_err(|error| format!("untilLocalTime: {error}"))?,
+        ),
+        _ => return Err("provide either minutes or untilLocalTime".to_string().into()),
+    };
+    postpone_schedule_internal(
+        &app,
+        target,
+        reason.as_deref().unwrap_or("snoozed by user from UI"),
+    )?;
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn update_active_schedule(
+    state: tauri::State<AppState>,
+    update: ScheduleUpdate,
+) -> Result<SchedulerSnapshot, String> {
+    let mut store = lock_store(&state.store);
+    let previous_store = store.clone();
+    let Some(active) = store.active.as_mut() else {
+        return Err("no active schedule to modify".to_string());
+    };
+
+    let schedule_id = active.id.clone();
+    let changes = apply_schedule_update(active, &update, now_ms())?;
+    if changes.is_empty() {
+        drop(store);
+        return Ok(state.snapshot());
+    }
+
+    push_event(
+        &mut store,
+        Some(schedule_id),
+        "modified",
+        "ok",
+        Some(changes.join("; ")),
+    );
+    if let Err(error) = state.persist_locked(&store) {
+        *store = previous_store;
+        return Err(format!("상태를 저장하지 못했습니다. 다시 시도해 주세요. ({error})"));
+    }
+    drop(store);
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn pause_schedule(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    reason: Option<String>,
+) -> Result<SchedulerSnapshot, String> {
+    set_schedule_paused_internal(
+        &app,
+        true,
+        reason.as_deref().unwrap_or("paused by user from UI"),
+    )?;
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn resume_schedule(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    reason: Option<String>,
+) -> Result<SchedulerSnapshot, String> {
+    set_schedule_paused_internal(
+        &app,
+        false,
+        reason.as_deref().unwrap_or("resumed by user from UI"),
+    )?;
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn request_app_quit(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<SchedulerSnapshot, String> {
+    request_quit_with_guard(&app, "uiCommand")?;
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn resolve_quit_guard(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    action: QuitGuardAction,
+) -> Result<SchedulerSnapshot, String> {
+    let outcome = {
+        let mut store = lock_store(&state.store);
+        let outcome = apply_quit_guard_action(&mut store, action)?;
+        if outcome.store_changed {
+            state.persist_locked(&store)?;
+        }
+        outcome
+    };
+
+    if outcome.should_hide_window {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.hide();
+        }
+    }
+
+    if outcome.should_exit {
+        set_allow_exit_once(&state, true);
+        app.exit(0);
+    } else {
+        set_allow_exit_once(&state, false);
+    }
+
+    Ok(state.snapshot())
+}
+
+#[tauri::command]
+fn update_settings(
+    state: tauri::State<AppState>,
+    updates: SettingsUpdate,
+) -> Result<SchedulerSnapshot, String> {
+    let mut store = lock_store(&state.store);
+
+    if let Some(alerts) = updates.default_pre_alerts {
+        store.settings.default_pre_alerts = normalize_alerts(&alerts);
+    }
+
+    if let Some(final_warning_sec) = updates.final_warning_sec {
+        store.settings.final_warning_sec = validate_final_warning_sec(final_warning_sec)?;
+    }
+
+    if let Some(simulate_only) = updates.simulate_only {
+        store.settings.simulate_only = simulate_only;
+    }
+
+    if let Some(count) = updates.max_postpone_count {
+        store.settings.max_postpone_count = Some(count).filter(|count| *count > 0);
+    }
+
+    if let Some(total_sec) = updates.max_postponed_total_sec {
+        store.settings.max_postponed_total_sec = Some(total_sec).filter(|total| *total > 0);
+    }
+
+    if let Some(deadline) = updates.postpone_deadline_local_time {
+        let deadline = deadline.trim().to_string();
+        if !deadline.is_empty() && NaiveTime::parse_from_str(&deadline, "%H:%M").is_err() {
+            return Err("postponeDeadlineLocalTime must match HH:MM format".to_string());
+        }
+        store.settings.postpone_deadline_local_time =
+            Some(deadline).filter(|value| !value.is_empty());
+    }
+
+    if let Some(window_sec) = updates.undo_cancel_window_sec {
+        if window_sec > UNDO_CANCEL_WINDOW_MAX_SEC {
+            return Err(format!(
+                "undoCancelWindowSec must be within 0..={UNDO_CANCEL_WINDOW_MAX_SEC}"
+            ));
+        }
+        store.settings.undo_cancel_window_sec = window_sec;
+    }
+
+    if let Some(hooks) = updates.pre_shutdown_hooks {
+        store.settings.pre_shutdown_hooks = validate_shutdown_hooks(&hooks)?;
+    }
+
+    if let Some(close) = updates.graceful_close {
+        store.settings.graceful_close = validate_graceful_close(&close)?;
+    }
+
+    let final_warning_sec = store.settings.final_warning_sec;
+    if let Some(active) = store.active.as_mut() {
+        active.final_warning_duration_sec =
+            final_warning_sec_for_mode(&active.mode, final_warning_sec);
+        let _ = sync_shutdown_at_ms(active);
+    }
+
+    push_event(
+        &mut store,
+        None,
+        "settings_updated",
+        "ok",
+        Some("settings were updated".to_string()),
+    );
+    state.persist_locked(&store)?;
+    Ok(state.snapshot())
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(|app| {
+            let state_path = resolve_state_path(app.handle());
+            let mut load_outcome = load_store(&state_path)
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+            let mut store = load_outcome.store;
+            if enforce_no_resume_in_mvp(&mut store) {
+                load_outcome.needs_persist = true;
+            }
+
+            if load_outcome.needs_persist {
+                persist_store(&state_path, &store)
+                    .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+            }
+
+            let startup_notice = load_outcome.startup_notice.take();
+            app.manage(AppState {
+                state_path,
+                store: Mutex::new(store),
+                runtime: Mutex::new(RuntimeState::default()),
+                scanner: Arc::new(Mutex::new(ProcessScanner::new())),
+                launcher: Mutex::new(LaunchSupervisor::default()),
+                watchers: Mutex::new(ConditionWatchers::default()),
+            });
+
+            setup_tray(app.handle())?;
+            start_scheduler_loop(app.handle().clone());
+            if let Some(notice) = startup_notice {
+                send_desktop_notification(app.handle(), "Auto Shutdown Scheduler", &notice);
+            }
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            get_scheduler_snapshot,
+            list_processes,
+            list_thermal_sensors,
+            arm_schedule,
+            preview_schedule,
+            list_presets,
+            save_preset,
+            delete_preset,
+            arm_preset,
+            enqueue_schedule,
+            list_schedule_queue,
+            reorder_schedule_queue,
+            remove_queued_schedule,
+            cancel_schedule,
+            undo_cancel,
+            postpone_schedule,
+            update_active_schedule,
+            pause_schedule,
+            resume_schedule,
+            request_app_quit,
+            resolve_quit_guard,
+            update_settings
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::ExitRequested { api, .. } = event {
+                if consume_allow_exit_once(app) {
+                    return;
+                }
+
+                if let Some(status) = active_status_requiring_quit_guard(app) {
+                    api.prevent_exit();
+                    let _ = emit_quit_guard_requested(app, "appExit", status);
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_final_warning_schedule() -> ActiveSchedule {
+        ActiveSchedule {
+            id: "sch-test".to_string(),
+            mode: ScheduleMode::Countdown,
+            summary: "test".to_string(),
+            armed_at_ms: 0,
+            trigger_at_ms: Some(0),
+            target_local_time: None,
+            target_tz_offset_minutes: None,
+            pre_alerts: vec![600, 300, 60],
+            fired_alerts: vec![600, 300, 60],
+            process_selector: None,
+            process_tree_pids: Vec::new(),
+            process_stable_sec: 10,
+            process_missing_since_ms: None,
+            process_instance_threshold: None,
+            process_instance_count: None,
+            launch_command: None,
+            launch_exit_policy: None,
+            launch_pid: None,
+            launch_exit_code: None,
+            launch_exited_at_ms: None,
+            systemd_unit: None,
+            unit_seen_active: false,
+            network_connection_count: None,
+            port_watch: None,
+            port_open: None,
+            log_watch: None,
+            log_matched_line: None,
+            log_start: None,
+            marker_watch: None,
+            http_watch: None,
+            media_watch: None,
+            battery_threshold_percent: None,
+            require_battery_power: false,
+            battery_guard_holding: false,
+            thermal_watch: None,
+            thermal_peak_celsius: None,
+            uptime_limit_sec: None,
+            boot_time_ms: None,
+            duration_sec: None,
+            not_before_ms: None,
+            deadline_ms: None,
+            final_warning_deferred: false,
+            post_condition_delay_sec: None,
+            post_delay_started_at_ms: None,
+            paused_at_ms: None,
+            postpone_count: 0,
+            postponed_total_sec: 0,
+            action: ScheduleAction::Shutdown,
+            condition_met_since_ms: None,
+            condition_detail: None,
+            snooze_until_ms: None,
+            process_match_degraded_logged: false,
+            status: ScheduleStatus::FinalWarning,
+            final_warning_started_at_ms: Some(1_000),
+            final_warning_duration_sec: 60,
+            shutdown_at_ms: Some(61_000),
+            shutdown_initiated_at_ms: None,
+        }
+    }
+
+    #[test]
+    fn shutdown_initiation_requires_elapsed_final_warning_window() {
+        let mut schedule = sample_final_warning_schedule();
+
+        assert!(!try_mark_shutdown_initiated(&mut schedule, 60_999));
+        assert_eq!(schedule.status, ScheduleStatus::FinalWarning);
+        assert_eq!(schedule.shutdown_initiated_at_ms, None);
+
+        assert!(try_mark_shutdown_initiated(&mut schedule, 61_000));
+        assert_eq!(schedule.status, ScheduleStatus::ShuttingDown);
+        assert_eq!(schedule.shutdown_initiated_at_ms, Some(61_000));
+    }
+
+    #[test]
+    fn shutdown_initiation_is_idempotent_after_first_transition() {
+        let mut schedule = sample_final_warning_schedule();
+
+        assert!(try_mark_shutdown_initiated(&mut schedule, 61_000));
+        assert!(!try_mark_shutdown_initiated(&mut schedule, 62_000));
+        assert_eq!(schedule.status, ScheduleStatus::ShuttingDown);
+        assert_eq!(schedule.shutdown_initiated_at_ms, Some(61_000));
+    }
+
+    #[test]
+    fn dry_run_shutdown_logs_abortable_command_plan() {
+        let settings = AppSettings {
+            default_pre_alerts: vec![600, 300, 60],
+            final_warning_sec: 60,
+            simulate_only: true,
+            ..AppSettings::default()
+        };
 
-                if !reverted {
-                    if let Some(started_at_ms) = active.final_warning_started_at_ms {
-                        let elapsed = now - started_at_ms;
-                        if elapsed >= (active.final_warning_duration_sec as i64) * 1000 {
-                            should_execute = Some(schedule_id.clone());
-                        }
-                    } else {
-                        active.final_warning_started_at_ms = Some(now);
-                        changed = true;
-                    }
-                }
-            }
-            ScheduleStatus::ShuttingDown => {}
-        }
-        if sync_shutdown_at_ms(active) {
-            changed = true;
-        }
+        let dispatch = run_shutdown_command(&settings)
+            .expect("simulate-only dry run should always return dispatch details");
 
-        for (event_type, reason) in pending_events {
-            push_event(&mut store, Some(schedule_id.clone()), &event_type, "ok", reason);
+        assert!(dispatch.dry_run);
+        assert!(dispatch.log_line().contains("DRY_RUN_SHUTDOWN_COMMAND"));
+
+        #[cfg(target_os = "windows")]
+        {
+            assert!(dispatch.command_line.contains("shutdown /s /t"));
+            assert!(dispatch
+                .command_line
+                .contains(&WINDOWS_ABORTABLE_SHUTDOWN_SEC.to_string()));
+            assert_eq!(dispatch.abort_hint.as_deref(), Some("shutdown /a"));
         }
 
-        if let Some(reason) = fail_safe_cancel_reason {
-            should_execute = None;
-            apply_process_exit_fail_safe_cancel(&mut store, &schedule_id, reason);
-            notifications.push(PendingNotification {
-                title: "Auto Shutdown Scheduler".to_string(),
-                body: "프로세스 종료 감시 설정이 손상되어 스케줄을 안전 중단했습니다. 감시 대상을 다시 선택해 주세요."
-                    .to_string(),
-            });
-            changed = true;
+        #[cfg(target_os = "macos")]
+        {
+            assert!(dispatch.command_line.contains("osascript"));
+            assert_eq!(dispatch.abort_hint, None);
         }
 
-        if changed {
-            let _ = state.persist_locked(&store);
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        {
+            assert!(dispatch.command_line.contains("unsupported"));
+            assert_eq!(dispatch.abort_hint, None);
         }
     }
 
-    for notification in notifications {
-        send_desktop_notification(app, &notification.title, &notification.body);
+    #[test]
+    fn shutdown_at_ms_policy_for_armed_time_based_modes() {
+        let mut schedule = sample_final_warning_schedule();
+        schedule.status = ScheduleStatus::Armed;
+        schedule.mode = ScheduleMode::Countdown;
+        schedule.trigger_at_ms = Some(10_000);
+        schedule.final_warning_started_at_ms = None;
+        schedule.final_warning_duration_sec = 90;
+        schedule.shutdown_at_ms = None;
+
+        assert!(sync_shutdown_at_ms(&mut schedule));
+        assert_eq!(schedule.shutdown_at_ms, Some(100_000));
     }
 
-    if let Some(schedule_id) = should_execute {
-        execute_active_shutdown(app, schedule_id);
+    #[test]
+    fn shutdown_at_ms_policy_for_process_exit_armed_and_final_warning() {
+        let mut armed = sample_final_warning_schedule();
+        armed.mode = ScheduleMode::ProcessExit;
+        armed.status = ScheduleStatus::Armed;
+        armed.trigger_at_ms = None;
+        armed.final_warning_started_at_ms = None;
+        armed.shutdown_at_ms = Some(1);
+
+        assert!(sync_shutdown_at_ms(&mut armed));
+        assert_eq!(armed.shutdown_at_ms, None);
+
+        let mut warning = armed.clone();
+        warning.status = ScheduleStatus::FinalWarning;
+        warning.final_warning_started_at_ms = Some(50_000);
+        warning.final_warning_duration_sec = 60;
+
+        assert!(sync_shutdown_at_ms(&mut warning));
+        assert_eq!(warning.shutdown_at_ms, Some(110_000));
     }
-}
 
-fn start_scheduler_loop(app: AppHandle) {
-    scheduler::start_scheduler_loop(app, tick_scheduler);
-}
+    #[test]
+    fn no_resume_policy_clears_persisted_active_schedule() {
+        let mut store = SchedulerStore::default();
+        let active = sample_final_warning_schedule();
+        let schedule_id = active.id.clone();
+        store.active = Some(active);
 
-fn upsert_active_schedule(
-    store: &mut SchedulerStore,
-    request: ScheduleRequest,
-) -> Result<String, String> {
-    if let Some(existing) = store.active.as_ref() {
-        if is_shutdown_execution_started(existing) {
-            return Err("shutdown has already started; cannot replace active schedule".to_string());
-        }
+        assert!(enforce_no_resume_in_mvp(&mut store));
+        assert!(store.active.is_none());
+
+        let last_event = store.history.last().expect("startup event should be recorded");
+        assert_eq!(last_event.event_type, "resume_not_supported");
+        assert_eq!(last_event.result, "ok");
+        assert_eq!(last_event.schedule_id.as_deref(), Some(schedule_id.as_str()));
     }
 
-    let mut preview_store = store.clone();
-    let next_active = build_active_schedule(&mut preview_store, request.clone())?;
-    let schedule_id = next_active.id.clone();
-    let summary = next_active.summary.clone();
+    #[test]
+    fn no_resume_policy_is_noop_without_active_schedule() {
+        let mut store = SchedulerStore::default();
 
-    if let Some(existing) = store.active.take() {
-        push_event(
-            store,
-            Some(existing.id),
-            "cancelled",
-            "ok",
-            Some("cancelled(reason=replace)".to_string()),
-        );
+        assert!(!enforce_no_resume_in_mvp(&mut store));
+        assert!(store.history.is_empty());
+        assert!(store.active.is_none());
     }
 
-    store.id_seq = preview_store.id_seq;
-    store.active = Some(next_active);
-    store.last_schedule_request = Some(request);
-    push_event(
-        store,
-        Some(schedule_id),
-        "armed",
-        "ok",
-        Some(summary.clone()),
-    );
+    #[test]
+    fn process_selector_validation_rejects_missing_or_empty_selector() {
+        assert_eq!(
+            normalize_and_validate_process_selector(None).unwrap_err(),
+            "process selector is missing"
+        );
 
-    Ok(summary)
-}
+        let empty = ProcessSelector {
+            pid: None,
+            name: Some("   ".to_string()),
+            executable: None,
+            cmdline_contains: None,
+        };
+        assert_eq!(
+            normalize_and_validate_process_selector(Some(&empty)).unwrap_err(),
+            "process selector is empty"
+        );
+    }
 
-fn default_quick_start_request(settings: &AppSettings) -> ScheduleRequest {
-    ScheduleRequest {
-        mode: ScheduleMode::Countdown,
-        duration_sec: Some(60 * 60),
-        target_local_time: None,
-        process_selector: None,
-        pre_alerts: Some(settings.default_pre_alerts.clone()),
-        process_stable_sec: None,
+    #[test]
+    fn process_selector_validation_rejects_shell_name_without_advanced_match() {
+        let selector = ProcessSelector {
+            pid: None,
+            name: Some("powershell".to_string()),
+            executable: None,
+            cmdline_contains: None,
+        };
+
+        let error = normalize_and_validate_process_selector(Some(&selector)).unwrap_err();
+        assert!(error.contains("shell 계열 프로세스"));
     }
-}
 
-fn emit_tray_quick_start_request(app: &AppHandle) -> Result<(), String> {
-    let state = app.state::<AppState>();
-    let request = {
-        let store = lock_store(&state.store);
-        store
-            .last_schedule_request
-            .clone()
-            .unwrap_or_else(|| default_quick_start_request(&store.settings))
-    };
+    #[test]
+    fn process_selector_validation_accepts_and_normalizes_valid_selector() {
+        let selector = ProcessSelector {
+            pid: Some(1234),
+            name: Some("  pwsh ".to_string()),
+            executable: Some("  C:\\\\Windows\\\\System32\\\\pwsh.exe ".to_string()),
+            cmdline_contains: Some("  -File ".to_string()),
+        };
 
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
+        let normalized = normalize_and_validate_process_selector(Some(&selector))
+            .expect("selector should be valid");
+        assert_eq!(normalized.pid, Some(1234));
+        assert_eq!(normalized.name.as_deref(), Some("pwsh"));
+        assert_eq!(
+            normalized.executable.as_deref(),
+            Some("C:\\\\Windows\\\\System32\\\\pwsh.exe")
+        );
+        assert_eq!(normalized.cmdline_contains.as_deref(), Some("-File"));
     }
 
-    app.emit("tray_quick_start_requested", request)
-        .map_err(|error| format!("failed to emit tray quick start request: {error}"))?;
+    #[test]
+    fn process_instance_threshold_records_crossings_in_both_directions() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::ProcessExit;
+        active.process_instance_threshold = Some(3);
 
-    Ok(())
-}
+        assert_eq!(record_process_instance_count(&mut active, 5), None);
+        assert_eq!(active.process_instance_count, Some(5));
+        assert_eq!(record_process_instance_count(&mut active, 3), None);
 
-fn is_quit_guard_status(status: &ScheduleStatus) -> bool {
-    matches!(status, ScheduleStatus::Armed | ScheduleStatus::FinalWarning)
-}
+        let fell = record_process_instance_count(&mut active, 2)
+            .expect("crossing below should be reported");
+        assert!(fell.contains("fell to 2"));
+        assert_eq!(record_process_instance_count(&mut active, 1), None);
 
-fn active_status_requiring_quit_guard(app: &AppHandle) -> Option<ScheduleStatus> {
-    let state = app.state::<AppState>();
-    let store = lock_store(&state.store);
-    store
-        .active
-        .as_ref()
-        .map(|active| active.status.clone())
-        .filter(is_quit_guard_status)
-}
+        let rose = record_process_instance_count(&mut active, 4)
+            .expect("crossing above should be reported");
+        assert!(rose.contains("rose to 4"));
+        assert_eq!(active.process_instance_count, Some(4));
+    }
 
-fn open_main_window(app: &AppHandle) {
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
+    #[test]
+    fn process_instance_threshold_first_sample_below_is_not_a_crossing() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::ProcessExit;
+        active.process_instance_threshold = Some(3);
+
+        assert_eq!(record_process_instance_count(&mut active, 1), None);
+        assert_eq!(active.process_instance_count, Some(1));
+        let rose = record_process_instance_count(&mut active, 3)
+            .expect("crossing above should be reported");
+        assert!(rose.contains("rose to 3"));
     }
-}
 
-fn set_allow_exit_once(state: &tauri::State<AppState>, allow: bool) {
-    let mut runtime = lock_runtime(&state.runtime);
-    runtime.allow_exit_once = allow;
-}
+    #[test]
+    fn process_instance_count_without_threshold_never_reports_crossing() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::ProcessExit;
 
-fn consume_allow_exit_once(app: &AppHandle) -> bool {
-    let state = app.state::<AppState>();
-    let mut runtime = lock_runtime(&state.runtime);
-    if runtime.allow_exit_once {
-        runtime.allow_exit_once = false;
-        true
-    } else {
-        false
+        assert_eq!(record_process_instance_count(&mut active, 0), None);
+        assert_eq!(active.process_instance_count, Some(0));
     }
-}
 
-fn emit_quit_guard_requested(
-    app: &AppHandle,
-    source: &str,
-    status: ScheduleStatus,
-) -> Result<(), String> {
-    open_main_window(app);
-    app.emit(
-        "quit_guard_requested",
-        QuitGuardPayload {
-            source: source.to_string(),
-            status,
-        },
-    )
-    .map_err(|error| format!("failed to emit quit guard request: {error}"))
-}
+    #[test]
+    fn process_instance_threshold_is_validated_for_process_exit_only() {
+        let mut store = SchedulerStore::default();
+        let mut request = ScheduleRequest {
+            mode: ScheduleMode::ProcessExit,
+            duration_sec: None,
+            target_local_time: None,
+            process_selector: Some(ProcessSelector {
+                pid: None,
+                name: Some("cc1".to_string()),
+                executable: None,
+                cmdline_contains: None,
+            }),
+            pre_alerts: None,
+            process_stable_sec: None,
+            process_instance_threshold: Some(0),
+            launch_command: None,
+            launch_exit_policy: None,
+            systemd_unit: None,
+            port_watch: None,
+            log_watch: None,
+            marker_watch: None,
+            http_watch: None,
+            media_watch: None,
+            battery_threshold_percent: None,
+            require_battery_power: false,
+            thermal_watch: None,
+            uptime_limit_sec: None,
+            not_before_local_time: None,
+            deadline_local_time: None,
+            post_condition_delay_sec: None,
+            action: None,
+        };
+
+        let error = build_active_schedule(&mut store, request.clone()).unwrap_err();
+        assert!(error.contains("processInstanceThreshold"));
 
-fn request_quit_with_guard(app: &AppHandle, source: &str) -> Result<(), String> {
-    let state = app.state::<AppState>();
-    if let Some(status) = active_status_requiring_quit_guard(app) {
-        set_allow_exit_once(&state, false);
-        emit_quit_guard_requested(app, source, status)?;
-        return Ok(());
+        request.process_instance_threshold = Some(4);
+        let active = build_active_schedule(&mut store, request.clone())
+            .expect("threshold should be accepted");
+        assert_eq!(active.process_instance_threshold, Some(4));
+        assert!(active.summary.contains("fewer than 4"));
+
+        request.mode = ScheduleMode::Countdown;
+        request.duration_sec = Some(60);
+        let active = build_active_schedule(&mut store, request)
+            .expect("countdown should ignore threshold");
+        assert_eq!(active.process_instance_threshold, None);
     }
 
-    set_allow_exit_once(&state, true);
-    app.exit(0);
-    Ok(())
-}
+    #[test]
+    fn launch_exit_policy_matches_success_and_failure() {
+        assert!(launch_exit_policy_allows(LaunchExitPolicy::Always, true));
+        assert!(launch_exit_policy_allows(LaunchExitPolicy::Always, false));
+        assert!(launch_exit_policy_allows(LaunchExitPolicy::OnSuccess, true));
+        assert!(!launch_exit_policy_allows(LaunchExitPolicy::OnSuccess, false));
+        assert!(!launch_exit_policy_allows(LaunchExitPolicy::OnFailure, true));
+        assert!(launch_exit_policy_allows(LaunchExitPolicy::OnFailure, false));
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct QuitResolutionOutcome {
-    should_exit: bool,
-    should_hide_window: bool,
-    store_changed: bool,
-}
+    #[test]
+    fn launch_exit_event_reason_keeps_exit_code_and_stderr_tail() {
+        assert_eq!(launch_exit_event_reason(Some(0), ""), "exit code 0; stderr empty");
+        assert_eq!(
+            launch_exit_event_reason(None, "killed"),
+            "terminated without exit code; stderr tail: killed"
+        );
 
-fn apply_quit_guard_action(
-    store: &mut SchedulerStore,
-    action: QuitGuardAction,
-) -> Result<QuitResolutionOutcome, String> {
-    match action {
-        QuitGuardAction::CancelAndQuit => {
-            let mut changed = false;
-            if let Some(active) = store.active.as_ref() {
-                if is_shutdown_execution_started(active) {
-                    return Err("shutdown has already started; cannot cancel".to_string());
-                }
+        let long_tail = format!("{}END", "x".repeat(LAUNCH_STDERR_HISTORY_CHARS));
+        let reason = launch_exit_event_reason(Some(2), &long_tail);
+        assert!(reason.starts_with("exit code 2; stderr tail: ..."));
+        assert!(reason.ends_with("END"));
+    }
 
-                let schedule_id = active.id.clone();
-                store.active = None;
-                push_event(
-                    store,
-                    Some(schedule_id),
-                    "cancelled",
-                    "ok",
-                    Some("사용자가 '스케줄 취소 후 종료'를 선택했습니다.".to_string()),
-                );
-                changed = true;
-            }
-            Ok(QuitResolutionOutcome {
-                should_exit: true,
-                should_hide_window: false,
-                store_changed: changed,
-            })
-        }
-        QuitGuardAction::KeepBackground => Ok(QuitResolutionOutcome {
-            should_exit: false,
-            should_hide_window: true,
-            store_changed: false,
-        }),
-        QuitGuardAction::Return => Ok(QuitResolutionOutcome {
-            should_exit: false,
-            should_hide_window: false,
-            store_changed: false,
-        }),
+    #[test]
+    fn launch_failure_is_cancelled_with_its_own_code() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::LaunchAndWatch;
+        let poll = LaunchPoll::Failed("launched command is no longer tracked".to_string());
+        let probe = launch_condition_probe(&mut active, Some(&poll), 1_000, &mut Vec::new());
+        let ConditionProbe::Failed { reason } = probe else {
+            panic!("a lost launch must cancel the schedule");
+        };
+        assert!(reason.starts_with("LAUNCH_FAILED: launched command is no longer tracked"));
     }
-}
 
-fn show_countdown_from_tray(app: &AppHandle) {
-    let state = app.state::<AppState>();
-    let store = lock_store(&state.store);
-    let now = now_ms();
+    #[test]
+    fn launch_and_watch_requires_program_and_defaults_to_always_policy() {
+        let mut store = SchedulerStore::default();
+        let mut request = ScheduleRequest {
+            mode: ScheduleMode::LaunchAndWatch,
+            duration_sec: None,
+            target_local_time: None,
+            process_selector: None,
+            pre_alerts: None,
+            process_stable_sec: None,
+            process_instance_threshold: None,
+            launch_command: None,
+            launch_exit_policy: None,
+            systemd_unit: None,
+            port_watch: None,
+            log_watch: None,
+            marker_watch: None,
+            http_watch: None,
+            media_watch: None,
+            battery_threshold_percent: None,
+            require_battery_power: false,
+            thermal_watch: None,
+            uptime_limit_sec: None,
+            not_before_local_time: None,
+            deadline_local_time: None,
+            post_condition_delay_sec: None,
+            action: None,
+        };
 
-    let message = if let Some(active) = store.active.as_ref() {
-        if active.status == ScheduleStatus::ShuttingDown {
-            "종료 명령 실행 중".to_string()
-        } else if let Some(shutdown_at_ms) = active.shutdown_at_ms {
-            let remaining = ((shutdown_at_ms - now).max(0) / 1000) as u64;
-            format!(
-                "종료 예정 {} · {}초 남음",
-                format_local_timestamp_ms(shutdown_at_ms),
-                remaining
-            )
-        } else if let Some(trigger_at_ms) = active.trigger_at_ms {
-            let remaining = ((trigger_at_ms - now).max(0) / 1000) as u64;
-            format!("자동 종료 대기 중 · {remaining}초 남음")
-        } else {
-            "자동 종료 대기 중 · 프로세스 종료 감시".to_string()
-        }
-    } else {
-        "활성 스케줄 없음".to_string()
-    };
+        let error = build_active_schedule(&mut store, request.clone()).unwrap_err();
+        assert!(error.contains("launchCommand is required"));
 
-    send_desktop_notification(app, "Auto Shutdown Scheduler", &message);
-}
+        request.launch_command = Some(LaunchCommand {
+            program: "  ".to_string(),
+            args: Vec::new(),
+            working_dir: None,
+        });
+        let error = build_active_schedule(&mut store, request.clone()).unwrap_err();
+        assert!(error.contains("program must not be empty"));
 
-fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
-    let quick_start =
-        MenuItemBuilder::with_id("quick_start_last_mode", "Quick Start Last Mode").build(app)?;
-    let show_countdown = MenuItemBuilder::with_id("show_countdown", "Show Countdown").build(app)?;
-    let show_window = MenuItemBuilder::with_id("show", "Open Window").build(app)?;
-    let cancel = MenuItemBuilder::with_id("cancel", "Cancel Schedule").build(app)?;
-    let postpone = MenuItemBuilder::with_id("postpone_10", "Snooze 10m").build(app)?;
-    let quit = MenuItemBuilder::with_id("quit", "Quit App").build(app)?;
+        request.launch_command = Some(LaunchCommand {
+            program: " backup.sh ".to_string(),
+            args: vec!["--full".to_string()],
+            working_dir: Some(std::env::temp_dir().display().to_string()),
+        });
+        let active =
+            build_active_schedule(&mut store, request).expect("launch request should be valid");
+        assert_eq!(active.launch_exit_policy, Some(LaunchExitPolicy::Always));
+        assert_eq!(
+            active.launch_command.as_ref().map(|command| command.program.as_str()),
+            Some("backup.sh")
+        );
+        assert!(active.summary.contains("`backup.sh --full`"));
+        assert_eq!(active.shutdown_at_ms, None);
+    }
 
-    let menu = MenuBuilder::new(app)
-        .items(&[
-            &quick_start,
-            &show_countdown,
-            &show_window,
-            &cancel,
-            &postpone,
-            &quit,
-        ])
-        .build()?;
+    #[test]
+    fn network_idle_waits_for_matched_processes_to_drop_every_connection() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::NetworkIdle;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 10;
+        let activity = |matched_pids: Vec<u32>, established| {
+            Ok(NetworkActivity {
+                matched_pids,
+                established,
+            })
+        };
 
-    TrayIconBuilder::new()
-        .menu(&menu)
-        .tooltip("Auto Shutdown Scheduler")
-        .on_menu_event(|app, event| match event.id.as_ref() {
-            "quick_start_last_mode" => {
-                if let Err(error) = emit_tray_quick_start_request(app) {
-                    send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
-                }
-            }
-            "show_countdown" => {
-                show_countdown_from_tray(app);
-            }
-            "show" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
-            }
-            "cancel" => {
-                let _ = cancel_active_schedule_internal(app, "cancelled from tray menu", true);
-            }
-            "postpone_10" => {
-                let _ = postpone_schedule_internal(app, 10, "snoozed 10m from tray menu");
-            }
-            "quit" => {
-                if let Err(error) = request_quit_with_guard(app, "trayMenu") {
-                    send_desktop_notification(app, "Auto Shutdown Scheduler", &error);
-                }
-            }
-            _ => {}
-        })
-        .on_tray_icon_event(|tray, event| {
-            if let TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Up,
-                ..
-            } = event
-            {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
-            }
-        })
-        .build(app)?;
+        let probe = network_idle_condition_probe(&mut active, &activity(Vec::new(), 0));
+        assert!(matches!(
+            probe,
+            ConditionProbe::Failed { reason } if reason.starts_with("NO_FAIL_OPEN_NETWORK_IDLE")
+        ));
+        assert_eq!(active.condition_detail.as_deref(), Some("no matching process"));
 
-    Ok(())
-}
+        let probe = network_idle_condition_probe(&mut active, &activity(vec![41, 42], 2));
+        assert_eq!(
+            advance_condition(&mut active, probe, 1_000, false),
+            ConditionTransition::Unchanged
+        );
+        assert_eq!(active.network_connection_count, Some(2));
 
-#[tauri::command]
-fn get_scheduler_snapshot(state: tauri::State<AppState>) -> SchedulerSnapshot {
-    state.snapshot()
-}
+        let probe = network_idle_condition_probe(&mut active, &activity(vec![41, 42], 0));
+        assert_eq!(
+            advance_condition(&mut active, probe, 2_000, false),
+            ConditionTransition::WindowOpened
+        );
+        let probe = network_idle_condition_probe(&mut active, &activity(vec![41, 42], 1));
+        assert_eq!(
+            advance_condition(&mut active, probe, 5_000, false),
+            ConditionTransition::WindowReset
+        );
 
-#[tauri::command]
-async fn list_processes(state: tauri::State<'_, AppState>) -> Result<Vec<ProcessInfo>, String> {
-    let scanner = Arc::clone(&state.scanner);
-    tauri::async_runtime::spawn_blocking(move || {
-        let mut scanner = lock_scanner(&scanner);
-        Ok(scanner.list_running_processes())
-    })
-    .await
-    .map_err(|error| format!("failed to join list_processes worker: {error}"))?
-}
+        let probe = network_idle_condition_probe(&mut active, &activity(vec![41], 0));
+        advance_condition(&mut active, probe, 6_000, false);
+        let probe = network_idle_condition_probe(&mut active, &activity(vec![41], 0));
+        assert!(matches!(
+            advance_condition(&mut active, probe, 16_000, false),
+            ConditionTransition::FinalWarning { .. }
+        ));
+        assert_eq!(active.process_tree_pids, vec![41]);
 
-#[tauri::command]
-fn arm_schedule(
-    app: AppHandle,
-    state: tauri::State<AppState>,
-    request: ScheduleRequest,
-) -> Result<SchedulerSnapshot, String> {
-    let mut store = lock_store(&state.store);
-    let previous_store = store.clone();
-    let had_active_before = previous_store.active.is_some();
-    let summary = upsert_active_schedule(&mut store, request)?;
-    if let Err(error) = state.persist_locked(&store) {
-        *store = previous_store;
-        if had_active_before {
-            let rollback_schedule_id = store.active.as_ref().map(|active| active.id.clone());
-            if let Some(schedule_id) = rollback_schedule_id {
-                push_event(
-                    &mut store,
-                    Some(schedule_id),
-                    "replace_rolled_back",
-                    "error",
-                    Some("새 스케줄 활성화에 실패해 기존 스케줄로 복원했습니다.".to_string()),
-                );
-                let _ = state.persist_locked(&store);
-            }
-        }
-        return Err(format!("상태를 저장하지 못했습니다. 다시 시도해 주세요. ({error})"));
+        let probe = network_idle_condition_probe(
+            &mut active,
+            &Err("failed to read open files of PID 41: permission denied".to_string()),
+        );
+        assert_eq!(
+            advance_condition(&mut active, probe, 17_000, false),
+            ConditionTransition::Unchanged
+        );
+        assert_eq!(active.status, ScheduleStatus::FinalWarning);
+
+        let probe = network_idle_condition_probe(&mut active, &activity(Vec::new(), 0));
+        assert!(matches!(probe, ConditionProbe::Met { .. }));
     }
-    drop(store);
 
-    send_desktop_notification(&app, "Auto Shutdown Scheduler", &summary);
-    Ok(state.snapshot())
-}
+    #[test]
+    fn port_closed_records_transitions_and_rejects_udp_connect_probe() {
+        let udp_connect = PortWatch {
+            port: 27015,
+            protocol: PortProtocol::Udp,
+            probe: PortProbe::Connect,
+        };
+        assert!(validate_port_watch(Some(&udp_connect))
+            .unwrap_err()
+            .contains("only available for tcp"));
+
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::PortClosed;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 5;
+        active.port_watch = Some(PortWatch {
+            port: 5173,
+            protocol: PortProtocol::Tcp,
+            probe: PortProbe::Connect,
+        });
+        let mut events = Vec::new();
+
+        let probe = port_closed_condition_probe(&mut active, &Ok(true), &mut events);
+        assert_eq!(probe, ConditionProbe::Pending);
+        assert!(events.is_empty());
+
+        let probe = port_closed_condition_probe(&mut active, &Ok(false), &mut events);
+        assert_eq!(
+            advance_condition(&mut active, probe, 1_000, false),
+            ConditionTransition::WindowOpened
+        );
+        let probe = port_closed_condition_probe(&mut active, &Ok(true), &mut events);
+        assert_eq!(
+            advance_condition(&mut active, probe, 2_000, false),
+            ConditionTransition::WindowReset
+        );
+        assert_eq!(
+            events,
+            vec![
+                ("port_closed".to_string(), Some("port tcp/5173".to_string())),
+                ("port_reopened".to_string(), Some("port tcp/5173".to_string())),
+            ]
+        );
+
+        let probe = port_closed_condition_probe(&mut active, &Err("boom".to_string()), &mut events);
+        assert_eq!(probe, ConditionProbe::Unavailable);
+        assert_eq!(events.len(), 2);
+        assert_eq!(active.port_open, Some(true));
+    }
 
-#[tauri::command]
-fn cancel_schedule(
-    app: AppHandle,
-    state: tauri::State<AppState>,
-    reason: Option<String>,
-) -> Result<SchedulerSnapshot, String> {
-    cancel_active_schedule_internal(
-        &app,
-        reason
-            .as_deref()
-            .unwrap_or("cancelled by user from UI"),
-        true,
-    )?;
-    Ok(state.snapshot())
-}
+    #[test]
+    fn log_pattern_latches_the_matching_line_into_final_warning() {
+        let watch = LogWatch {
+            path: "  ".to_string(),
+            pattern: "done".to_string(),
+            only_new_lines: false,
+        };
+        assert!(validate_log_watch(Some(&watch)).unwrap_err().contains("path must not be empty"));
+        let watch = LogWatch {
+            path: "/var/log/render.log".to_string(),
+            pattern: "Render (complete".to_string(),
+            only_new_lines: true,
+        };
+        assert!(validate_log_watch(Some(&watch)).unwrap_err().contains("not a valid regex"));
 
-#[tauri::command]
-fn postpone_schedule(
-    app: AppHandle,
-    state: tauri::State<AppState>,
-    minutes: u64,
-    reason: Option<String>,
-) -> Result<SchedulerSnapshot, String> {
-    postpone_schedule_internal(
-        &app,
-        minutes,
-        reason.as_deref().unwrap_or("snoozed by user from UI"),
-    )?;
-    Ok(state.snapshot())
-}
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::LogPattern;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
 
-#[tauri::command]
-fn request_app_quit(
-    app: AppHandle,
-    state: tauri::State<AppState>,
-) -> Result<SchedulerSnapshot, String> {
-    request_quit_with_guard(&app, "uiCommand")?;
-    Ok(state.snapshot())
-}
+        let probe = log_pattern_condition_probe(&mut active, &Ok(None));
+        assert_eq!(
+            advance_condition(&mut active, probe, 1_000, false),
+            ConditionTransition::Unchanged
+        );
 
-#[tauri::command]
-fn resolve_quit_guard(
-    app: AppHandle,
-    state: tauri::State<AppState>,
-    action: QuitGuardAction,
-) -> Result<SchedulerSnapshot, String> {
-    let outcome = {
-        let mut store = lock_store(&state.store);
-        let outcome = apply_quit_guard_action(&mut store, action)?;
-        if outcome.store_changed {
-            state.persist_locked(&store)?;
+        let probe = log_pattern_condition_probe(
+            &mut active,
+            &Ok(Some("[12:00] Render complete: 240 frames".to_string())),
+        );
+        match advance_condition(&mut active, probe, 2_000, false) {
+            ConditionTransition::FinalWarning { reason } => {
+                assert!(reason.contains("Render complete: 240 frames"));
+            }
+            other => panic!("unexpected transition: {other:?}"),
         }
-        outcome
-    };
 
-    if outcome.should_hide_window {
-        if let Some(window) = app.get_webview_window("main") {
-            let _ = window.hide();
-        }
+        let probe = log_pattern_condition_probe(&mut active, &Err("log rotated".to_string()));
+        assert!(matches!(probe, ConditionProbe::Met { .. }));
+        assert_eq!(
+            advance_condition(&mut active, probe, 3_000, false),
+            ConditionTransition::Unchanged
+        );
+        assert_eq!(active.status, ScheduleStatus::FinalWarning);
     }
 
-    if outcome.should_exit {
-        set_allow_exit_once(&state, true);
-        app.exit(0);
-    } else {
-        set_allow_exit_once(&state, false);
+    #[test]
+    fn marker_file_waits_for_the_stable_window_in_either_direction() {
+        let watch = MarkerWatch {
+            path: "/tmp/*/done.flag".to_string(),
+            condition: MarkerCondition::Appears,
+        };
+        assert!(validate_marker_watch(Some(&watch))
+            .unwrap_err()
+            .contains("only supports * and ? in the file name"));
+
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::MarkerFile;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 5;
+        active.marker_watch = Some(MarkerWatch {
+            path: "/home/me/Downloads/*.part".to_string(),
+            condition: MarkerCondition::Disappears,
+        });
+
+        let probe = marker_file_condition_probe(&mut active, &Ok(true));
+        assert_eq!(
+            advance_condition(&mut active, probe, 1_000, false),
+            ConditionTransition::Unchanged
+        );
+        let probe = marker_file_condition_probe(&mut active, &Ok(false));
+        assert_eq!(
+            advance_condition(&mut active, probe, 2_000, false),
+            ConditionTransition::WindowOpened
+        );
+        let probe = marker_file_condition_probe(&mut active, &Err("denied".to_string()));
+        assert_eq!(
+            advance_condition(&mut active, probe, 3_000, false),
+            ConditionTransition::WindowReset
+        );
+        let probe = marker_file_condition_probe(&mut active, &Ok(false));
+        advance_condition(&mut active, probe, 4_000, false);
+        let probe = marker_file_condition_probe(&mut active, &Ok(false));
+        match advance_condition(&mut active, probe, 9_000, false) {
+            ConditionTransition::FinalWarning { reason } => {
+                assert!(reason.contains("*.part disappeared"));
+            }
+            other => panic!("unexpected transition: {other:?}"),
+        }
     }
 
-    Ok(state.snapshot())
-}
+    #[test]
+    fn http_poll_treats_errors_as_not_done() {
+        let remote = HttpWatch {
+            url: "http://10.0.0.5:8080/status".to_string(),
+            expression: "/active == 0".to_string(),
+        };
+        assert!(validate_http_watch(Some(&remote)).unwrap_err().contains("loopback"));
 
-#[tauri::command]
-fn update_settings(
-    state: tauri::State<AppState>,
-    updates: SettingsUpdate,
-) -> Result<SchedulerSnapshot, String> {
-    let mut store = lock_store(&state.store);
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::HttpPoll;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 5;
+        active.http_watch = Some(HttpWatch {
+            url: "http://localhost:6800/status".to_string(),
+            expression: "/active == 0".to_string(),
+        });
 
-    if let Some(alerts) = updates.default_pre_alerts {
-        store.settings.default_pre_alerts = normalize_alerts(&alerts);
+        let probe = http_poll_condition_probe(&mut active, &Ok(true));
+        assert_eq!(
+            advance_condition(&mut active, probe, 1_000, false),
+            ConditionTransition::WindowOpened
+        );
+        let probe = http_poll_condition_probe(&mut active, &Err("connect refused".to_string()));
+        assert_eq!(
+            advance_condition(&mut active, probe, 3_000, false),
+            ConditionTransition::WindowReset
+        );
+        assert!(active
+            .condition_detail
+            .as_deref()
+            .unwrap_or_default()
+            .contains("connect refused"));
+
+        let probe = http_poll_condition_probe(&mut active, &Ok(true));
+        advance_condition(&mut active, probe, 4_000, false);
+        let probe = http_poll_condition_probe(&mut active, &Ok(true));
+        assert!(matches!(
+            advance_condition(&mut active, probe, 9_000, false),
+            ConditionTransition::FinalWarning { .. }
+        ));
     }
 
-    if let Some(final_warning_sec) = updates.final_warning_sec {
-        store.settings.final_warning_sec = validate_final_warning_sec(final_warning_sec)?;
-    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn media_idle_fires_after_playback_stays_stopped() {
+        let (_server, client, status) =
+            media_watch::test_support::fake_mpris(&["org.mpris.MediaPlayer2.mpv"]);
+        let mut watcher = MprisWatcher::with_connection(client);
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::MediaIdle;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 30;
+        let mut step = |active: &mut ActiveSchedule, now: i64| {
+            let observed = watcher.query(None);
+            let probe = media_idle_condition_probe(active, &observed);
+            advance_condition(active, probe, now, false)
+        };
 
-    if let Some(simulate_only) = updates.simulate_only {
-        store.settings.simulate_only = simulate_only;
+        status.set("Playing");
+        assert_eq!(step(&mut active, 1_000), ConditionTransition::Unchanged);
+        assert_eq!(active.condition_detail.as_deref(), Some("playing: mpv"));
+
+        status.set("Paused");
+        assert_eq!(step(&mut active, 2_000), ConditionTransition::WindowOpened);
+        status.set("Playing");
+        assert_eq!(step(&mut active, 20_000), ConditionTransition::WindowReset);
+
+        status.set("Stopped");
+        step(&mut active, 21_000);
+        assert!(matches!(step(&mut active, 51_000), ConditionTransition::FinalWarning { .. }));
     }
 
-    let final_warning_sec = store.settings.final_warning_sec;
-    if let Some(active) = store.active.as_mut() {
-        active.final_warning_duration_sec = final_warning_sec;
-        let _ = sync_shutdown_at_ms(active);
+    #[test]
+    fn battery_low_fires_on_battery_and_guard_holds_on_ac() {
+        let status = |percent, on_ac| {
+            Ok(Some(BatteryStatus {
+                percent,
+                on_ac,
+                charging: on_ac,
+                batteries: 1,
+            }))
+        };
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::BatteryLow;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 5;
+        active.battery_threshold_percent = Some(10);
+
+        let probe = battery_low_condition_probe(&mut active, &status(8, true));
+        assert_eq!(probe, ConditionProbe::Pending);
+        assert_eq!(active.condition_detail.as_deref(), Some("8% on AC"));
+
+        let probe = battery_low_condition_probe(&mut active, &status(9, false));
+        assert_eq!(
+            advance_condition(&mut active, probe, 1_000, false),
+            ConditionTransition::WindowOpened
+        );
+        let probe = battery_low_condition_probe(&mut active, &status(9, false));
+        assert_eq!(advance_condition(&mut active, probe, 6_000, true), ConditionTransition::Held);
+        assert_eq!(active.status, ScheduleStatus::Armed);
+        let probe = battery_low_condition_probe(&mut active, &status(9, false));
+        assert!(matches!(
+            advance_condition(&mut active, probe, 7_000, false),
+            ConditionTransition::FinalWarning { reason } if reason.contains("battery at 9%")
+        ));
+
+        assert_eq!(battery_guard_holds(None), Ok(false));
+        assert_eq!(battery_guard_holds(Some(&status(50, true))), Ok(true));
+        assert_eq!(battery_guard_holds(Some(&status(50, false))), Ok(false));
+        assert!(battery_guard_holds(Some(&Ok(None))).is_err());
+        assert!(battery_guard_holds(Some(&Err("permission denied".to_string()))).is_err());
     }
 
-    push_event(
-        &mut store,
-        None,
-        "settings_updated",
-        "ok",
-        Some("settings were updated".to_string()),
-    );
-    state.persist_locked(&store)?;
-    Ok(state.snapshot())
-}
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn thermal_high_uses_short_final_warning_and_tracks_peak() {
+        let mut store = SchedulerStore::default();
+        let request = ScheduleRequest {
+            mode: ScheduleMode::ThermalHigh,
+            duration_sec: None,
+            target_local_time: None,
+            process_selector: None,
+            pre_alerts: None,
+            process_stable_sec: Some(5),
+            process_instance_threshold: None,
+            launch_command: None,
+            launch_exit_policy: None,
+            systemd_unit: None,
+            port_watch: None,
+            log_watch: None,
+            marker_watch: None,
+            http_watch: None,
+            media_watch: None,
+            battery_threshold_percent: None,
+            require_battery_power: false,
+            thermal_watch: Some(ThermalWatch {
+                sensor: " x86_pkg_temp ".to_string(),
+                threshold_celsius: 90.0,
+            }),
+            uptime_limit_sec: None,
+            not_before_local_time: None,
+            deadline_local_time: None,
+            post_condition_delay_sec: None,
+            action: None,
+        };
+        let mut active =
+            build_active_schedule(&mut store, request).expect("thermal request should be valid");
+        assert_eq!(active.final_warning_duration_sec, THERMAL_FINAL_WARNING_SEC);
+        assert!(active.summary.contains("x86_pkg_temp"));
+
+        let root = thermal::test_support::fake_sys_class_root("lib");
+        let reader = ThermalReader::with_root(root.path());
+        let step = |active: &mut ActiveSchedule, temp: &str, now: i64| {
+            thermal::test_support::write_sensor(
+                root.path(),
+                "thermal",
+                "thermal_zone0",
+                &[("type", "x86_pkg_temp"), ("temp", temp)],
+            );
+            let probe = thermal_high_condition_probe(active, &reader.read("x86_pkg_temp"));
+            advance_condition(active, probe, now, false)
+        };
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_notification::init())
-        .setup(|app| {
-            let state_path = resolve_state_path(app.handle());
-            let mut load_outcome = load_store(&state_path)
-                .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
-            let mut store = load_outcome.store;
-            if enforce_no_resume_in_mvp(&mut store) {
-                load_outcome.needs_persist = true;
+        assert_eq!(step(&mut active, "91000", 1_000), ConditionTransition::WindowOpened);
+        assert_eq!(step(&mut active, "97500", 3_000), ConditionTransition::Unchanged);
+        assert_eq!(step(&mut active, "85000", 4_000), ConditionTransition::WindowReset);
+        assert_eq!(active.thermal_peak_celsius, Some(97.5));
+        assert_eq!(step(&mut active, "92000", 5_000), ConditionTransition::WindowOpened);
+        match step(&mut active, "93000", 10_000) {
+            ConditionTransition::FinalWarning { reason } => {
+                assert!(reason.contains("x86_pkg_temp at 93.0°C"));
+                assert!(reason.contains("peak 97.5°C"));
             }
+            other => panic!("expected final warning, got {other:?}"),
+        }
 
-            if load_outcome.needs_persist {
-                persist_store(&state_path, &store)
-                    .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
-            }
+        assert_eq!(final_warning_sec_for_mode(&ScheduleMode::ThermalHigh, 120), 15);
+        assert_eq!(final_warning_sec_for_mode(&ScheduleMode::Countdown, 120), 120);
+    }
 
-            let startup_notice = load_outcome.startup_notice.take();
-            app.manage(AppState {
-                state_path,
-                store: Mutex::new(store),
-                runtime: Mutex::new(RuntimeState::default()),
-                scanner: Arc::new(Mutex::new(ProcessScanner::new())),
-            });
+    #[test]
+    fn uptime_limit_trigger_follows_boot_time() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::UptimeLimit;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.uptime_limit_sec = Some(12 * 60 * 60);
+        active.boot_time_ms = None;
+        active.fired_alerts = vec![600];
+        let boot = 1_700_000_000_000;
+        let now = boot + 60 * 60 * 1000;
+
+        assert_eq!(realign_uptime_trigger(&mut active, boot, now), Ok(true));
+        assert_eq!(active.trigger_at_ms, Some(boot + 12 * 60 * 60 * 1000));
+        assert_eq!(active.fired_alerts, vec![600]);
+        assert_eq!(
+            compute_shutdown_at_ms(&active),
+            Some(boot + 12 * 60 * 60 * 1000 + 60 * 1000)
+        );
 
-            setup_tray(app.handle())?;
-            start_scheduler_loop(app.handle().clone());
-            if let Some(notice) = startup_notice {
-                send_desktop_notification(app.handle(), "Auto Shutdown Scheduler", &notice);
-            }
-            Ok(())
-        })
-        .invoke_handler(tauri::generate_handler![
-            get_scheduler_snapshot,
-            list_processes,
-            arm_schedule,
-            cancel_schedule,
-            postpone_schedule,
-            request_app_quit,
-            resolve_quit_guard,
-            update_settings
-        ])
-        .build(tauri::generate_context!())
-        .expect("error while building tauri application")
-        .run(|app, event| {
-            if let RunEvent::ExitRequested { api, .. } = event {
-                if consume_allow_exit_once(app) {
-                    return;
-                }
+        assert_eq!(realign_uptime_trigger(&mut active, boot + 1_000, now), Ok(false));
+        assert_eq!(active.boot_time_ms, Some(boot));
+
+        let rebooted = now + 60 * 1000;
+        assert_eq!(realign_uptime_trigger(&mut active, rebooted, rebooted + 1_000), Ok(true));
+        assert_eq!(active.trigger_at_ms, Some(rebooted + 12 * 60 * 60 * 1000));
+
+        postpone_active(&mut active, PostponeTarget::Minutes(10), rebooted + 2_000)
+            .expect("uptime schedule can be postponed");
+        let postponed = active.trigger_at_ms.expect("postponed trigger");
+        assert_eq!(postponed, rebooted + (12 * 60 + 10) * 60 * 1000);
+        active.fired_alerts = vec![600];
+        let stepped = rebooted - 30 * 1000;
+        assert_eq!(realign_uptime_trigger(&mut active, stepped, rebooted + 3_000), Ok(true));
+        assert_eq!(active.trigger_at_ms, Some(postponed - 30 * 1000));
+        assert_eq!(active.fired_alerts, vec![600]);
+
+        active.uptime_limit_sec = Some(60);
+        active.boot_time_ms = None;
+        assert_eq!(realign_uptime_trigger(&mut active, boot, now), Ok(true));
+        assert_eq!(active.trigger_at_ms, Some(now));
+
+        active.uptime_limit_sec = Some(u64::MAX);
+        active.boot_time_ms = None;
+        assert!(realign_uptime_trigger(&mut active, boot, now)
+            .unwrap_err()
+            .contains("uptimeLimitSec must be within"));
+        assert_eq!(active.trigger_at_ms, Some(now));
+        assert!(validate_uptime_limit(0).is_err());
+        assert_eq!(validate_uptime_limit(UPTIME_LIMIT_MAX_SEC), Ok(UPTIME_LIMIT_MAX_SEC));
+    }
 
-                if let Some(status) = active_status_requiring_quit_guard(app) {
-                    api.prevent_exit();
-                    let _ = emit_quit_guard_requested(app, "appExit", status);
-                }
-            }
-        });
-}
+    #[test]
+    fn condition_window_defers_entry_and_deadline_is_reported() {
+        let time = |value: &str| Some(value.to_string());
+        assert!(resolve_condition_window(&ScheduleMode::Countdown, time("22:00").as_ref(), None)
+            .unwrap_err()
+            .contains("condition-based"));
+        assert!(resolve_condition_window(&ScheduleMode::MarkerFile, time("25:00").as_ref(), None)
+            .unwrap_err()
+            .starts_with("notBeforeLocalTime"));
+        assert!(resolve_condition_window(
+            &ScheduleMode::ProcessExit,
+            time("06:00").as_ref(),
+            time("06:00").as_ref()
+        )
+        .is_err());
+        let (not_before_ms, deadline_ms, suffix) = resolve_condition_window(
+            &ScheduleMode::ProcessExit,
+            None,
+            time("06:00").as_ref(),
+        )
+        .expect("deadline-only window is valid");
+        assert_eq!((not_before_ms.is_none(), deadline_ms.is_some()), (true, true));
+        assert_eq!(suffix, " (deadline 06:00)");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::MarkerFile;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 5;
+        active.not_before_ms = Some(20_000);
+        active.deadline_ms = Some(60_000);
+        let met = || ConditionProbe::Met {
+            reason: "marker appeared".to_string(),
+        };
 
-    fn sample_final_warning_schedule() -> ActiveSchedule {
-        ActiveSchedule {
-            id: "sch-test".to_string(),
-            mode: ScheduleMode::Countdown,
-            summary: "test".to_string(),
-            armed_at_ms: 0,
-            trigger_at_ms: Some(0),
-            target_local_time: None,
-            target_tz_offset_minutes: None,
-            pre_alerts: vec![600, 300, 60],
-            fired_alerts: vec![600, 300, 60],
-            process_selector: None,
-            process_tree_pids: Vec::new(),
-            process_stable_sec: 10,
-            process_missing_since_ms: None,
-            snooze_until_ms: None,
-            process_match_degraded_logged: false,
-            status: ScheduleStatus::FinalWarning,
-            final_warning_started_at_ms: Some(1_000),
-            final_warning_duration_sec: 60,
-            shutdown_at_ms: Some(61_000),
-            shutdown_initiated_at_ms: None,
-        }
+        assert_eq!(
+            advance_condition(&mut active, met(), 1_000, false),
+            ConditionTransition::WindowOpened
+        );
+        assert_eq!(
+            advance_condition(&mut active, met(), 6_000, false),
+            ConditionTransition::Deferred
+        );
+        assert_eq!(active.status, ScheduleStatus::Armed);
+        assert!(matches!(
+            advance_condition(&mut active, met(), 20_000, false),
+            ConditionTransition::FinalWarning { .. }
+        ));
+
+        assert!(!deadline_reached(&active, 59_999));
+        assert!(deadline_reached(&active, 60_000));
     }
 
     #[test]
-    fn shutdown_initiation_requires_elapsed_final_warning_window() {
-        let mut schedule = sample_final_warning_schedule();
+    fn post_condition_delay_runs_before_final_warning() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::MarkerFile;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 5;
+        active.post_condition_delay_sec = Some(30 * 60);
+        let met = || ConditionProbe::Met {
+            reason: "marker appeared; entered shutdown waiting mode".to_string(),
+        };
 
-        assert!(!try_mark_shutdown_initiated(&mut schedule, 60_999));
-        assert_eq!(schedule.status, ScheduleStatus::FinalWarning);
-        assert_eq!(schedule.shutdown_initiated_at_ms, None);
+        advance_condition(&mut active, met(), 1_000, false);
+        assert!(matches!(
+            advance_condition(&mut active, met(), 6_000, true),
+            ConditionTransition::DelayStarted { .. }
+        ));
+        assert_eq!(active.status, ScheduleStatus::Armed);
+        assert_eq!(active.post_delay_started_at_ms, Some(6_000));
+        assert_eq!(active.trigger_at_ms, Some(6_000 + 30 * 60 * 1000));
+        assert_eq!(
+            compute_shutdown_at_ms(&active),
+            Some(6_000 + 30 * 60 * 1000 + 60 * 1000)
+        );
 
-        assert!(try_mark_shutdown_initiated(&mut schedule, 61_000));
-        assert_eq!(schedule.status, ScheduleStatus::ShuttingDown);
-        assert_eq!(schedule.shutdown_initiated_at_ms, Some(61_000));
+        let mut store = SchedulerStore::default();
+        let mut request = default_quick_start_request(&store.settings);
+        request.post_condition_delay_sec = Some(60);
+        assert!(build_active_schedule(&mut store, request)
+            .unwrap_err()
+            .contains("condition-based"));
     }
 
     #[test]
-    fn shutdown_initiation_is_idempotent_after_first_transition() {
-        let mut schedule = sample_final_warning_schedule();
+    fn pause_freezes_countdown_and_resume_shifts_trigger() {
+        let mut active = sample_final_warning_schedule();
+        assert!(pause_active(&mut active, 1_000).is_err());
 
-        assert!(try_mark_shutdown_initiated(&mut schedule, 61_000));
-        assert!(!try_mark_shutdown_initiated(&mut schedule, 62_000));
-        assert_eq!(schedule.status, ScheduleStatus::ShuttingDown);
-        assert_eq!(schedule.shutdown_initiated_at_ms, Some(61_000));
+        active.mode = ScheduleMode::Countdown;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.trigger_at_ms = Some(100_000);
+        pause_active(&mut active, 40_000).expect("armed countdown can be paused");
+        assert_eq!(active.shutdown_at_ms, None);
+        assert_eq!(remaining_trigger_ms(&active, 90_000), 60_000);
+        assert!(pause_active(&mut active, 41_000).is_err());
+
+        assert_eq!(resume_active(&mut active, 70_000), Ok(30_000));
+        assert_eq!(active.trigger_at_ms, Some(130_000));
+        assert_eq!(active.shutdown_at_ms, Some(190_000));
+        assert!(resume_active(&mut active, 71_000).is_err());
+
+        active.mode = ScheduleMode::MarkerFile;
+        assert!(pause_active(&mut active, 72_000)
+            .unwrap_err()
+            .contains("post-condition delays"));
+        active.post_delay_started_at_ms = Some(60_000);
+        assert!(pause_active(&mut active, 72_000).is_ok());
+
+        active.deadline_ms = Some(80_000);
+        assert!(!enter_deadline_final_warning(&mut active, 80_000));
+        assert_eq!(active.status, ScheduleStatus::Armed);
+        assert_eq!(active.paused_at_ms, Some(72_000));
+        resume_active(&mut active, 81_000).expect("paused delay resumes");
+        assert!(enter_deadline_final_warning(&mut active, 81_000));
+        assert_eq!(active.status, ScheduleStatus::FinalWarning);
+        assert_eq!(active.final_warning_started_at_ms, Some(81_000));
     }
 
     #[test]
-    fn dry_run_shutdown_logs_abortable_command_plan() {
-        let settings = AppSettings {
-            default_pre_alerts: vec![600, 300, 60],
-            final_warning_sec: 60,
-            simulate_only: true,
+    fn schedule_update_keeps_id_and_reports_field_diff() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::Countdown;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.armed_at_ms = 0;
+        active.trigger_at_ms = Some(1_200_000);
+        active.summary = "Countdown 20m 0s".to_string();
+        active.pre_alerts = vec![600, 300, 60];
+        active.fired_alerts = vec![600];
+        let id = active.id.clone();
+
+        let update = ScheduleUpdate {
+            pre_alerts: Some(vec![900, 600, 60]),
+            ..ScheduleUpdate::default()
+        };
+        let changes = apply_schedule_update(&mut active, &update, 700_000).expect("update");
+        assert_eq!(changes, vec!["preAlerts: [600, 300, 60] -> [900, 600, 60]".to_string()]);
+        assert_eq!(active.id, id);
+        assert_eq!(active.fired_alerts, vec![900, 600]);
+
+        let update = ScheduleUpdate {
+            duration_sec: Some(3_600),
+            ..ScheduleUpdate::default()
+        };
+        let changes = apply_schedule_update(&mut active, &update, 700_000).expect("update");
+        assert_eq!(changes, vec!["durationSec: 1200 -> 3600".to_string()]);
+        assert_eq!(active.summary, "Countdown 60m 0s");
+        assert_eq!(active.trigger_at_ms, Some(3_600_000));
+        assert!(active.fired_alerts.is_empty());
+        assert_eq!(active.shutdown_at_ms, Some(3_660_000));
+        assert_eq!(apply_schedule_update(&mut active, &update, 700_000), Ok(Vec::new()));
+
+        // A paused minute and the action suffix both survive a second edit.
+        active.trigger_at_ms = Some(3_660_000);
+        active.summary = "Countdown 60m 0s; run `backup` instead of shutdown".to_string();
+        let longer = ScheduleUpdate {
+            duration_sec: Some(4_200),
+            ..ScheduleUpdate::default()
+        };
+        let changes = apply_schedule_update(&mut active, &longer, 700_000).expect("update");
+        assert_eq!(changes, vec!["durationSec: 3600 -> 4200".to_string()]);
+        assert_eq!(active.summary, "Countdown 70m 0s; run `backup` instead of shutdown");
+        assert_eq!(active.trigger_at_ms, Some(4_260_000));
+        assert_eq!(apply_schedule_update(&mut active, &longer, 700_000), Ok(Vec::new()));
+
+        let target = ScheduleUpdate {
+            target_local_time: Some("23:00".to_string()),
+            ..ScheduleUpdate::default()
+        };
+        assert!(apply_schedule_update(&mut active, &target, 700_000)
+            .unwrap_err()
+            .contains("specificTime"));
+        let elapsed = ScheduleUpdate {
+            duration_sec: Some(600),
+            pre_alerts: Some(vec![120]),
+            ..ScheduleUpdate::default()
         };
+        assert!(apply_schedule_update(&mut active, &elapsed, 700_000).is_err());
+        assert_eq!(active.pre_alerts, vec![900, 600, 60]);
 
-        let dispatch = run_shutdown_command(&settings)
-            .expect("simulate-only dry run should always return dispatch details");
+        active.status = ScheduleStatus::ShuttingDown;
+        assert!(apply_schedule_update(&mut active, &update, 700_000)
+            .unwrap_err()
+            .contains("shutdown has already started"));
+    }
 
-        assert!(dispatch.dry_run);
-        assert!(dispatch.log_line().contains("DRY_RUN_SHUTDOWN_COMMAND"));
+    #[test]
+    fn postpone_keeps_mode_and_tracks_cumulative_delay() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::SpecificTime;
+        active.target_local_time = Some("22:00".to_string());
+        active.trigger_at_ms = Some(100_000);
+        active.final_warning_started_at_ms = Some(100_000);
+        active.final_warning_duration_sec = 60;
+        active.pre_alerts = vec![600, 300, 60];
+        active.fired_alerts = vec![600, 300, 60];
+
+        let delay = postpone_active(&mut active, PostponeTarget::Minutes(5), 120_000)
+            .expect("final warning can be postponed");
+        assert_eq!(delay, 300);
+        assert!(matches!(active.mode, ScheduleMode::SpecificTime));
+        assert_eq!(active.target_local_time.as_deref(), Some("22:00"));
+        assert_eq!(active.status, ScheduleStatus::Armed);
+        assert_eq!(active.trigger_at_ms, Some(420_000));
+        assert_eq!(active.fired_alerts, vec![600, 300, 60]);
+
+        let delay = postpone_active(&mut active, PostponeTarget::Until(1_020_000), 130_000)
+            .expect("absolute postpone");
+        assert_eq!(delay, 600);
+        assert_eq!(active.trigger_at_ms, Some(1_020_000));
+        assert_eq!(active.postpone_count, 2);
+        assert_eq!(active.postponed_total_sec, 900);
+        assert!(postpone_active(&mut active, PostponeTarget::Until(900_000), 130_000)
+            .unwrap_err()
+            .contains("later than"));
+        assert!(postpone_active(&mut active, PostponeTarget::Minutes(0), 130_000).is_err());
+        assert_eq!(active.postpone_count, 2);
+
+        active.mode = ScheduleMode::MarkerFile;
+        active.trigger_at_ms = None;
+        let delay = postpone_active(&mut active, PostponeTarget::Minutes(10), 200_000)
+            .expect("condition schedules snooze");
+        assert_eq!(delay, 600);
+        assert_eq!(active.snooze_until_ms, Some(800_000));
 
-        #[cfg(target_os = "windows")]
-        {
-            assert!(dispatch.command_line.contains("shutdown /s /t"));
-            assert!(dispatch
-                .command_line
-                .contains(&WINDOWS_ABORTABLE_SHUTDOWN_SEC.to_string()));
-            assert_eq!(dispatch.abort_hint.as_deref(), Some("shutdown /a"));
-        }
+        active.status = ScheduleStatus::ShuttingDown;
+        assert!(postpone_active(&mut active, PostponeTarget::Minutes(5), 200_000).is_err());
+    }
 
-        #[cfg(target_os = "macos")]
-        {
-            assert!(dispatch.command_line.contains("osascript"));
-            assert_eq!(dispatch.abort_hint, None);
-        }
+    #[test]
+    fn postpone_limits_refuse_with_structured_codes() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::Countdown;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.armed_at_ms = 0;
+        active.trigger_at_ms = Some(600_000);
+        let _ = sync_shutdown_at_ms(&mut active);
+
+        let mut settings = AppSettings {
+            max_postpone_count: Some(2),
+            max_postponed_total_sec: Some(900),
+            ..AppSettings::default()
+        };
+        let postpone = |active: &ActiveSchedule, minutes: u64, settings: &AppSettings| {
+            let mut next = active.clone();
+            postpone_active(&mut next, PostponeTarget::Minutes(minutes), 1_000)?;
+            check_postpone_limits(settings, active, &next, 1_000)?;
+            Ok::<_, PostponeError>(next)
+        };
 
-        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-        {
-            assert!(dispatch.command_line.contains("unsupported"));
-            assert_eq!(dispatch.abort_hint, None);
-        }
+        let error = postpone(&active, 20, &settings).unwrap_err();
+        assert_eq!(error.code, PostponeErrorCode::DelayLimit);
+        assert!(error.message.contains("15m 0s left"));
+
+        active = postpone(&active, 10, &settings).expect("within limits");
+        active = postpone(&active, 5, &settings).expect("uses the rest of the budget");
+        assert_eq!(active.postponed_total_sec, 900);
+        let error = postpone(&active, 1, &settings).unwrap_err();
+        assert_eq!(error.code, PostponeErrorCode::CountLimit);
+        assert!(postpone_block_reason(&settings, &active, 1_000).is_some());
+
+        settings.max_postpone_count = None;
+        assert_eq!(
+            postpone(&active, 1, &settings).unwrap_err().code,
+            PostponeErrorCode::DelayLimit
+        );
+
+        settings.max_postponed_total_sec = None;
+        let deadline_ms = now_ms() + 30 * 60 * 1000;
+        let deadline = Local
+            .timestamp_millis_opt(deadline_ms)
+            .single()
+            .expect("local time")
+            .format("%H:%M")
+            .to_string();
+        settings.postpone_deadline_local_time = Some(deadline);
+        active.armed_at_ms = now_ms();
+        let resolved = postpone_deadline_ms(&settings, &active).expect("deadline resolves");
+        assert!(resolved <= deadline_ms && resolved > deadline_ms - 60_000);
+        active.trigger_at_ms = Some(resolved - 120_000);
+        let _ = sync_shutdown_at_ms(&mut active);
+        let mut next = active.clone();
+        postpone_active(&mut next, PostponeTarget::Minutes(5), 1_000).expect("postpone");
+        let error = check_postpone_limits(&settings, &active, &next, 1_000).unwrap_err();
+        assert_eq!(error.code, PostponeErrorCode::Deadline);
+        assert!(postpone_block_reason(&settings, &active, resolved).is_some());
+        assert_eq!(
+            PostponeError::from("no active schedule".to_string()).code,
+            PostponeErrorCode::Rejected
+        );
     }
 
     #[test]
-    fn shutdown_at_ms_policy_for_armed_time_based_modes() {
+    fn cancelled_schedule_can_be_restored_within_window() {
+        let mut store = SchedulerStore::default();
+        store.settings.undo_cancel_window_sec = 30;
         let mut schedule = sample_final_warning_schedule();
-        schedule.status = ScheduleStatus::Armed;
-        schedule.mode = ScheduleMode::Countdown;
-        schedule.trigger_at_ms = Some(10_000);
-        schedule.final_warning_started_at_ms = None;
-        schedule.final_warning_duration_sec = 90;
-        schedule.shutdown_at_ms = None;
+        schedule.fired_alerts = vec![600, 300];
+        let id = schedule.id.clone();
+        store.cancelled = Some(CancelledSchedule {
+            schedule: schedule.clone(),
+            cancelled_at_ms: 100_000,
+        });
 
-        assert!(sync_shutdown_at_ms(&mut schedule));
-        assert_eq!(schedule.shutdown_at_ms, Some(100_000));
+        assert_eq!(undo_cancel_until_ms(&store, 110_000), Some(130_000));
+        store.active = Some(sample_final_warning_schedule());
+        assert!(restore_cancelled_schedule(&mut store, 110_000)
+            .unwrap_err()
+            .contains("already active"));
+        assert_eq!(undo_cancel_until_ms(&store, 110_000), None);
+
+        store.active = None;
+        restore_cancelled_schedule(&mut store, 120_000).expect("within the undo window");
+        let restored = store.active.as_ref().expect("restored schedule");
+        assert_eq!(restored.id, id);
+        assert_eq!(restored.fired_alerts, vec![600, 300]);
+        assert_eq!(restored.trigger_at_ms, schedule.trigger_at_ms);
+        assert_eq!(restored.final_warning_started_at_ms, Some(120_000));
+        let event = store.history.last().expect("history event");
+        assert_eq!(event.event_type, "cancel_undone");
+        assert!(store.cancelled.is_none());
+
+        store.active = None;
+        store.cancelled = Some(CancelledSchedule {
+            schedule,
+            cancelled_at_ms: 100_000,
+        });
+        assert!(restore_cancelled_schedule(&mut store, 130_000).is_err());
+        assert!(store.cancelled.is_none());
     }
 
     #[test]
-    fn shutdown_at_ms_policy_for_process_exit_armed_and_final_warning() {
-        let mut armed = sample_final_warning_schedule();
-        armed.mode = ScheduleMode::ProcessExit;
-        armed.status = ScheduleStatus::Armed;
-        armed.trigger_at_ms = None;
-        armed.final_warning_started_at_ms = None;
-        armed.shutdown_at_ms = Some(1);
-
-        assert!(sync_shutdown_at_ms(&mut armed));
-        assert_eq!(armed.shutdown_at_ms, None);
+    fn schedule_queue_validates_reorders_and_promotes_in_order() {
+        let countdown = |duration_sec: u64| ScheduleRequest {
+            duration_sec: Some(duration_sec),
+            ..default_quick_start_request(&AppSettings::default())
+        };
+        let mut store = SchedulerStore::default();
+        assert!(enqueue_schedule_request(&mut store, countdown(60), 1_000)
+            .unwrap_err()
+            .contains("arm this schedule directly"));
+
+        upsert_active_schedule(&mut store, countdown(600)).expect("arm first schedule");
+        let first = enqueue_schedule_request(&mut store, countdown(7_200), 1_000).expect("queue");
+        let second = enqueue_schedule_request(&mut store, countdown(300), 1_000).expect("queue");
+        assert!(enqueue_schedule_request(&mut store, countdown(0), 1_000).is_err());
+        assert_eq!(store.queue.len(), 2);
+        assert_eq!(store.queue[0].summary, "Countdown 120m 0s");
+
+        let partial = std::slice::from_ref(&second);
+        assert!(reorder_schedule_queue_entries(&mut store, partial).is_err());
+        assert!(
+            reorder_schedule_queue_entries(&mut store, &[second.clone(), second.clone()]).is_err()
+        );
+        reorder_schedule_queue_entries(&mut store, &[second.clone(), first.clone()])
+            .expect("reorder");
+        assert_eq!(store.queue[0].id, second);
+
+        let persisted = SchedulerStore::from_persisted(store.to_persisted());
+        assert_eq!(persisted.queue.len(), 2);
+
+        let finished_id = store.active.as_ref().expect("active").id.clone();
+        let (next, request) =
+            take_next_queued(&mut store, &finished_id).expect("promote head of queue");
+        assert_eq!(next.summary, "Countdown 5m 0s");
+        assert_eq!(request.duration_sec, Some(300));
+        assert_eq!(store.queue.len(), 1);
+        assert_eq!(store.queue[0].behind_schedule_id.as_deref(), Some(next.id.as_str()));
+
+        store.queue[0].request.duration_sec = Some(0);
+        assert!(take_next_queued(&mut store, &next.id).is_none());
+        let event = store.history.last().expect("skip event");
+        assert_eq!(event.event_type, "queue_skipped");
+        assert_eq!(event.schedule_id.as_deref(), Some(first.as_str()));
+
+        assert!(remove_queued_schedule_entry(&mut store, &first).is_err());
+    }
 
-        let mut warning = armed.clone();
-        warning.status = ScheduleStatus::FinalWarning;
-        warning.final_warning_started_at_ms = Some(50_000);
-        warning.final_warning_duration_sec = 60;
+    #[test]
+    fn schedule_queue_is_dropped_with_the_schedule_it_waits_for() {
+        let countdown = |duration_sec: u64| ScheduleRequest {
+            duration_sec: Some(duration_sec),
+            ..default_quick_start_request(&AppSettings::default())
+        };
+        let cleared = |store: &SchedulerStore| {
+            store
+                .history
+                .iter()
+                .filter(|event| event.event_type == "queue_cleared")
+                .count()
+        };
+        let mut store = SchedulerStore::default();
 
-        assert!(sync_shutdown_at_ms(&mut warning));
-        assert_eq!(warning.shutdown_at_ms, Some(110_000));
+        // Restart: the active schedule is not resumed, so its queue must not outlive it.
+        upsert_active_schedule(&mut store, countdown(600)).expect("arm");
+        enqueue_schedule_request(&mut store, countdown(300), 1_000).expect("queue");
+        let mut store = SchedulerStore::from_persisted(store.to_persisted());
+        assert!(enforce_no_resume_in_mvp(&mut store));
+        assert!(store.queue.is_empty());
+        assert_eq!(cleared(&store), 1);
+
+        // Replace: the queue belonged to the replaced schedule.
+        upsert_active_schedule(&mut store, countdown(600)).expect("arm");
+        enqueue_schedule_request(&mut store, countdown(300), 1_000).expect("queue");
+        upsert_active_schedule(&mut store, countdown(900)).expect("replace");
+        assert!(store.queue.is_empty());
+        assert_eq!(cleared(&store), 2);
+
+        // Cancel.
+        enqueue_schedule_request(&mut store, countdown(300), 1_000).expect("queue");
+        apply_quit_guard_action(&mut store, QuitGuardAction::CancelAndQuit).expect("cancel");
+        assert!(store.queue.is_empty());
+        assert_eq!(cleared(&store), 3);
+
+        // An entry left behind by another schedule is never promoted.
+        upsert_active_schedule(&mut store, countdown(600)).expect("arm");
+        enqueue_schedule_request(&mut store, countdown(300), 1_000).expect("queue");
+        store.active = None;
+        store.active = Some(build_active_schedule(&mut store, countdown(60)).expect("build"));
+        let unrelated_id = store.active.as_ref().expect("active").id.clone();
+        let final_warning = || vec![("final_warning".to_string(), Some("ended".to_string()))];
+        assert_eq!(record_tick_events(&mut store, &unrelated_id, true, final_warning()), None);
+        assert!(store.queue.is_empty());
+        assert_eq!(store.active.as_ref().map(|active| active.id.as_str()), Some(&*unrelated_id));
+        let skipped = &store.history[store.history.len() - 2];
+        assert_eq!(skipped.event_type, "queue_skipped");
+        assert!(skipped.reason.as_deref().unwrap_or_default().contains("another schedule"));
+        let event = store.history.last().expect("final warning event");
+        assert_eq!(event.event_type, "final_warning");
+        assert_eq!(event.schedule_id.as_deref(), Some(unrelated_id.as_str()));
+
+        // The schedule an entry was queued behind hands over to it when it finishes.
+        enqueue_schedule_request(&mut store, countdown(300), 1_000).expect("queue");
+        let promoted = record_tick_events(&mut store, &unrelated_id, true, final_warning());
+        let (next_id, summary) = promoted.expect("promote the entry queued behind it");
+        assert_eq!(summary, "Countdown 5m 0s");
+        assert_eq!(store.active.as_ref().map(|active| active.id.as_str()), Some(&*next_id));
+        let completed = &store.history[store.history.len() - 2];
+        assert_eq!(completed.event_type, "completed");
+        assert_eq!(completed.schedule_id.as_deref(), Some(unrelated_id.as_str()));
     }
 
     #[test]
-    fn no_resume_policy_clears_persisted_active_schedule() {
+    fn run_command_action_is_validated_and_named_in_the_summary() {
+        let action: ScheduleAction = serde_json::from_value(serde_json::json!({
+            "type": "runCommand",
+            "command": { "program": " notify-send ", "args": ["download finished"] },
+            "timeoutSec": 30
+        }))
+        .expect("runCommand action parses");
+        let request = |action: Option<ScheduleAction>| ScheduleRequest {
+            duration_sec: Some(600),
+            action,
+            ..default_quick_start_request(&AppSettings::default())
+        };
+
         let mut store = SchedulerStore::default();
-        let active = sample_final_warning_schedule();
-        let schedule_id = active.id.clone();
-        store.active = Some(active);
+        upsert_active_schedule(&mut store, request(Some(action))).expect("arm");
+        let active = store.active.as_ref().expect("active");
+        assert_eq!(
+            active.summary,
+            "Countdown 10m 0s; run `notify-send download finished` instead of shutdown"
+        );
+        let ScheduleAction::RunCommand {
+            command,
+            timeout_sec,
+        } = &active.action
+        else {
+            panic!("expected a runCommand action");
+        };
+        assert_eq!(command.program, "notify-send");
+        assert_eq!(*timeout_sec, 30);
 
-        assert!(enforce_no_resume_in_mvp(&mut store));
-        assert!(store.active.is_none());
+        let too_long = ScheduleAction::RunCommand {
+            command: command.clone(),
+            timeout_sec: RUN_COMMAND_TIMEOUT_MAX_SEC + 1,
+        };
+        assert!(upsert_active_schedule(&mut store, request(Some(too_long))).is_err());
+        let blank = ScheduleAction::RunCommand {
+            command: LaunchCommand {
+                program: " ".to_string(),
+                args: Vec::new(),
+                working_dir: None,
+            },
+            timeout_sec: 30,
+        };
+        assert!(upsert_active_schedule(&mut store, request(Some(blank)))
+            .unwrap_err()
+            .starts_with("action.command.program"));
+
+        upsert_active_schedule(&mut store, request(None)).expect("arm plain shutdown");
+        let mut persisted = serde_json::to_value(store.active.as_ref().unwrap()).unwrap();
+        assert_eq!(persisted["action"]["type"], "shutdown");
+        persisted.as_object_mut().unwrap().remove("action");
+        let restored: ActiveSchedule = serde_json::from_value(persisted).expect("old state loads");
+        assert!(matches!(restored.action, ScheduleAction::Shutdown));
+
+        let run = Ok(TimedRun {
+            exit_code: Some(23),
+            timed_out: false,
+            elapsed: Duration::from_millis(200),
+            stdout_tail: String::new(),
+            stderr_tail: "rsync: connection refused".to_string(),
+        });
+        assert_eq!(
+            timed_run_outcome(&run, 30),
+            (
+                "error",
+                "exit code 23 after 0.2s; stderr: rsync: connection refused".to_string()
+            )
+        );
+    }
 
-        let last_event = store.history.last().expect("startup event should be recorded");
-        assert_eq!(last_event.event_type, "resume_not_supported");
-        assert_eq!(last_event.result, "ok");
-        assert_eq!(last_event.schedule_id.as_deref(), Some(schedule_id.as_str()));
+    #[test]
+    fn graceful_close_validates_selectors_and_reports_outcome() {
+        let selector = |name: &str| ProcessSelector {
+            pid: None,
+            name: Some(name.to_string()),
+            executable: None,
+            cmdline_contains: None,
+        };
+        let close = |selectors: Vec<ProcessSelector>, timeout_sec: u64| GracefulClose {
+            selectors,
+            timeout_sec,
+            on_timeout: LingeringProcessPolicy::Abort,
+        };
+
+        assert!(validate_graceful_close(&close(Vec::new(), 0)).expect("off").is_none());
+        let validated = validate_graceful_close(&close(vec![selector(" code ")], 30))
+            .expect("valid")
+            .expect("enabled");
+        assert_eq!(validated.selectors[0].name.as_deref(), Some("code"));
+        assert!(validate_graceful_close(&close(vec![selector("code")], 0)).is_err());
+        assert!(validate_graceful_close(&close(vec![selector("code")], 301)).is_err());
+        assert!(validate_graceful_close(&close(vec![selector(" ")], 30))
+            .unwrap_err()
+            .starts_with("gracefulClose.selectors[0]"));
+
+        // A bad persisted selector or timeout must not switch the whole phase off.
+        let mut persisted = PersistedState::default();
+        persisted.settings.graceful_close =
+            Some(close(vec![selector(" "), selector("postgres")], 900));
+        assert!(persisted_settings_need_sanitizing(&persisted.settings));
+        let store = SchedulerStore::from_persisted(persisted);
+        let kept = store.settings.graceful_close.as_ref().expect("phase stays enabled");
+        assert_eq!(kept.selectors.len(), 1);
+        assert_eq!(kept.selectors[0].name.as_deref(), Some("postgres"));
+        assert_eq!(kept.timeout_sec, GRACEFUL_CLOSE_TIMEOUT_MAX_SEC);
+        assert_eq!(
+            store
+                .history
+                .iter()
+                .filter(|event| event.event_type == "graceful_close_sanitized")
+                .count(),
+            2
+        );
+        assert!(!persisted_settings_need_sanitizing(&store.settings));
+
+        let report = GracefulCloseReport {
+            closed: vec!["code (41)".to_string()],
+            refused: vec!["postgres (7) [still running]".to_string()],
+            killed: Vec::new(),
+        };
+        assert_eq!(
+            report.event_reason(),
+            "closed: code (41); refused: postgres (7) [still running]"
+        );
     }
 
     #[test]
-    fn no_resume_policy_is_noop_without_active_schedule() {
+    fn shutdown_hooks_are_validated_and_logged_per_run() {
+        let hook = |name: &str, program: &str, timeout_sec: u64| ShutdownHook {
+            name: name.to_string(),
+            command: LaunchCommand {
+                program: program.to_string(),
+                args: vec!["stop".to_string(), "lab-vm".to_string()],
+                working_dir: Some("  ".to_string()),
+            },
+            timeout_sec,
+            abort_on_failure: true,
+        };
+        let hooks = validate_shutdown_hooks(&[hook(" Stop VM ", "virsh", 120)]).expect("valid");
+        assert_eq!(hooks[0].name, "Stop VM");
+        assert!(hooks[0].command.working_dir.is_none());
+        assert!(validate_shutdown_hooks(&[hook("Stop VM", " ", 120)])
+            .unwrap_err()
+            .contains("preShutdownHooks[0].command.program"));
+        assert!(validate_shutdown_hooks(&[hook("Stop VM", "virsh", 0)]).is_err());
+        assert!(validate_shutdown_hooks(&[hook("Stop VM", "virsh", 601)]).is_err());
+        assert!(validate_shutdown_hooks(&vec![hook("Stop VM", "virsh", 5); 9]).is_err());
+
+        let run = |exit_code: Option<i32>, timed_out: bool| {
+            Ok(TimedRun {
+                exit_code,
+                timed_out,
+                elapsed: Duration::from_millis(1_500),
+                stdout_tail: "Domain lab-vm destroyed".to_string(),
+                stderr_tail: String::new(),
+            })
+        };
+        let (result, reason) = shutdown_hook_event(&hooks[0], &run(Some(0), false), false);
+        assert_eq!(result, "ok");
+        assert_eq!(
+            reason,
+            "Stop VM: exit code 0 after 1.5s; stdout: Domain lab-vm destroyed"
+        );
+        let (result, reason) = shutdown_hook_event(&hooks[0], &run(None, true), true);
+        assert_eq!(result, "error");
+        assert!(reason.starts_with("DRY_RUN Stop VM: timed out after 120s"));
+        let (result, _) = shutdown_hook_event(&hooks[0], &run(Some(1), false), false);
+        assert_eq!(result, "error");
+        let spawn_error = Err("failed to run `virsh`".to_string());
+        assert_eq!(shutdown_hook_event(&hooks[0], &spawn_error, false).0, "error");
+    }
+
+    #[test]
+    fn invalid_persisted_hook_is_dropped_alone_and_recorded() {
+        let hook = |name: &str, timeout_sec: u64| ShutdownHook {
+            name: name.to_string(),
+            command: LaunchCommand {
+                program: "umount".to_string(),
+                args: vec!["/mnt/nas".to_string()],
+                working_dir: None,
+            },
+            timeout_sec,
+            abort_on_failure: true,
+        };
+        let mut persisted = PersistedState::default();
+        persisted.settings.pre_shutdown_hooks =
+            vec![hook("Stop VM", 0), hook("Unmount NAS", 60), hook(" ", 60)];
+
+        let store = SchedulerStore::from_persisted(persisted);
+        let names = store
+            .settings
+            .pre_shutdown_hooks
+            .iter()
+            .map(|hook| hook.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Unmount NAS"]);
+        let dropped = store
+            .history
+            .iter()
+            .filter(|event| event.event_type == "pre_shutdown_hook_dropped")
+            .filter_map(|event| event.reason.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 2);
+        assert!(dropped[0].starts_with("preShutdownHooks[0].timeoutSec"));
+        assert!(dropped[1].starts_with("preShutdownHooks[2].name"));
+    }
+
+    #[test]
+    fn schedule_preview_reports_timeline_without_touching_the_store() {
         let mut store = SchedulerStore::default();
+        store.settings.final_warning_sec = 60;
+        let request = ScheduleRequest {
+            duration_sec: Some(300),
+            pre_alerts: Some(vec![600, 60]),
+            ..default_quick_start_request(&store.settings)
+        };
 
-        assert!(!enforce_no_resume_in_mvp(&mut store));
-        assert!(store.history.is_empty());
+        let preview = preview_schedule_request(&store, request.clone()).expect("preview");
+        let trigger = preview.trigger_at_ms.expect("countdown has a trigger");
+        assert_eq!(trigger - preview.computed_at_ms, 300_000);
+        assert_eq!(preview.final_warning_starts_at_ms, Some(trigger));
+        assert_eq!(preview.shutdown_at_ms, Some(trigger + 60_000));
+        assert_eq!(preview.pre_alerts[0].threshold_sec, 600);
+        assert_eq!(preview.pre_alerts[0].fires_at_ms, preview.computed_at_ms);
+        assert_eq!(preview.pre_alerts[1].fires_at_ms, trigger - 60_000);
+        assert!(preview.dst_resolution.is_none());
+        assert!(preview.warnings.iter().any(|warning| warning.contains("fires immediately")));
         assert!(store.active.is_none());
+        assert_eq!(store.id_seq, 0);
+        assert!(store.history.is_empty());
+
+        let specific = ScheduleRequest {
+            mode: ScheduleMode::SpecificTime,
+            duration_sec: None,
+            target_local_time: Some("23:00".to_string()),
+            ..request.clone()
+        };
+        let preview = preview_schedule_request(&store, specific).expect("specific-time preview");
+        assert!(preview.dst_resolution.is_some());
+
+        upsert_active_schedule(&mut store, request.clone()).expect("arm");
+        let preview = preview_schedule_request(&store, request).expect("preview over active");
+        assert!(preview.warnings.iter().any(|warning| warning.starts_with("arming replaces")));
+        assert!(preview_schedule_request(&store, ScheduleRequest {
+            duration_sec: Some(0),
+            ..default_quick_start_request(&store.settings)
+        })
+        .is_err());
     }
 
     #[test]
-    fn process_selector_validation_rejects_missing_or_empty_selector() {
+    fn schedule_presets_validate_names_and_survive_persistence() {
+        let countdown = |duration_sec: u64| ScheduleRequest {
+            duration_sec: Some(duration_sec),
+            ..default_quick_start_request(&AppSettings::default())
+        };
+        let mut store = SchedulerStore::default();
+        let render =
+            save_schedule_preset(&mut store, None, " Overnight render ", countdown(7_200), 1)
+                .expect("save preset");
+        assert_eq!(store.presets[0].name, "Overnight render");
+        assert!(save_schedule_preset(&mut store, None, "overnight RENDER", countdown(60), 2)
+            .unwrap_err()
+            .contains("already exists"));
+        assert!(save_schedule_preset(&mut store, None, "  ", countdown(60), 2).is_err());
+        assert!(save_schedule_preset(&mut store, None, "Movie", countdown(0), 2).is_err());
+        assert!(store.active.is_none(), "saving a preset must not arm it");
+
+        let movie = save_schedule_preset(&mut store, None, "Movie sleep timer", countdown(5_400), 3)
+            .expect("save second preset");
+        save_schedule_preset(&mut store, Some(&render), "Overnight render", countdown(10_800), 4)
+            .expect("rename in place");
+        assert_eq!(store.presets[0].request.duration_sec, Some(10_800));
+        assert_eq!(store.presets[0].created_at_ms, 1);
+        assert_eq!(store.presets[0].updated_at_ms, 4);
         assert_eq!(
-            normalize_and_validate_process_selector(None).unwrap_err(),
-            "process selector is missing"
+            store.history.last().map(|event| event.event_type.as_str()),
+            Some("preset_updated")
         );
 
-        let empty = ProcessSelector {
-            pid: None,
-            name: Some("   ".to_string()),
-            executable: None,
-            cmdline_contains: None,
+        let mut restored = SchedulerStore::from_persisted(store.to_persisted());
+        assert_eq!(restored.presets.len(), 2);
+        delete_schedule_preset(&mut restored, &movie).expect("delete preset");
+        assert!(delete_schedule_preset(&mut restored, &movie).is_err());
+        assert_eq!(restored.presets.len(), 1);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn systemd_unit_request_defaults_to_service_suffix() {
+        let mut store = SchedulerStore::default();
+        let mut request = ScheduleRequest {
+            mode: ScheduleMode::SystemdUnit,
+            duration_sec: None,
+            target_local_time: None,
+            process_selector: None,
+            pre_alerts: None,
+            process_stable_sec: Some(5),
+            process_instance_threshold: None,
+            launch_command: None,
+            launch_exit_policy: None,
+            systemd_unit: None,
+            port_watch: None,
+            log_watch: None,
+            marker_watch: None,
+            http_watch: None,
+            media_watch: None,
+            battery_threshold_percent: None,
+            require_battery_power: false,
+            thermal_watch: None,
+            uptime_limit_sec: None,
+            not_before_local_time: None,
+            deadline_local_time: None,
+            post_condition_delay_sec: None,
+            action: None,
         };
+
+        let error = build_active_schedule(&mut store, request.clone()).unwrap_err();
+        assert!(error.contains("systemdUnit is required"));
+
+        request.systemd_unit = Some(SystemdUnitWatch {
+            unit: "backup job".to_string(),
+            require_success: false,
+        });
+        let error = build_active_schedule(&mut store, request.clone()).unwrap_err();
+        assert!(error.contains("not a valid unit name"));
+
+        request.systemd_unit = Some(SystemdUnitWatch {
+            unit: " backup ".to_string(),
+            require_success: true,
+        });
+        let active =
+            build_active_schedule(&mut store, request).expect("unit request should be valid");
         assert_eq!(
-            normalize_and_validate_process_selector(Some(&empty)).unwrap_err(),
-            "process selector is empty"
+            active.systemd_unit.as_ref().map(|watch| watch.unit.as_str()),
+            Some("backup.service")
         );
+        assert!(active.summary.contains("backup.service"));
+        assert_eq!(active.shutdown_at_ms, None);
     }
 
+    #[cfg(target_os = "linux")]
     #[test]
-    fn process_selector_validation_rejects_shell_name_without_advanced_match() {
-        let selector = ProcessSelector {
-            pid: None,
-            name: Some("powershell".to_string()),
-            executable: None,
-            cmdline_contains: None,
+    fn systemd_unit_condition_waits_for_run_then_honours_stable_window() {
+        let (_server, client, fake) = unit_watch::test_support::fake_systemd("backup.service");
+        let mut watcher = UnitWatcher::with_connection(client);
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::SystemdUnit;
+        active.status = ScheduleStatus::Armed;
+        active.final_warning_started_at_ms = None;
+        active.process_stable_sec = 5;
+        active.systemd_unit = Some(SystemdUnitWatch {
+            unit: "backup.service".to_string(),
+            require_success: false,
+        });
+        let mut step = |active: &mut ActiveSchedule, now: i64| {
+            let observed = watcher.query("backup.service");
+            let probe = systemd_unit_condition_probe(active, &observed);
+            advance_condition(active, probe, now, false)
         };
 
-        let error = normalize_and_validate_process_selector(Some(&selector)).unwrap_err();
-        assert!(error.contains("shell 계열 프로세스"));
+        fake.set(Some(("inactive", "dead")));
+        assert_eq!(step(&mut active, 1_000), ConditionTransition::Unchanged);
+        assert!(!active.unit_seen_active);
+
+        fake.set(Some(("active", "running")));
+        assert_eq!(step(&mut active, 2_000), ConditionTransition::Unchanged);
+        assert!(active.unit_seen_active);
+
+        fake.set(Some(("failed", "failed")));
+        assert_eq!(step(&mut active, 3_000), ConditionTransition::WindowOpened);
+        assert_eq!(step(&mut active, 7_000), ConditionTransition::Unchanged);
+        assert!(matches!(
+            step(&mut active, 8_000),
+            ConditionTransition::FinalWarning { reason } if reason.contains("failed/failed")
+        ));
+        assert_eq!(active.status, ScheduleStatus::FinalWarning);
+
+        fake.set(Some(("activating", "start")));
+        assert!(matches!(step(&mut active, 9_000), ConditionTransition::Reverted { .. }));
+        assert_eq!(active.status, ScheduleStatus::Armed);
+
+        active.systemd_unit = Some(SystemdUnitWatch {
+            unit: "backup.service".to_string(),
+            require_success: true,
+        });
+        fake.set(None);
+        assert!(matches!(
+            step(&mut active, 10_000),
+            ConditionTransition::Skipped { reason } if reason.contains("unloaded before its result")
+        ));
     }
 
     #[test]
-    fn process_selector_validation_accepts_and_normalizes_valid_selector() {
-        let selector = ProcessSelector {
-            pid: Some(1234),
-            name: Some("  pwsh ".to_string()),
-            executable: Some("  C:\\\\Windows\\\\System32\\\\pwsh.exe ".to_string()),
-            cmdline_contains: Some("  -File ".to_string()),
-        };
+    fn unit_observation_errors_keep_final_warning_and_missing_watch_has_own_code() {
+        let mut active = sample_final_warning_schedule();
+        active.mode = ScheduleMode::SystemdUnit;
+        active.systemd_unit = Some(SystemdUnitWatch {
+            unit: "backup.service".to_string(),
+            require_success: false,
+        });
+        let bus_error = Err("org.freedesktop.DBus.Error.NoReply".to_string());
 
-        let normalized = normalize_and_validate_process_selector(Some(&selector))
-            .expect("selector should be valid");
-        assert_eq!(normalized.pid, Some(1234));
-        assert_eq!(normalized.name.as_deref(), Some("pwsh"));
+        let probe = systemd_unit_condition_probe(&mut active, &bus_error);
+        assert_eq!(probe, ConditionProbe::Unavailable);
         assert_eq!(
-            normalized.executable.as_deref(),
-            Some("C:\\\\Windows\\\\System32\\\\pwsh.exe")
+            advance_condition(&mut active, probe, 1_000, false),
+            ConditionTransition::Unchanged
         );
-        assert_eq!(normalized.cmdline_contains.as_deref(), Some("-File"));
+        assert_eq!(active.status, ScheduleStatus::FinalWarning);
+
+        active.status = ScheduleStatus::Armed;
+        active.condition_met_since_ms = Some(500);
+        let probe = systemd_unit_condition_probe(&mut active, &bus_error);
+        assert_eq!(
+            advance_condition(&mut active, probe, 2_000, false),
+            ConditionTransition::WindowReset
+        );
+
+        active.systemd_unit = None;
+        let ConditionProbe::Failed { reason } = systemd_unit_condition_probe(&mut active, &Ok(None))
+        else {
+            panic!("a missing watch must cancel the schedule");
+        };
+        assert!(reason.starts_with("NO_FAIL_OPEN_WATCH_MISSING: systemd unit watch"));
     }
 
     #[test]
@@ -2359,6 +8153,23 @@ mod tests {
             process_selector: None,
             pre_alerts: None,
             process_stable_sec: None,
+            process_instance_threshold: None,
+            launch_command: None,
+            launch_exit_policy: None,
+            systemd_unit: None,
+            port_watch: None,
+            log_watch: None,
+            marker_watch: None,
+            http_watch: None,
+            media_watch: None,
+            battery_threshold_percent: None,
+            require_battery_power: false,
+            thermal_watch: None,
+            uptime_limit_sec: None,
+            not_before_local_time: None,
+            deadline_local_time: None,
+            post_condition_delay_sec: None,
+            action: None,
         };
 
         let error = upsert_active_schedule(&mut store, invalid_request).unwrap_err();
@@ -0,0 +1,290 @@
+use std::{
+    fs::{self, File},
+    io::{ErrorKind, Read, Seek, SeekFrom},
+    path::PathBuf,
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::LogWatch;
+
+const PARTIAL_LINE_LIMIT_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct FileIdentity {
+    dev: u64,
+    ino: u64,
+}
+
+#[cfg(unix)]
+fn file_identity(metadata: &fs::Metadata) -> Option<FileIdentity> {
+    use std::os::unix::fs::MetadataExt;
+    Some(FileIdentity {
+        dev: metadata.dev(),
+        ino: metadata.ino(),
+    })
+}
+
+#[cfg(not(unix))]
+fn file_identity(_metadata: &fs::Metadata) -> Option<FileIdentity> {
+    None
+}
+
+// Captured at arm so a line written before the first poll still counts as new.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogStart {
+    offset: u64,
+    identity: Option<FileIdentity>,
+}
+
+pub(crate) fn capture_log_start(watch: &LogWatch) -> Result<Option<LogStart>, String> {
+    if !watch.only_new_lines {
+        return Ok(None);
+    }
+    match fs::metadata(&watch.path) {
+        Ok(metadata) => Ok(Some(LogStart {
+            offset: metadata.len(),
+            identity: file_identity(&metadata),
+        })),
+        // A file that does not exist yet is new content in its entirety once it shows up.
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(Some(LogStart {
+            offset: 0,
+            identity: None,
+        })),
+        Err(error) => Err(format!("failed to stat {}: {error}", watch.path)),
+    }
+}
+
+#[derive(Debug)]
+struct OpenLog {
+    file: File,
+    identity: Option<FileIdentity>,
+    offset: u64,
+}
+
+// Follows rotation and truncation the way `tail -F` does.
+#[derive(Debug)]
+pub(crate) struct LogTailer {
+    schedule_id: String,
+    path: PathBuf,
+    pattern: Regex,
+    open: Option<OpenLog>,
+    start: Option<LogStart>,
+    skip_existing: bool,
+    partial: Vec<u8>,
+}
+
+impl LogTailer {
+    pub(crate) fn new(
+        schedule_id: &str,
+        watch: &LogWatch,
+        start: Option<LogStart>,
+    ) -> Result<Self, String> {
+        let pattern = Regex::new(&watch.pattern)
+            .map_err(|error| format!("logWatch.pattern is not a valid regex: {error}"))?;
+        Ok(Self {
+            schedule_id: schedule_id.to_string(),
+            path: PathBuf::from(&watch.path),
+            pattern,
+            open: None,
+            start,
+            // Schedules persisted before the start was captured at arm time skip to the end.
+            skip_existing: watch.only_new_lines && start.is_none(),
+            partial: Vec::new(),
+        })
+    }
+
+    pub(crate) fn schedule_id(&self) -> &str {
+        &self.schedule_id
+    }
+
+    pub(crate) fn poll(&mut self) -> Result<Option<String>, String> {
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => Some(metadata),
+            Err(error) if error.kind() == ErrorKind::NotFound => None,
+            Err(error) => {
+                return Err(format!("failed to stat {}: {error}", self.path.display()));
+            }
+        };
+        // A file that shows up later is new content in its entirety.
+        let start = self.start.take();
+        let skip_existing = std::mem::take(&mut self.skip_existing);
+
+        let Some(metadata) = metadata else {
+            // Mid-rotation: whatever the old handle still holds is the last of the old file.
+            return self.drain_open();
+        };
+
+        let identity = file_identity(&metadata);
+        let replaced = match self.open.as_ref() {
+            Some(open) => identity.is_some() && open.identity != identity,
+            None => true,
+        };
+        if replaced {
+            if let Some(line) = self.drain_open()? {
+                return Ok(Some(line));
+            }
+            let mut file = File::open(&self.path)
+                .map_err(|error| format!("failed to open {}: {error}", self.path.display()))?;
+            let seek_to = match start {
+                Some(start) if start.identity == identity && metadata.len() >= start.offset => {
+                    Some(SeekFrom::Start(start.offset))
+                }
+                _ if skip_existing => Some(SeekFrom::End(0)),
+                _ => None,
+            };
+            let offset = match seek_to {
+                Some(position) => file
+                    .seek(position)
+                    .map_err(|error| format!("failed to seek {}: {error}", self.path.display()))?,
+                None => 0,
+            };
+            self.partial.clear();
+            self.open = Some(OpenLog {
+                file,
+                identity,
+                offset,
+            });
+        } else if let Some(open) = self.open.as_mut() {
+            if metadata.len() < open.offset {
+                open.file
+                    .seek(SeekFrom::Start(0))
+                    .map_err(|error| format!("failed to seek {}: {error}", self.path.display()))?;
+                open.offset = 0;
+                self.partial.clear();
+            }
+        }
+
+        self.read_open()
+    }
+
+    fn drain_open(&mut self) -> Result<Option<String>, String> {
+        if self.open.is_none() {
+            return Ok(None);
+        }
+        let result = self.read_open();
+        self.open = None;
+        self.partial.clear();
+        result
+    }
+
+    fn read_open(&mut self) -> Result<Option<String>, String> {
+        let Some(open) = self.open.as_mut() else {
+            return Ok(None);
+        };
+
+        let mut chunk = Vec::new();
+        let read = open
+            .file
+            .read_to_end(&mut chunk)
+            .map_err(|error| format!("failed to read {}: {error}", self.path.display()))?;
+        open.offset += read as u64;
+        self.partial.extend_from_slice(&chunk);
+
+        let mut matched = None;
+        while let Some(newline) = self.partial.iter().position(|byte| *byte == b'\n') {
+            let line = self.partial.drain(..=newline).collect::<Vec<u8>>();
+            if matched.is_some() {
+                continue;
+            }
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+            if self.pattern.is_match(line) {
+                matched = Some(line.to_string());
+            }
+        }
+        if self.partial.len() > PARTIAL_LINE_LIMIT_BYTES {
+            // A runaway line without a newline is not a log line worth keeping in memory.
+            self.partial.clear();
+        }
+        Ok(matched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, path::Path};
+
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn temp_log(label: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new(&format!("log-{label}"));
+        let path = dir.path().join("render.log");
+        (dir, path)
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .expect("open log for append");
+        file.write_all(text.as_bytes()).expect("append log");
+    }
+
+    fn watch(path: &Path, only_new_lines: bool) -> LogWatch {
+        LogWatch {
+            path: path.display().to_string(),
+            pattern: "Render (complete|finished)".to_string(),
+            only_new_lines,
+        }
+    }
+
+    #[test]
+    fn only_new_lines_ignores_matches_written_before_arming() {
+        let (_dir, path) = temp_log("only-new");
+        append(&path, "frame 1\nRender complete\n");
+
+        let mut fresh = LogTailer::new("sch-1", &watch(&path, true), None).expect("valid pattern");
+        assert_eq!(fresh.poll(), Ok(None));
+        let mut existing =
+            LogTailer::new("sch-2", &watch(&path, false), None).expect("valid pattern");
+        assert_eq!(existing.poll(), Ok(Some("Render complete".to_string())));
+
+        append(&path, "frame 2\nRender fin");
+        assert_eq!(fresh.poll(), Ok(None));
+        append(&path, "ished\r\n");
+        assert_eq!(fresh.poll(), Ok(Some("Render finished".to_string())));
+
+    }
+
+    #[test]
+    fn start_captured_at_arm_keeps_lines_written_before_the_first_poll() {
+        let (_dir, path) = temp_log("arm-start");
+        append(&path, "Render complete\n");
+        let watch = watch(&path, true);
+        let start = capture_log_start(&watch).expect("stat log");
+        append(&path, "frame 1\nRender finished\n");
+
+        let mut tailer = LogTailer::new("sch-1", &watch, start).expect("valid pattern");
+        assert_eq!(tailer.poll(), Ok(Some("Render finished".to_string())));
+
+        let _ = fs::remove_file(&path);
+        let start = capture_log_start(&watch).expect("missing log is not an error");
+        append(&path, "Render complete\n");
+        let mut tailer = LogTailer::new("sch-2", &watch, start).expect("valid pattern");
+        assert_eq!(tailer.poll(), Ok(Some("Render complete".to_string())));
+
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tailer_follows_truncation_and_rotation() {
+        let (_dir, path) = temp_log("rotate");
+        append(&path, "a long line that makes the file larger than the next write\n");
+        let mut tailer = LogTailer::new("sch-1", &watch(&path, true), None).expect("valid pattern");
+        assert_eq!(tailer.poll(), Ok(None));
+
+        fs::write(&path, "Render complete\n").expect("truncate log");
+        assert_eq!(tailer.poll(), Ok(Some("Render complete".to_string())));
+
+        let rotated = path.with_extension("log.1");
+        fs::rename(&path, &rotated).expect("rotate log");
+        append(&path, "frame 3\nRender finished\n");
+        assert_eq!(tailer.poll(), Ok(Some("Render finished".to_string())));
+
+    }
+}
@@ -0,0 +1,107 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+pub(crate) fn has_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+pub(crate) fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None::<(usize, usize)>;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(item) if *item == '?' || chars_equal(*item, name[n]) => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|item| *item == '*')
+}
+
+#[cfg(target_os = "windows")]
+fn chars_equal(left: char, right: char) -> bool {
+    left.to_lowercase().eq(right.to_lowercase())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn chars_equal(left: char, right: char) -> bool {
+    left == right
+}
+
+// Globs only in the final component; a missing parent means absent.
+pub(crate) fn marker_present(path: &str) -> Result<bool, String> {
+    let path = Path::new(path);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if !has_glob(&file_name) {
+        return match fs::symlink_metadata(path) {
+            Ok(_) => Ok(true),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(format!("failed to check {}: {error}", path.display())),
+        };
+    }
+
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    let entries = match fs::read_dir(parent) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(false),
+        Err(error) => return Err(format!("failed to list {}: {error}", parent.display())),
+    };
+    Ok(entries
+        .flatten()
+        .any(|entry| glob_matches(&file_name, &entry.file_name().to_string_lossy())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn glob_matches_star_and_question_mark() {
+        assert!(glob_matches("*.crdownload", "movie.mkv.crdownload"));
+        assert!(glob_matches("*.part", ".part"));
+        assert!(glob_matches("render-??.done", "render-07.done"));
+        assert!(glob_matches("a*b*c", "aXXbYYbZZc"));
+        assert!(!glob_matches("*.part", "movie.mkv"));
+        assert!(!glob_matches("render-??.done", "render-7.done"));
+        assert!(!glob_matches("a*b*c", "aXXbYY"));
+    }
+
+    #[test]
+    fn marker_present_checks_plain_paths_and_globs() {
+        let temp = TempDir::new("marker");
+        let dir = temp.path();
+        let pattern = dir.join("*.part").display().to_string();
+        let plain = dir.join("done.flag").display().to_string();
+
+        assert_eq!(marker_present(&pattern), Ok(false));
+        assert_eq!(marker_present(&plain), Ok(false));
+
+        fs::write(dir.join("video.mp4.part"), b"").expect("write partial download");
+        fs::write(dir.join("done.flag"), b"").expect("write flag");
+        assert_eq!(marker_present(&pattern), Ok(true));
+        assert_eq!(marker_present(&plain), Ok(true));
+
+        let missing_dir = dir.join("missing").join("*.part").display().to_string();
+        assert_eq!(marker_present(&missing_dir), Ok(false));
+    }
+}
@@ -0,0 +1,215 @@
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PlaybackState {
+    pub(crate) players: Vec<String>,
+    pub(crate) playing: Vec<String>,
+}
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+
+pub(crate) fn player_matches_filter(bus_name: &str, filter: Option<&str>) -> bool {
+    let Some(player) = bus_name.strip_prefix(MPRIS_PREFIX) else {
+        return false;
+    };
+    match filter {
+        Some(filter) => player.to_lowercase().contains(&filter.to_lowercase()),
+        None => true,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct MprisWatcher {
+    #[cfg(target_os = "linux")]
+    connection: Option<zbus::blocking::Connection>,
+}
+
+impl std::fmt::Debug for MprisWatcher {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.debug_struct("MprisWatcher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl MprisWatcher {
+    #[cfg(test)]
+    pub(crate) fn with_connection(connection: zbus::blocking::Connection) -> Self {
+        Self {
+            connection: Some(connection),
+        }
+    }
+
+    pub(crate) fn query(&mut self, filter: Option<&str>) -> Result<PlaybackState, String> {
+        if self.connection.is_none() {
+            let connection = zbus::blocking::Connection::session()
+                .map_err(|error| format!("failed to connect to the session bus: {error}"))?;
+            self.connection = Some(connection);
+        }
+        let Some(connection) = self.connection.as_ref() else {
+            return Err("session bus connection unavailable".to_string());
+        };
+
+        let result = query_playback_state(connection, filter);
+        if result.is_err() {
+            self.connection = None;
+        }
+        result
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl MprisWatcher {
+    pub(crate) fn query(&mut self, _filter: Option<&str>) -> Result<PlaybackState, String> {
+        Err("media playback watching is only supported on Linux".to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn query_playback_state(
+    connection: &zbus::blocking::Connection,
+    filter: Option<&str>,
+) -> Result<PlaybackState, String> {
+    let bus = zbus::blocking::fdo::DBusProxy::new(connection)
+        .map_err(|error| format!("failed to create bus proxy: {error}"))?;
+    let names = bus
+        .list_names()
+        .map_err(|error| format!("ListNames failed: {error}"))?;
+
+    let mut state = PlaybackState::default();
+    for name in names {
+        let name = name.to_string();
+        if !player_matches_filter(&name, filter) {
+            continue;
+        }
+
+        let player = zbus::blocking::proxy::Builder::<zbus::blocking::Proxy>::new(connection)
+            .destination(name.clone())
+            .and_then(|builder| builder.path("/org/mpris/MediaPlayer2"))
+            .and_then(|builder| builder.interface("org.mpris.MediaPlayer2.Player"))
+            .map(|builder| builder.cache_properties(zbus::proxy::CacheProperties::No))
+            .and_then(|builder| builder.build())
+            .map_err(|error| format!("failed to create player proxy for {name}: {error}"))?;
+        // A player that quits between ListNames and this call is simply gone, not an error.
+        let Ok(status) = player.get_property::<String>("PlaybackStatus") else {
+            continue;
+        };
+
+        let player_name = name.trim_start_matches(MPRIS_PREFIX).to_string();
+        if status == "Playing" {
+            state.playing.push(player_name.clone());
+        }
+        state.players.push(player_name);
+    }
+    Ok(state)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+pub(crate) mod test_support {
+    use std::{
+        os::unix::net::UnixStream,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use zbus::{blocking::connection::Builder, interface, Guid};
+
+    #[derive(Clone)]
+    pub(crate) struct FakePlayerState(Arc<Mutex<String>>);
+
+    impl FakePlayerState {
+        pub(crate) fn set(&self, status: &str) {
+            *self.0.lock().unwrap() = status.to_string();
+        }
+    }
+
+    struct FakeBus {
+        names: Vec<String>,
+    }
+
+    #[interface(name = "org.freedesktop.DBus")]
+    impl FakeBus {
+        fn list_names(&self) -> Vec<String> {
+            self.names.clone()
+        }
+    }
+
+    struct FakePlayer {
+        status: FakePlayerState,
+    }
+
+    #[interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl FakePlayer {
+        #[zbus(property)]
+        fn playback_status(&self) -> String {
+            self.status.0.lock().unwrap().clone()
+        }
+    }
+
+    pub(crate) fn fake_mpris(
+        names: &[&str],
+    ) -> (zbus::blocking::Connection, zbus::blocking::Connection, FakePlayerState) {
+        let (server_stream, client_stream) = UnixStream::pair().expect("socket pair");
+        let status = FakePlayerState(Arc::new(Mutex::new("Stopped".to_string())));
+        let bus = FakeBus {
+            names: names.iter().map(|name| name.to_string()).collect(),
+        };
+        let player = FakePlayer {
+            status: status.clone(),
+        };
+
+        let server = thread::spawn(move || {
+            Builder::async_io_unix_stream(server_stream)
+                .server(Guid::generate())
+                .expect("server guid")
+                .p2p()
+                .serve_at("/org/freedesktop/DBus", bus)
+                .expect("serve bus")
+                .serve_at("/org/mpris/MediaPlayer2", player)
+                .expect("serve player")
+                .build()
+                .expect("server connection")
+        });
+        let client = Builder::async_io_unix_stream(client_stream)
+            .p2p()
+            .build()
+            .expect("client connection");
+        let server = server.join().expect("server thread");
+        (server, client, status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_filter_matches_mpris_suffix_case_insensitively() {
+        assert!(player_matches_filter("org.mpris.MediaPlayer2.vlc", None));
+        assert!(player_matches_filter(
+            "org.mpris.MediaPlayer2.firefox.instance_1_42",
+            Some("Firefox")
+        ));
+        assert!(!player_matches_filter("org.mpris.MediaPlayer2.vlc", Some("spotify")));
+        assert!(!player_matches_filter("org.freedesktop.Notifications", None));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn mpris_watcher_reports_playing_players_from_mock_bus() {
+        let (_server, client, status) = test_support::fake_mpris(&[
+            "org.freedesktop.DBus",
+            "org.mpris.MediaPlayer2.vlc",
+            ":1.42",
+        ]);
+        let mut watcher = MprisWatcher::with_connection(client);
+
+        let state = watcher.query(None).expect("query should succeed");
+        assert_eq!(state.players, vec!["vlc".to_string()]);
+        assert!(state.playing.is_empty());
+
+        status.set("Playing");
+        let state = watcher.query(None).expect("query should succeed");
+        assert_eq!(state.playing, vec!["vlc".to_string()]);
+
+        let state = watcher.query(Some("spotify")).expect("query should succeed");
+        assert!(state.players.is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    collections::HashSet,
+    fs,
+    io::ErrorKind,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream},
+    path::PathBuf,
+    time::Duration,
+};
+
+use super::PortProtocol;
+
+pub(crate) const TCP_STATE_ESTABLISHED: u8 = 0x01;
+pub(crate) const TCP_STATE_LISTEN: u8 = 0x0A;
+const CONNECT_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SocketEntry {
+    pub(crate) local_addr: IpAddr,
+    pub(crate) local_port: u16,
+    pub(crate) state: u8,
+    pub(crate) inode: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NetworkActivity {
+    pub(crate) matched_pids: Vec<u32>,
+    pub(crate) established: u32,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct NetScanner {
+    root: PathBuf,
+}
+
+impl Default for NetScanner {
+    fn default() -> Self {
+        Self::with_root("/proc")
+    }
+}
+
+impl NetScanner {
+    pub(crate) fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    // A missing table (e.g. IPv6 disabled) reads as empty.
+    pub(crate) fn read_table(&self, table: &str) -> Result<Vec<SocketEntry>, String> {
+        let path = self.root.join("net").join(table);
+        match fs::read_to_string(&path) {
+            Ok(text) => Ok(parse_socket_table(&text)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(error) => Err(format!("failed to read {}: {error}", path.display())),
+        }
+    }
+
+    pub(crate) fn socket_inodes(&self, pid: u32) -> Result<HashSet<u64>, String> {
+        let fd_dir = self.root.join(pid.to_string()).join("fd");
+        let entries = match fs::read_dir(&fd_dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(HashSet::new()),
+            Err(error) => {
+                return Err(format!("failed to read open files of PID {pid}: {error}"));
+            }
+        };
+
+        let mut inodes = HashSet::new();
+        for entry in entries.flatten() {
+            if let Ok(target) = fs::read_link(entry.path()) {
+                if let Some(inode) = parse_socket_inode(&target.to_string_lossy()) {
+                    inodes.insert(inode);
+                }
+            }
+        }
+        Ok(inodes)
+    }
+
+    pub(crate) fn count_established(&self, pids: &[u32]) -> Result<u32, String> {
+        let mut inodes = HashSet::new();
+        for pid in pids {
+            inodes.extend(self.socket_inodes(*pid)?);
+        }
+        if inodes.is_empty() {
+            return Ok(0);
+        }
+
+        let mut established = 0;
+        for table in ["tcp", "tcp6"] {
+            established += self
+                .read_table(table)?
+                .iter()
+                .filter(|entry| entry.state == TCP_STATE_ESTABLISHED && inodes.contains(&entry.inode))
+                .count() as u32;
+        }
+        Ok(established)
+    }
+
+    pub(crate) fn is_port_listening(&self, port: u16, protocol: PortProtocol) -> Result<bool, String> {
+        let tables = match protocol {
+            PortProtocol::Tcp => ["tcp", "tcp6"],
+            PortProtocol::Udp => ["udp", "udp6"],
+        };
+        for table in tables {
+            let listening = self.read_table(table)?.iter().any(|entry| {
+                entry.local_port == port
+                    && (entry.local_addr.is_loopback() || entry.local_addr.is_unspecified())
+                    && (matches!(protocol, PortProtocol::Udp) || entry.state == TCP_STATE_LISTEN)
+            });
+            if listening {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+pub(crate) fn accepts_local_connection(port: u16) -> bool {
+    [IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V6(Ipv6Addr::LOCALHOST)]
+        .into_iter()
+        .any(|addr| {
+            TcpStream::connect_timeout(&SocketAddr::new(addr, port), CONNECT_PROBE_TIMEOUT).is_ok()
+        })
+}
+
+pub(crate) fn parse_socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+}
+
+pub(crate) fn parse_socket_table(text: &str) -> Vec<SocketEntry> {
+    text.lines().skip(1).filter_map(parse_socket_row).collect()
+}
+
+fn parse_socket_row(line: &str) -> Option<SocketEntry> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+    if fields.len() < 10 {
+        return None;
+    }
+
+    let (addr, port) = fields[1].split_once(':')?;
+    Some(SocketEntry {
+        local_addr: parse_hex_addr(addr)?,
+        local_port: u16::from_str_radix(port, 16).ok()?,
+        state: u8::from_str_radix(fields[3], 16).ok()?,
+        inode: fields[9].parse().ok()?,
+    })
+}
+
+// Each 32-bit word is printed in host byte order.
+fn parse_hex_addr(hex: &str) -> Option<IpAddr> {
+    match hex.len() {
+        8 => {
+            let word = u32::from_str_radix(hex, 16).ok()?;
+            Some(IpAddr::V4(Ipv4Addr::from(word.to_ne_bytes())))
+        }
+        32 => {
+            let mut octets = [0u8; 16];
+            for (index, chunk) in octets.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(&hex[index * 8..index * 8 + 8], 16).ok()?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            Some(IpAddr::V6(Ipv6Addr::from(octets)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::{fs, path::Path};
+
+    use crate::test_support::TempDir;
+
+    pub(crate) const TCP_HEADER: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n";
+
+    pub(crate) fn tcp_row(local: &str, remote: &str, state: u8, inode: u64) -> String {
+        format!(
+            "   0: {local} {remote} {state:02X} 00000000:00000000 00:00000000 00000000  1000        0 {inode} 1 0000000000000000 20 4 30 10 -1\n"
+        )
+    }
+
+    pub(crate) fn fake_proc_root(label: &str) -> TempDir {
+        let root = TempDir::new(&format!("proc-{label}"));
+        fs::create_dir_all(root.path().join("net")).expect("create fake proc root");
+        root
+    }
+
+    pub(crate) fn write_table(root: &Path, table: &str, rows: &[String]) {
+        let text = std::iter::once(TCP_HEADER.to_string())
+            .chain(rows.iter().cloned())
+            .collect::<String>();
+        fs::write(root.join("net").join(table), text).expect("write fake socket table");
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn add_socket_fd(root: &Path, pid: u32, fd: u32, inode: u64) {
+        let fd_dir = root.join(pid.to_string()).join("fd");
+        fs::create_dir_all(&fd_dir).expect("create fake fd dir");
+        std::os::unix::fs::symlink(format!("socket:[{inode}]"), fd_dir.join(fd.to_string()))
+            .expect("create fake socket fd");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_support::*, *};
+
+    #[test]
+    fn socket_table_rows_decode_addresses_ports_and_states() {
+        let text = format!(
+            "{TCP_HEADER}{}{}",
+            tcp_row("0100007F:1F90", "00000000:0000", TCP_STATE_LISTEN, 111),
+            tcp_row("0A00020F:C350", "5DB8D822:01BB", TCP_STATE_ESTABLISHED, 222),
+        );
+        let entries = parse_socket_table(&text);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].local_addr, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(entries[0].local_port, 8080);
+        assert_eq!(entries[0].state, TCP_STATE_LISTEN);
+        assert_eq!(entries[1].local_addr, IpAddr::V4(Ipv4Addr::new(15, 2, 0, 10)));
+        assert_eq!(entries[1].inode, 222);
+
+        let v6 = parse_socket_table(&format!(
+            "{TCP_HEADER}{}",
+            tcp_row(
+                "00000000000000000000000001000000:1F90",
+                "00000000000000000000000000000000:0000",
+                TCP_STATE_LISTEN,
+                333
+            )
+        ));
+        assert_eq!(v6[0].local_addr, IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert_eq!(parse_socket_inode("socket:[4242]"), Some(4242));
+        assert_eq!(parse_socket_inode("pipe:[4242]"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn count_established_only_counts_sockets_owned_by_the_given_pids() {
+        let root = fake_proc_root("established");
+        add_socket_fd(root.path(), 100, 3, 501);
+        add_socket_fd(root.path(), 100, 4, 502);
+        add_socket_fd(root.path(), 200, 3, 601);
+        write_table(
+            root.path(),
+            "tcp",
+            &[
+                tcp_row("0100007F:C350", "0100007F:1F90", TCP_STATE_ESTABLISHED, 501),
+                tcp_row("00000000:1F90", "00000000:0000", TCP_STATE_LISTEN, 502),
+                tcp_row("0100007F:C351", "0100007F:1F90", TCP_STATE_ESTABLISHED, 601),
+            ],
+        );
+        let scanner = NetScanner::with_root(root.path());
+
+        assert_eq!(scanner.count_established(&[100]), Ok(1));
+        assert_eq!(scanner.count_established(&[100, 200]), Ok(2));
+        assert_eq!(scanner.count_established(&[300]), Ok(0));
+
+    }
+
+    #[test]
+    fn port_listening_ignores_remote_binds_and_non_listening_tcp() {
+        let root = fake_proc_root("listening");
+        write_table(
+            root.path(),
+            "tcp",
+            &[
+                tcp_row("0100007F:C350", "0100007F:1F90", TCP_STATE_ESTABLISHED, 1),
+                tcp_row("0F02000A:1F91", "00000000:0000", TCP_STATE_LISTEN, 2),
+            ],
+        );
+        write_table(
+            root.path(),
+            "udp6",
+            &[tcp_row(
+                "00000000000000000000000000000000:6987",
+                "00000000000000000000000000000000:0000",
+                0x07,
+                3,
+            )],
+        );
+        let scanner = NetScanner::with_root(root.path());
+
+        assert_eq!(scanner.is_port_listening(0xC350, PortProtocol::Tcp), Ok(false));
+        assert_eq!(scanner.is_port_listening(0x1F91, PortProtocol::Tcp), Ok(false));
+        assert_eq!(scanner.is_port_listening(0x6987, PortProtocol::Udp), Ok(true));
+
+        write_table(
+            root.path(),
+            "tcp6",
+            &[tcp_row(
+                "00000000000000000000000000000000:1F91",
+                "00000000000000000000000000000000:0000",
+                TCP_STATE_LISTEN,
+                4,
+            )],
+        );
+        assert_eq!(scanner.is_port_listening(0x1F91, PortProtocol::Tcp), Ok(true));
+
+    }
+
+    #[test]
+    fn connect_probe_sees_a_local_listener() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind local listener");
+        let port = listener.local_addr().expect("listener address").port();
+        assert!(accepts_local_connection(port));
+
+        drop(listener);
+        assert!(!accepts_local_connection(port));
+    }
+}
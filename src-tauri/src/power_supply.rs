@@ -0,0 +1,148 @@
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+use super::BatteryStatus;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PowerSupplyReader {
+    root: PathBuf,
+}
+
+impl Default for PowerSupplyReader {
+    fn default() -> Self {
+        Self::with_root("/sys/class/power_supply")
+    }
+}
+
+impl PowerSupplyReader {
+    pub(crate) fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    // `Ok(None)` without a system battery.
+    pub(crate) fn read(&self) -> Result<Option<BatteryStatus>, String> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(format!("failed to list {}: {error}", self.root.display()));
+            }
+        };
+
+        let mut capacities = Vec::new();
+        let mut discharging = false;
+        let mut charging = false;
+        let mut mains_seen = false;
+        let mut mains_online = false;
+        for entry in entries.flatten() {
+            let supply = entry.path();
+            let read = |name: &str| {
+                fs::read_to_string(supply.join(name))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+            };
+
+            match read("type").as_deref() {
+                Some("Battery") => {
+                    // Wireless mice and headsets report scope=Device; only system batteries count.
+                    if read("scope").as_deref() == Some("Device") {
+                        continue;
+                    }
+                    let Some(capacity) = read("capacity")
+                        .and_then(|value| value.parse::<u8>().ok())
+                        .or_else(|| ratio_percent(read("energy_now"), read("energy_full")))
+                        .or_else(|| ratio_percent(read("charge_now"), read("charge_full")))
+                    else {
+                        continue;
+                    };
+                    capacities.push(capacity.min(100) as u32);
+                    match read("status").as_deref() {
+                        Some("Discharging") => discharging = true,
+                        Some("Charging") => charging = true,
+                        _ => {}
+                    }
+                }
+                Some("Mains") | Some("USB") | Some("USB_C") | Some("USB_PD") => {
+                    mains_seen = true;
+                    mains_online |= read("online").as_deref() == Some("1");
+                }
+                _ => {}
+            }
+        }
+
+        if capacities.is_empty() {
+            return Ok(None);
+        }
+        let percent = (capacities.iter().sum::<u32>() as f64 / capacities.len() as f64).round();
+        Ok(Some(BatteryStatus {
+            percent: percent as u8,
+            on_ac: if mains_seen { mains_online } else { !discharging },
+            charging,
+            batteries: capacities.len() as u32,
+        }))
+    }
+}
+
+fn ratio_percent(now: Option<String>, full: Option<String>) -> Option<u8> {
+    let now = now?.parse::<f64>().ok()?;
+    let full = full?.parse::<f64>().ok()?;
+    if full <= 0.0 {
+        return None;
+    }
+    Some(((now / full) * 100.0).round().clamp(0.0, 100.0) as u8)
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::fs;
+
+    use crate::test_support::TempDir;
+
+    pub(crate) fn fake_power_root(label: &str) -> TempDir {
+        TempDir::new(&format!("power-{label}"))
+    }
+
+    pub(crate) fn write_supply(root: &std::path::Path, name: &str, attributes: &[(&str, &str)]) {
+        let supply = root.join(name);
+        fs::create_dir_all(&supply).expect("create fake supply");
+        for (attribute, value) in attributes {
+            fs::write(supply.join(attribute), format!("{value}\n")).expect("write attribute");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_support::*, *};
+
+    #[test]
+    fn reader_averages_system_batteries_and_reports_mains() {
+        let root = fake_power_root("laptop");
+        let reader = PowerSupplyReader::with_root(root.path());
+        assert_eq!(reader.read(), Ok(None));
+
+        write_supply(
+            root.path(),
+            "BAT0",
+            &[("type", "Battery"), ("capacity", "9"), ("status", "Discharging")],
+        );
+        write_supply(
+            root.path(),
+            "BAT1",
+            &[("type", "Battery"), ("energy_now", "15000"), ("energy_full", "50000")],
+        );
+        write_supply(
+            root.path(),
+            "hidpp_battery_0",
+            &[("type", "Battery"), ("scope", "Device"), ("capacity", "100")],
+        );
+        write_supply(root.path(), "AC", &[("type", "Mains"), ("online", "0")]);
+
+        let status = reader.read().expect("read batteries").expect("battery present");
+        assert_eq!(status.percent, 20);
+        assert_eq!(status.batteries, 2);
+        assert!(!status.on_ac);
+
+        write_supply(root.path(), "AC", &[("type", "Mains"), ("online", "1")]);
+        assert!(reader.read().expect("read batteries").expect("battery present").on_ac);
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use sysinfo::{Pid, ProcessesToUpdate, System};
+use sysinfo::{Pid, Process, ProcessStatus, ProcessesToUpdate, Signal, System};
 
 use super::{
     is_shell_like_process_name, normalize_selector_path, normalize_selector_text, ProcessInfo,
@@ -52,9 +52,13 @@ impl ProcessScanner {
         &mut self,
         selector: &ProcessSelector,
         tracked_pids: &[u32],
+        instance_threshold: Option<u32>,
     ) -> ProcessMatchResult {
         self.refresh();
 
+        // With a threshold every source adds to the count.
+        let count_all = instance_threshold.is_some();
+
         let selector_name =
             normalize_selector_text(selector.name.as_ref()).map(|name| name.to_lowercase());
         let selector_executable = normalize_selector_path(selector.executable.as_ref());
@@ -72,6 +76,7 @@ impl ProcessScanner {
         let advanced_requested = selector_executable.is_some() || selector_cmdline.is_some();
         let mut advanced_data_unavailable = false;
         let mut running = false;
+        let mut name_fallback_matched = false;
         let mut source = ProcessMatchSource::None;
         let mut next_tracked = tracked_pids.iter().copied().collect::<HashSet<u32>>();
 
@@ -99,7 +104,7 @@ impl ProcessScanner {
             source = ProcessMatchSource::TrackedPids;
         }
 
-        if !running && advanced_requested {
+        if (!running || count_all) && advanced_requested {
             let advanced_match = self
                 .system
                 .processes()
@@ -151,20 +156,32 @@ impl ProcessScanner {
                 .collect::<Vec<_>>();
 
             if !advanced_match.is_empty() {
-                running = true;
-                source = ProcessMatchSource::Advanced;
+                if !running {
+                    running = true;
+                    source = ProcessMatchSource::Advanced;
+                }
                 next_tracked.extend(advanced_match);
             }
         }
 
-        if !running && allow_name_fallback {
+        if (!running || count_all) && allow_name_fallback {
             if let Some(needle) = selector_name.as_ref() {
                 let by_name = self
                     .system
                     .processes()
                     .iter()
                     .filter_map(|(pid, process)| {
-                        if process.name().to_string_lossy().to_lowercase() == *needle {
+                        // When counting, same-name processes only stand in for unreadable criteria.
+                        let unverifiable = !count_all
+                            || !advanced_requested
+                            || advanced_match(
+                                process,
+                                selector_executable.as_ref(),
+                                selector_cmdline.as_ref(),
+                            )
+                            .is_none();
+                        let name = process.name().to_string_lossy().to_lowercase();
+                        if unverifiable && name == *needle {
                             Some(pid.as_u32())
                         } else {
                             None
@@ -172,8 +189,11 @@ impl ProcessScanner {
                     })
                     .collect::<Vec<_>>();
                 if !by_name.is_empty() {
-                    running = true;
-                    source = ProcessMatchSource::NameFallback;
+                    if !running {
+                        running = true;
+                        source = ProcessMatchSource::NameFallback;
+                    }
+                    name_fallback_matched = true;
                     next_tracked.extend(by_name);
                 }
             }
@@ -181,17 +201,112 @@ impl ProcessScanner {
 
         let mut normalized = next_tracked.into_iter().collect::<Vec<_>>();
         normalized.sort_unstable();
+        let instance_count = normalized.len() as u32;
+        if let Some(threshold) = instance_threshold {
+            running = instance_count >= threshold;
+        }
 
         ProcessMatchResult {
             running,
             matched_pids: normalized,
+            instance_count,
             source,
-            degraded_to_name: advanced_requested
-                && advanced_data_unavailable
-                && source == ProcessMatchSource::NameFallback,
+            degraded_to_name: advanced_requested && advanced_data_unavailable && name_fallback_matched,
+        }
+    }
+
+    pub(crate) fn close_targets(&mut self, selector: &ProcessSelector) -> Vec<u32> {
+        self.refresh();
+
+        let selector_name =
+            normalize_selector_text(selector.name.as_ref()).map(|name| name.to_lowercase());
+        let selector_executable = normalize_selector_path(selector.executable.as_ref());
+        let selector_cmdline = normalize_selector_text(selector.cmdline_contains.as_ref())
+            .map(|item| item.to_lowercase());
+        let advanced_requested = selector_executable.is_some() || selector_cmdline.is_some();
+
+        let mut targets = HashSet::<u32>::new();
+        if let Some(pid) = selector.pid {
+            let children_index = self.build_children_index();
+            targets.extend(collect_tree_from_index(
+                Pid::from_u32(pid),
+                &children_index,
+                |candidate| self.system.process(candidate).is_some(),
+            ));
+        }
+
+        for (pid, process) in self.system.processes() {
+            let name_matches = selector_name
+                .as_ref()
+                .is_some_and(|name| process.name().to_string_lossy().to_lowercase() == *name);
+            let matched = if advanced_requested {
+                // Same-name processes only count when their exe/cmdline cannot be read.
+                match advanced_match(
+                    process,
+                    selector_executable.as_ref(),
+                    selector_cmdline.as_ref(),
+                ) {
+                    Some(matched) => matched,
+                    None => name_matches,
+                }
+            } else {
+                name_matches && !selector_name.as_deref().is_some_and(is_shell_like_process_name)
+            };
+            if matched {
+                targets.insert(pid.as_u32());
+            }
+        }
+
+        let mut targets = targets.into_iter().collect::<Vec<_>>();
+        targets.sort_unstable();
+        targets
+    }
+
+    pub(crate) fn process_name(&self, pid: u32) -> Option<String> {
+        self.system
+            .process(Pid::from_u32(pid))
+            .map(|process| process.name().to_string_lossy().to_string())
+    }
+
+    // Zombies count as gone.
+    pub(crate) fn alive_pids(&mut self, pids: &[u32]) -> Vec<u32> {
+        let targets = pids.iter().copied().map(Pid::from_u32).collect::<Vec<_>>();
+        self.system.refresh_processes(ProcessesToUpdate::Some(&targets), true);
+        pids.iter()
+            .copied()
+            .filter(|pid| {
+                self.system
+                    .process(Pid::from_u32(*pid))
+                    .is_some_and(|process| process.status() != ProcessStatus::Zombie)
+            })
+            .collect()
+    }
+
+    pub(crate) fn request_close(&mut self, pid: u32) -> Result<(), String> {
+        // An already-exited pid is left for the caller's liveness poll to report as closed.
+        if self.alive_pids(&[pid]).is_empty() {
+            return Ok(());
+        }
+        let process = self
+            .system
+            .process(Pid::from_u32(pid))
+            .ok_or("process already exited".to_string())?;
+        match process.kill_with(Signal::Term) {
+            Some(true) => Ok(()),
+            Some(false) => Err("termination request was rejected".to_string()),
+            None => Err("polite termination is not supported on this platform".to_string()),
         }
     }
 
+    pub(crate) fn force_kill(&mut self, pid: u32) -> bool {
+        if self.alive_pids(&[pid]).is_empty() {
+            return false;
+        }
+        self.system
+            .process(Pid::from_u32(pid))
+            .is_some_and(|process| process.kill())
+    }
+
     fn build_children_index(&self) -> HashMap<Pid, Vec<Pid>> {
         let mut index = HashMap::<Pid, Vec<Pid>>::new();
         for (pid, process) in self.system.processes() {
@@ -203,6 +318,45 @@ impl ProcessScanner {
     }
 }
 
+fn advanced_match(
+    process: &Process,
+    executable: Option<&String>,
+    cmdline: Option<&String>,
+) -> Option<bool> {
+    let mut unreadable = false;
+    if let Some(expected) = executable {
+        match process.exe() {
+            Some(path) => {
+                if path.display().to_string().replace('\\', "/").to_lowercase() != *expected {
+                    return Some(false);
+                }
+            }
+            None => unreadable = true,
+        }
+    }
+    if let Some(token) = cmdline {
+        let parts = process.cmd();
+        if parts.is_empty() {
+            unreadable = true;
+        } else {
+            let joined = parts
+                .iter()
+                .map(|part| part.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase();
+            if !joined.contains(token) {
+                return Some(false);
+            }
+        }
+    }
+    if unreadable {
+        None
+    } else {
+        Some(true)
+    }
+}
+
 fn collect_tree_from_index<F>(
     root_pid: Pid,
     children_index: &HashMap<Pid, Vec<Pid>>,
@@ -259,4 +413,90 @@ mod tests {
 
         assert_eq!(collected, vec![10, 11, 12, 13]);
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn instance_threshold_counts_only_cmdline_matches_when_they_are_readable() {
+        use std::{process::Command, thread, time::Duration};
+
+        let mut wanted = Command::new("sleep").arg("3018").spawn().expect("spawn sleep");
+        let mut unrelated = Command::new("sleep").arg("3017").spawn().expect("spawn sleep");
+        thread::sleep(Duration::from_millis(300));
+
+        let selector = ProcessSelector {
+            pid: None,
+            name: Some("sleep".to_string()),
+            executable: None,
+            cmdline_contains: Some("sleep 3018".to_string()),
+        };
+        let mut scanner = ProcessScanner::new();
+        let single = scanner.is_process_running(&selector, &[], Some(1));
+        let pair = scanner.is_process_running(&selector, &[], Some(2));
+        let _ = wanted.kill();
+        let _ = unrelated.kill();
+        let _ = wanted.wait();
+        let _ = unrelated.wait();
+
+        assert!(single.running);
+        assert!(single.matched_pids.contains(&wanted.id()));
+        assert!(!single.matched_pids.contains(&unrelated.id()));
+        assert_eq!(single.instance_count, 1);
+        assert!(!pair.running);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn close_targets_skip_same_name_processes_that_fail_the_cmdline_match() {
+        use std::{process::Command, thread, time::Duration};
+
+        let mut wanted = Command::new("sleep").arg("3028").spawn().expect("spawn sleep");
+        let mut unrelated = Command::new("sleep").arg("3027").spawn().expect("spawn sleep");
+        thread::sleep(Duration::from_millis(300));
+
+        let selector = ProcessSelector {
+            pid: None,
+            name: Some("sleep".to_string()),
+            executable: None,
+            cmdline_contains: Some("sleep 3028".to_string()),
+        };
+        let mut scanner = ProcessScanner::new();
+        let targets = scanner.close_targets(&selector);
+        for pid in &targets {
+            scanner.request_close(*pid).expect("signal target");
+        }
+        wanted.wait().expect("targeted sleep exits on SIGTERM");
+        let unrelated_survived = scanner.alive_pids(&[unrelated.id()]) == vec![unrelated.id()];
+        let _ = unrelated.kill();
+        let _ = unrelated.wait();
+
+        assert_eq!(targets, vec![wanted.id()]);
+        assert!(unrelated_survived);
+        assert!(!scanner.force_kill(wanted.id()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn request_close_terminates_cooperative_processes_and_force_kill_the_rest() {
+        use std::{process::Command, thread, time::Duration};
+
+        let mut polite = Command::new("sleep").arg("30").spawn().expect("spawn sleep");
+        let mut stubborn = Command::new("sh")
+            .args(["-c", "trap '' TERM; exec sleep 30"])
+            .spawn()
+            .expect("spawn sh");
+        thread::sleep(Duration::from_millis(300));
+
+        let mut scanner = ProcessScanner::new();
+        let pids = [polite.id(), stubborn.id()];
+        assert_eq!(scanner.alive_pids(&pids), pids.to_vec());
+        scanner.request_close(polite.id()).expect("signal sleep");
+        scanner.request_close(stubborn.id()).expect("signal sh");
+        polite.wait().expect("sleep exits on SIGTERM");
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(scanner.alive_pids(&pids), vec![stubborn.id()]);
+
+        assert!(scanner.force_kill(stubborn.id()));
+        stubborn.wait().expect("killed sh is reaped");
+        assert!(scanner.alive_pids(&pids).is_empty());
+    }
 }
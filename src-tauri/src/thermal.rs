@@ -0,0 +1,201 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use super::ThermalSensor;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ThermalReader {
+    root: PathBuf,
+}
+
+impl Default for ThermalReader {
+    fn default() -> Self {
+        Self::with_root("/sys/class")
+    }
+}
+
+impl ThermalReader {
+    pub(crate) fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    // Sensors that fail to read (some firmware zones return EIO) are left out.
+    pub(crate) fn list(&self) -> Result<Vec<ThermalSensor>, String> {
+        let mut sensors = Vec::new();
+
+        for zone in sorted_entries(&self.root.join("thermal"))? {
+            let id = file_name(&zone);
+            if !id.starts_with("thermal_zone") {
+                continue;
+            }
+            let Some(celsius) = read_millidegrees(&zone.join("temp")) else {
+                continue;
+            };
+            let label = read_trimmed(&zone.join("type")).unwrap_or_else(|| id.clone());
+            sensors.push(ThermalSensor { id, label, celsius });
+        }
+
+        for device in sorted_entries(&self.root.join("hwmon"))? {
+            let device_id = file_name(&device);
+            let device_name =
+                read_trimmed(&device.join("name")).unwrap_or_else(|| device_id.clone());
+            let Ok(entries) = fs::read_dir(&device) else {
+                continue;
+            };
+            let mut inputs = entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let input = name.strip_prefix("temp")?.strip_suffix("_input")?;
+                    input.parse::<u32>().ok()
+                })
+                .collect::<Vec<_>>();
+            inputs.sort_unstable();
+
+            for input in inputs {
+                let Some(celsius) = read_millidegrees(&device.join(format!("temp{input}_input")))
+                else {
+                    continue;
+                };
+                let channel = read_trimmed(&device.join(format!("temp{input}_label")))
+                    .unwrap_or_else(|| format!("temp{input}"));
+                sensors.push(ThermalSensor {
+                    id: format!("{device_id}/temp{input}"),
+                    label: format!("{device_name} {channel}"),
+                    celsius,
+                });
+            }
+        }
+
+        Ok(sensors)
+    }
+
+    // Matches the id first, then the label.
+    pub(crate) fn read(&self, sensor: &str) -> Result<Option<ThermalSensor>, String> {
+        let sensors = self.list()?;
+        let by_id = sensors.iter().position(|item| item.id == sensor);
+        let index = by_id.or_else(|| {
+            sensors
+                .iter()
+                .position(|item| item.label.eq_ignore_ascii_case(sensor))
+        });
+        Ok(index.map(|index| sensors[index].clone()))
+    }
+}
+
+fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(format!("failed to list {}: {error}", dir.display())),
+    };
+    let mut paths = entries.flatten().map(|entry| entry.path()).collect::<Vec<_>>();
+    // thermal_zone10 sorts after thermal_zone9.
+    paths.sort_by_key(|path| {
+        let name = file_name(path);
+        let digits = name.trim_start_matches(|item: char| !item.is_ascii_digit());
+        (name.len() - digits.len(), digits.parse::<u32>().unwrap_or(u32::MAX), name)
+    });
+    Ok(paths)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn read_millidegrees(path: &Path) -> Option<f64> {
+    read_trimmed(path)?
+        .parse::<i64>()
+        .ok()
+        .map(|millidegrees| millidegrees as f64 / 1000.0)
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::fs;
+
+    use crate::test_support::TempDir;
+
+    pub(crate) fn fake_sys_class_root(label: &str) -> TempDir {
+        TempDir::new(&format!("thermal-{label}"))
+    }
+
+    pub(crate) fn write_sensor(
+        root: &std::path::Path,
+        class: &str,
+        device: &str,
+        attributes: &[(&str, &str)],
+    ) {
+        let dir = root.join(class).join(device);
+        fs::create_dir_all(&dir).expect("create fake sensor");
+        for (attribute, value) in attributes {
+            fs::write(dir.join(attribute), format!("{value}\n")).expect("write attribute");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_support::*, *};
+
+    #[test]
+    fn reader_lists_zones_and_hwmon_inputs() {
+        let root = fake_sys_class_root("list");
+        let reader = ThermalReader::with_root(root.path());
+        assert_eq!(reader.list(), Ok(Vec::new()));
+
+        write_sensor(
+            root.path(),
+            "thermal",
+            "thermal_zone10",
+            &[("type", "iwlwifi"), ("temp", "41000")],
+        );
+        write_sensor(
+            root.path(),
+            "thermal",
+            "thermal_zone2",
+            &[("type", "x86_pkg_temp"), ("temp", "87500")],
+        );
+        write_sensor(
+            root.path(),
+            "thermal",
+            "cooling_device0",
+            &[("type", "Fan"), ("cur_state", "0")],
+        );
+        write_sensor(root.path(), "thermal", "thermal_zone3", &[("type", "acpitz")]);
+        write_sensor(
+            root.path(),
+            "hwmon",
+            "hwmon1",
+            &[
+                ("name", "coretemp"),
+                ("temp1_input", "88000"),
+                ("temp1_label", "Package id 0"),
+                ("temp2_input", "70000"),
+            ],
+        );
+
+        let sensors = reader.list().expect("list sensors");
+        let ids = sensors.iter().map(|item| item.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["thermal_zone2", "thermal_zone10", "hwmon1/temp1", "hwmon1/temp2"]);
+        assert_eq!(sensors[0].celsius, 87.5);
+        assert_eq!(sensors[2].label, "coretemp Package id 0");
+        assert_eq!(sensors[3].label, "coretemp temp2");
+
+        let by_label = reader.read("X86_PKG_TEMP").expect("read sensor").expect("sensor present");
+        assert_eq!(by_label.id, "thermal_zone2");
+        assert_eq!(reader.read("hwmon9/temp1"), Ok(None));
+    }
+}
@@ -0,0 +1,270 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UnitState {
+    pub(crate) active_state: String,
+    pub(crate) sub_state: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnitPhase {
+    Running,
+    Inactive,
+    Failed,
+}
+
+impl UnitState {
+    pub(crate) fn phase(&self) -> UnitPhase {
+        match self.active_state.as_str() {
+            "inactive" => UnitPhase::Inactive,
+            "failed" => UnitPhase::Failed,
+            _ => UnitPhase::Running,
+        }
+    }
+
+    pub(crate) fn label(&self) -> String {
+        format!("{}/{}", self.active_state, self.sub_state)
+    }
+}
+
+// `Ok(None)` once systemd has dropped a transient unit.
+#[derive(Default)]
+pub(crate) struct UnitWatcher {
+    #[cfg(target_os = "linux")]
+    connection: Option<zbus::blocking::Connection>,
+}
+
+impl std::fmt::Debug for UnitWatcher {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.debug_struct("UnitWatcher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl UnitWatcher {
+    #[cfg(test)]
+    pub(crate) fn with_connection(connection: zbus::blocking::Connection) -> Self {
+        Self {
+            connection: Some(connection),
+        }
+    }
+
+    pub(crate) fn query(&mut self, unit: &str) -> Result<Option<UnitState>, String> {
+        if self.connection.is_none() {
+            let connection = zbus::blocking::Connection::system()
+                .map_err(|error| format!("failed to connect to the system bus: {error}"))?;
+            self.connection = Some(connection);
+        }
+        let Some(connection) = self.connection.as_ref() else {
+            return Err("system bus connection unavailable".to_string());
+        };
+
+        let result = query_unit_state(connection, unit);
+        if result.is_err() {
+            // Drop the connection so the next tick reconnects instead of reusing a broken one.
+            self.connection = None;
+        }
+        result
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl UnitWatcher {
+    pub(crate) fn query(&mut self, _unit: &str) -> Result<Option<UnitState>, String> {
+        Err("systemd unit watching is only supported on Linux".to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn systemd_proxy<'a>(
+    connection: &zbus::blocking::Connection,
+    path: zbus::zvariant::OwnedObjectPath,
+    interface: &'static str,
+) -> zbus::Result<zbus::blocking::Proxy<'a>> {
+    zbus::blocking::proxy::Builder::new(connection)
+        .destination("org.freedesktop.systemd1")?
+        .path(path)?
+        .interface(interface)?
+        .cache_properties(zbus::proxy::CacheProperties::No)
+        .build()
+}
+
+#[cfg(target_os = "linux")]
+fn query_unit_state(
+    connection: &zbus::blocking::Connection,
+    unit: &str,
+) -> Result<Option<UnitState>, String> {
+    use zbus::zvariant::OwnedObjectPath;
+
+    let manager_path = OwnedObjectPath::try_from("/org/freedesktop/systemd1")
+        .map_err(|error| format!("invalid systemd manager path: {error}"))?;
+    let manager = systemd_proxy(connection, manager_path, "org.freedesktop.systemd1.Manager")
+        .map_err(|error| format!("failed to create systemd manager proxy: {error}"))?;
+
+    let unit_path = match manager.call::<_, _, OwnedObjectPath>("GetUnit", &(unit,)) {
+        Ok(path) => path,
+        Err(zbus::Error::MethodError(name, _, _))
+            if name.as_str() == "org.freedesktop.systemd1.NoSuchUnit" =>
+        {
+            return Ok(None);
+        }
+        Err(error) => return Err(format!("GetUnit({unit}) failed: {error}")),
+    };
+
+    let unit_proxy = systemd_proxy(connection, unit_path, "org.freedesktop.systemd1.Unit")
+        .map_err(|error| format!("failed to create unit proxy for {unit}: {error}"))?;
+    let active_state = unit_proxy
+        .get_property::<String>("ActiveState")
+        .map_err(|error| format!("failed to read ActiveState of {unit}: {error}"))?;
+    let sub_state = unit_proxy
+        .get_property::<String>("SubState")
+        .map_err(|error| format!("failed to read SubState of {unit}: {error}"))?;
+
+    Ok(Some(UnitState {
+        active_state,
+        sub_state,
+    }))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+pub(crate) mod test_support {
+    use std::{
+        os::unix::net::UnixStream,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use zbus::{blocking::connection::Builder, interface, zvariant::OwnedObjectPath, DBusError, Guid};
+
+    pub(crate) const FAKE_UNIT_PATH: &str = "/org/freedesktop/systemd1/unit/backup_2eservice";
+
+    #[derive(Clone)]
+    pub(crate) struct FakeUnitState(pub(crate) Arc<Mutex<Option<(String, String)>>>);
+
+    impl FakeUnitState {
+        pub(crate) fn set(&self, state: Option<(&str, &str)>) {
+            *self.0.lock().unwrap() =
+                state.map(|(active, sub)| (active.to_string(), sub.to_string()));
+        }
+
+        fn current(&self) -> Option<(String, String)> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    #[derive(Debug, DBusError)]
+    #[zbus(prefix = "org.freedesktop.systemd1")]
+    enum FakeSystemdError {
+        #[zbus(error)]
+        ZBus(zbus::Error),
+        NoSuchUnit(String),
+    }
+
+    struct FakeManager {
+        unit_name: String,
+        state: FakeUnitState,
+    }
+
+    #[interface(name = "org.freedesktop.systemd1.Manager")]
+    impl FakeManager {
+        fn get_unit(&self, name: &str) -> Result<OwnedObjectPath, FakeSystemdError> {
+            if name != self.unit_name || self.state.current().is_none() {
+                return Err(FakeSystemdError::NoSuchUnit(format!("Unit {name} not loaded.")));
+            }
+            Ok(OwnedObjectPath::try_from(FAKE_UNIT_PATH).map_err(zbus::Error::from)?)
+        }
+    }
+
+    struct FakeUnit {
+        state: FakeUnitState,
+    }
+
+    #[interface(name = "org.freedesktop.systemd1.Unit")]
+    impl FakeUnit {
+        #[zbus(property)]
+        fn active_state(&self) -> String {
+            self.state.current().map(|state| state.0).unwrap_or_default()
+        }
+
+        #[zbus(property)]
+        fn sub_state(&self) -> String {
+            self.state.current().map(|state| state.1).unwrap_or_default()
+        }
+    }
+
+    pub(crate) fn fake_systemd(
+        unit_name: &str,
+    ) -> (zbus::blocking::Connection, zbus::blocking::Connection, FakeUnitState) {
+        let (server_stream, client_stream) = UnixStream::pair().expect("socket pair");
+        let state = FakeUnitState(Arc::new(Mutex::new(None)));
+        let manager = FakeManager {
+            unit_name: unit_name.to_string(),
+            state: state.clone(),
+        };
+        let unit = FakeUnit {
+            state: state.clone(),
+        };
+
+        let server = thread::spawn(move || {
+            Builder::async_io_unix_stream(server_stream)
+                .server(Guid::generate())
+                .expect("server guid")
+                .p2p()
+                .serve_at("/org/freedesktop/systemd1", manager)
+                .expect("serve manager")
+                .serve_at(FAKE_UNIT_PATH, unit)
+                .expect("serve unit")
+                .build()
+                .expect("server connection")
+        });
+        let client = Builder::async_io_unix_stream(client_stream)
+            .p2p()
+            .build()
+            .expect("client connection");
+        let server = server.join().expect("server thread");
+        (server, client, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_phase_treats_transitional_states_as_running() {
+        let state = |active: &str| UnitState {
+            active_state: active.to_string(),
+            sub_state: "dead".to_string(),
+        };
+
+        assert_eq!(state("active").phase(), UnitPhase::Running);
+        assert_eq!(state("activating").phase(), UnitPhase::Running);
+        assert_eq!(state("deactivating").phase(), UnitPhase::Running);
+        assert_eq!(state("inactive").phase(), UnitPhase::Inactive);
+        assert_eq!(state("failed").phase(), UnitPhase::Failed);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn unit_watcher_reads_states_from_stand_in_systemd() {
+        let (_server, client, fake) = test_support::fake_systemd("backup.service");
+        let mut watcher = UnitWatcher::with_connection(client);
+
+        assert_eq!(watcher.query("backup.service").expect("query should succeed"), None);
+
+        fake.set(Some(("active", "running")));
+        assert_eq!(
+            watcher.query("backup.service").expect("query should succeed"),
+            Some(UnitState {
+                active_state: "active".to_string(),
+                sub_state: "running".to_string(),
+            })
+        );
+
+        fake.set(Some(("failed", "failed")));
+        let state = watcher
+            .query("backup.service")
+            .expect("query should succeed")
+            .expect("unit should be loaded");
+        assert_eq!(state.phase(), UnitPhase::Failed);
+        assert_eq!(state.label(), "failed/failed");
+    }
+}
@@ -1,10 +1,17 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  PostponeError,
+  PostponeErrorCode,
   ProcessInfo,
+  QueuedSchedule,
   QuitGuardAction,
+  SchedulePreset,
+  SchedulePreview,
   ScheduleRequest,
   SchedulerSnapshot,
+  ScheduleUpdate,
   SettingsUpdate,
+  ThermalSensor,
 } from "./types";
 import { mockSchedulerApi } from "./mockScheduler";
 
@@ -27,6 +34,13 @@ export async function listProcesses(): Promise<ProcessInfo[]> {
   return invoke<ProcessInfo[]>("list_processes");
 }
 
+export async function listThermalSensors(): Promise<ThermalSensor[]> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.listThermalSensors();
+  }
+  return invoke<ThermalSensor[]>("list_thermal_sensors");
+}
+
 export async function armSchedule(
   request: ScheduleRequest,
 ): Promise<SchedulerSnapshot> {
@@ -36,6 +50,13 @@ export async function armSchedule(
   return invoke<SchedulerSnapshot>("arm_schedule", { request });
 }
 
+export async function previewSchedule(request: ScheduleRequest): Promise<SchedulePreview> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.previewSchedule(request);
+  }
+  return invoke<SchedulePreview>("preview_schedule", { request });
+}
+
 export async function cancelSchedule(
   reason?: string,
 ): Promise<SchedulerSnapshot> {
@@ -45,14 +66,133 @@ export async function cancelSchedule(
   return invoke<SchedulerSnapshot>("cancel_schedule", { reason });
 }
 
+export async function enqueueSchedule(request: ScheduleRequest): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.enqueueSchedule(request);
+  }
+  return invoke<SchedulerSnapshot>("enqueue_schedule", { request });
+}
+
+export async function listScheduleQueue(): Promise<QueuedSchedule[]> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.listScheduleQueue();
+  }
+  return invoke<QueuedSchedule[]>("list_schedule_queue");
+}
+
+export async function reorderScheduleQueue(ids: string[]): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.reorderScheduleQueue(ids);
+  }
+  return invoke<SchedulerSnapshot>("reorder_schedule_queue", { ids });
+}
+
+export async function removeQueuedSchedule(id: string): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.removeQueuedSchedule(id);
+  }
+  return invoke<SchedulerSnapshot>("remove_queued_schedule", { id });
+}
+
+export async function listPresets(): Promise<SchedulePreset[]> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.listPresets();
+  }
+  return invoke<SchedulePreset[]>("list_presets");
+}
+
+export async function savePreset(
+  name: string,
+  request: ScheduleRequest,
+  id?: string,
+): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.savePreset(name, request, id);
+  }
+  return invoke<SchedulerSnapshot>("save_preset", { id, name, request });
+}
+
+export async function deletePreset(id: string): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.deletePreset(id);
+  }
+  return invoke<SchedulerSnapshot>("delete_preset", { id });
+}
+
+export async function armPreset(id: string): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.armPreset(id);
+  }
+  return invoke<SchedulerSnapshot>("arm_preset", { id });
+}
+
+export async function undoCancel(): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.undoCancel();
+  }
+  return invoke<SchedulerSnapshot>("undo_cancel");
+}
+
 export async function postponeSchedule(
   minutes: number,
   reason?: string,
 ): Promise<SchedulerSnapshot> {
   if (shouldUseMockApi) {
-    return mockSchedulerApi.postponeSchedule(minutes, reason);
+    return mockSchedulerApi.postponeSchedule(minutes, reason).catch(rethrowPostponeError);
+  }
+  return invoke<SchedulerSnapshot>("postpone_schedule", { minutes, reason }).catch(rethrowPostponeError);
+}
+
+export async function postponeScheduleUntil(
+  untilLocalTime: string,
+  reason?: string,
+): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi
+      .postponeScheduleUntil(untilLocalTime, reason)
+      .catch(rethrowPostponeError);
+  }
+  return invoke<SchedulerSnapshot>("postpone_schedule", { untilLocalTime, reason }).catch(
+    rethrowPostponeError,
+  );
+}
+
+export class PostponeRefusedError extends Error {
+  readonly code: PostponeErrorCode;
+
+  constructor(error: PostponeError) {
+    super(error.message);
+    this.name = "PostponeRefusedError";
+    this.code = error.code;
+  }
+}
+
+function rethrowPostponeError(error: unknown): never {
+  if (typeof error === "object" && error !== null && "code" in error && "message" in error) {
+    throw new PostponeRefusedError(error as PostponeError);
+  }
+  throw error;
+}
+
+export async function updateActiveSchedule(update: ScheduleUpdate): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.updateActiveSchedule(update);
+  }
+  return invoke<SchedulerSnapshot>("update_active_schedule", { update });
+}
+
+export async function pauseSchedule(reason?: string): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.pauseSchedule(reason);
+  }
+  return invoke<SchedulerSnapshot>("pause_schedule", { reason });
+}
+
+export async function resumeSchedule(reason?: string): Promise<SchedulerSnapshot> {
+  if (shouldUseMockApi) {
+    return mockSchedulerApi.resumeSchedule(reason);
   }
-  return invoke<SchedulerSnapshot>("postpone_schedule", { minutes, reason });
+  return invoke<SchedulerSnapshot>("resume_schedule", { reason });
 }
 
 export async function updateSettings(
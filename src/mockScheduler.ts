@@ -3,10 +3,15 @@ import type {
   AppSettings,
   ExecutionEvent,
   ProcessInfo,
+  QueuedSchedule,
   QuitGuardAction,
+  SchedulePreset,
+  SchedulePreview,
   ScheduleRequest,
   SchedulerSnapshot,
+  ScheduleUpdate,
   SettingsUpdate,
+  ThermalSensor,
 } from "./types";
 
 interface MockSchedulerState {
@@ -15,6 +20,9 @@ interface MockSchedulerState {
   history: ExecutionEvent[];
   nowMs: number;
   nextId: number;
+  cancelled?: { schedule: ActiveSchedule; cancelledAtMs: number };
+  queue: QueuedSchedule[];
+  presets: SchedulePreset[];
 }
 
 declare global {
@@ -27,6 +35,7 @@ const DEFAULT_SETTINGS: AppSettings = {
   defaultPreAlerts: [600, 300, 60],
   finalWarningSec: 60,
   simulateOnly: true,
+  undoCancelWindowSec: 60,
 };
 
 const MOCK_PROCESSES: ProcessInfo[] = [
@@ -40,6 +49,8 @@ const state: MockSchedulerState = {
   history: [],
   nowMs: Date.now(),
   nextId: 1,
+  queue: [],
+  presets: [],
 };
 
 function resetMockState(): void {
@@ -48,6 +59,9 @@ function resetMockState(): void {
   state.history = [];
   state.nowMs = Date.now();
   state.nextId = 1;
+  state.cancelled = undefined;
+  state.queue = [];
+  state.presets = [];
 }
 
 function buildScheduleSummary(request: ScheduleRequest): string {
@@ -88,7 +102,11 @@ function computeShutdownAtMs(active: ActiveSchedule): number | undefined {
     return active.finalWarningStartedAtMs + active.finalWarningDurationSec * 1000;
   }
 
-  if (active.mode === "processExit" || active.triggerAtMs === undefined) {
+  if (
+    active.mode === "processExit" ||
+    active.triggerAtMs === undefined ||
+    active.pausedAtMs !== undefined
+  ) {
     return undefined;
   }
 
@@ -116,6 +134,13 @@ function pushHistory(eventType: string, result: string, reason: string, schedule
   }
 }
 
+function clearMockQueue(reason: string): void {
+  if (state.queue.length > 0) {
+    pushHistory("queue_cleared", "ok", `${state.queue.length} queued schedule(s) dropped: ${reason}`);
+    state.queue = [];
+  }
+}
+
 function evaluateTransitions(): void {
   const active = state.active;
   if (!active) {
@@ -125,9 +150,20 @@ function evaluateTransitions(): void {
   if (
     active.status === "armed" &&
     active.mode !== "processExit" &&
+    active.pausedAtMs === undefined &&
     active.triggerAtMs !== undefined &&
     active.triggerAtMs <= state.nowMs
   ) {
+    const next = state.queue.find((item) => item.behindScheduleId === active.id);
+    state.queue = state.queue.filter((item) => item !== next);
+    if (next) {
+      pushHistory("completed", "ok", "MOCK: STARTING_NEXT_QUEUED", active.id);
+      state.active = createMockActive(`mock-${state.nextId++}`, next.request);
+      const nextId = state.active.id;
+      state.queue = state.queue.map((item) => ({ ...item, behindScheduleId: nextId }));
+      pushHistory("armed", "ok", `${next.summary} (from queue)`, state.active.id);
+      return;
+    }
     state.active = withComputedShutdownAt({
       ...active,
       status: "finalWarning",
@@ -164,9 +200,20 @@ function buildSnapshot(): SchedulerSnapshot {
     settings: { ...state.settings },
     history: state.history.map((item) => ({ ...item })),
     nowMs: state.nowMs,
+    undoCancelUntilMs: mockUndoCancelUntilMs(),
+    queue: state.queue.map((item) => ({ ...item })),
+    presets: state.presets.map((item) => ({ ...item })),
   };
 }
 
+function mockUndoCancelUntilMs(): number | undefined {
+  if (!state.cancelled || state.active) {
+    return undefined;
+  }
+  const untilMs = state.cancelled.cancelledAtMs + (state.settings.undoCancelWindowSec ?? 0) * 1000;
+  return state.nowMs < untilMs ? untilMs : undefined;
+}
+
 async function getSchedulerSnapshot(): Promise<SchedulerSnapshot> {
   return buildSnapshot();
 }
@@ -175,14 +222,58 @@ async function listProcesses(): Promise<ProcessInfo[]> {
   return MOCK_PROCESSES.map((item) => ({ ...item }));
 }
 
+async function listThermalSensors(): Promise<ThermalSensor[]> {
+  return [];
+}
+
 async function armSchedule(request: ScheduleRequest): Promise<SchedulerSnapshot> {
   state.nowMs = Date.now();
 
   if (state.active) {
     pushHistory("cancelled", "ok", "MOCK: REPLACED_BY_NEW_SCHEDULE", state.active.id);
   }
+  clearMockQueue("MOCK: REPLACED");
 
   const scheduleId = `mock-${state.nextId++}`;
+  state.active = createMockActive(scheduleId, request);
+  state.cancelled = undefined;
+  pushHistory("armed", "ok", "MOCK: ARMED", scheduleId);
+  return buildSnapshot();
+}
+
+async function previewSchedule(request: ScheduleRequest): Promise<SchedulePreview> {
+  state.nowMs = Date.now();
+  const planned = createMockActive("mock-preview", request);
+  const triggerAtMs = planned.triggerAtMs;
+  const warnings = state.active
+    ? [`arming replaces the active schedule: ${state.active.summary}`]
+    : [];
+  if (state.settings.simulateOnly) {
+    warnings.push("simulate-only is on; the shutdown will only be simulated");
+  }
+
+  return {
+    mode: planned.mode,
+    summary: planned.summary,
+    computedAtMs: state.nowMs,
+    triggerAtMs,
+    preAlerts:
+      triggerAtMs === undefined
+        ? []
+        : planned.preAlerts.map((thresholdSec) => ({
+            thresholdSec,
+            firesAtMs: Math.max(state.nowMs, triggerAtMs - thresholdSec * 1000),
+          })),
+    finalWarningStartsAtMs: triggerAtMs,
+    finalWarningDurationSec: planned.finalWarningDurationSec,
+    shutdownAtMs: planned.shutdownAtMs,
+    targetTzOffsetMinutes: planned.targetTzOffsetMinutes,
+    dstResolution: request.mode === "specificTime" ? "unambiguous" : undefined,
+    warnings,
+  };
+}
+
+function createMockActive(scheduleId: string, request: ScheduleRequest): ActiveSchedule {
   const triggerAtMs =
     request.mode === "countdown"
       ? state.nowMs + Math.max(1, request.durationSec ?? 60) * 1000
@@ -197,12 +288,13 @@ async function armSchedule(request: ScheduleRequest): Promise<SchedulerSnapshot>
         ? [...request.preAlerts]
         : [...state.settings.defaultPreAlerts];
 
-  state.active = {
+  return withComputedShutdownAt({
     id: scheduleId,
     mode: request.mode,
     summary: buildScheduleSummary(request),
     armedAtMs: state.nowMs,
     triggerAtMs,
+    durationSec: request.mode === "countdown" ? Math.max(1, request.durationSec ?? 60) : undefined,
     targetLocalTime: request.targetLocalTime,
     targetTzOffsetMinutes: new Date().getTimezoneOffset(),
     preAlerts,
@@ -211,22 +303,140 @@ async function armSchedule(request: ScheduleRequest): Promise<SchedulerSnapshot>
     processStableSec: Math.max(5, Math.round(request.processStableSec ?? 10)),
     status: "armed",
     finalWarningDurationSec: state.settings.finalWarningSec,
-  };
-  state.active = withComputedShutdownAt(state.active);
+    action: request.action ?? { type: "shutdown" },
+  });
+}
 
-  pushHistory("armed", "ok", "MOCK: ARMED", scheduleId);
+async function enqueueSchedule(request: ScheduleRequest): Promise<SchedulerSnapshot> {
+  state.nowMs = Date.now();
+  if (!state.active) {
+    throw new Error("no active schedule to queue behind; arm this schedule directly");
+  }
+
+  const id = `mock-queued-${state.nextId++}`;
+  state.queue.push({
+    id,
+    summary: buildScheduleSummary(request),
+    queuedAtMs: state.nowMs,
+    behindScheduleId: state.active.id,
+    request,
+  });
+  pushHistory("queued", "ok", `#${state.queue.length}: MOCK: QUEUED`, id);
+  return buildSnapshot();
+}
+
+async function listScheduleQueue(): Promise<QueuedSchedule[]> {
+  return state.queue.map((item) => ({ ...item }));
+}
+
+async function reorderScheduleQueue(ids: string[]): Promise<SchedulerSnapshot> {
+  const complete =
+    ids.length === state.queue.length && state.queue.every((item) => ids.includes(item.id));
+  if (!complete) {
+    throw new Error("reorder must list every queued schedule exactly once");
+  }
+  state.queue.sort((left, right) => ids.indexOf(left.id) - ids.indexOf(right.id));
+  return buildSnapshot();
+}
+
+async function removeQueuedSchedule(id: string): Promise<SchedulerSnapshot> {
+  const removed = state.queue.find((item) => item.id === id);
+  if (!removed) {
+    throw new Error(`no queued schedule ${id}`);
+  }
+  state.queue = state.queue.filter((item) => item.id !== id);
+  pushHistory("queue_removed", "ok", removed.summary, id);
+  return buildSnapshot();
+}
+
+async function listPresets(): Promise<SchedulePreset[]> {
+  return state.presets.map((item) => ({ ...item }));
+}
+
+async function savePreset(
+  name: string,
+  request: ScheduleRequest,
+  id?: string,
+): Promise<SchedulerSnapshot> {
+  state.nowMs = Date.now();
+  const trimmed = name.trim();
+  if (!trimmed) {
+    throw new Error("preset name is required");
+  }
+  const duplicate = state.presets.some(
+    (item) => item.id !== id && item.name.toLowerCase() === trimmed.toLowerCase(),
+  );
+  if (duplicate) {
+    throw new Error(`a preset named "${trimmed}" already exists`);
+  }
+
+  const existing = id ? state.presets.find((item) => item.id === id) : undefined;
+  if (id && !existing) {
+    throw new Error(`no preset ${id}`);
+  }
+  if (existing) {
+    existing.name = trimmed;
+    existing.request = request;
+    existing.updatedAtMs = state.nowMs;
+    pushHistory("preset_updated", "ok", `${trimmed}: MOCK: PRESET_UPDATED`);
+  } else {
+    state.presets.push({
+      id: `mock-preset-${state.nextId++}`,
+      name: trimmed,
+      request,
+      createdAtMs: state.nowMs,
+      updatedAtMs: state.nowMs,
+    });
+    pushHistory("preset_saved", "ok", `${trimmed}: MOCK: PRESET_SAVED`);
+  }
+  return buildSnapshot();
+}
+
+async function deletePreset(id: string): Promise<SchedulerSnapshot> {
+  const removed = state.presets.find((item) => item.id === id);
+  if (!removed) {
+    throw new Error(`no preset ${id}`);
+  }
+  state.presets = state.presets.filter((item) => item.id !== id);
+  pushHistory("preset_deleted", "ok", removed.name);
   return buildSnapshot();
 }
 
+async function armPreset(id: string): Promise<SchedulerSnapshot> {
+  const preset = state.presets.find((item) => item.id === id);
+  if (!preset) {
+    throw new Error(`no preset ${id}`);
+  }
+  return armSchedule(preset.request);
+}
+
 async function cancelSchedule(reason = "MOCK: USER_CANCELLED"): Promise<SchedulerSnapshot> {
   state.nowMs = Date.now();
   if (state.active) {
     pushHistory("cancelled", "ok", reason, state.active.id);
+    state.cancelled = { schedule: state.active, cancelledAtMs: state.nowMs };
     state.active = undefined;
+    clearMockQueue("MOCK: CANCELLED");
   }
   return buildSnapshot();
 }
 
+async function undoCancel(): Promise<SchedulerSnapshot> {
+  state.nowMs = Date.now();
+  if (!state.cancelled || mockUndoCancelUntilMs() === undefined) {
+    throw new Error("there is no cancelled schedule to restore");
+  }
+
+  const { schedule } = state.cancelled;
+  state.cancelled = undefined;
+  state.active = withComputedShutdownAt({
+    ...schedule,
+    finalWarningStartedAtMs: schedule.status === "finalWarning" ? state.nowMs : undefined,
+  });
+  pushHistory("cancel_undone", "ok", "MOCK: CANCEL_UNDONE", schedule.id);
+  return buildSnapshot();
+}
+
 async function postponeSchedule(minutes: number, reason = "MOCK: USER_POSTPONED"): Promise<SchedulerSnapshot> {
   state.nowMs = Date.now();
   if (!state.active) {
@@ -234,32 +444,164 @@ async function postponeSchedule(minutes: number, reason = "MOCK: USER_POSTPONED"
   }
 
   const safeMinutes = Math.min(1440, Math.max(1, Math.round(minutes)));
-  if (state.active.mode === "processExit") {
-    state.active = withComputedShutdownAt({
-      ...state.active,
-      status: "armed",
-      finalWarningStartedAtMs: undefined,
-      snoozeUntilMs: state.nowMs + safeMinutes * 60 * 1000,
-    });
-  } else {
-    state.active = withComputedShutdownAt({
-      ...state.active,
-      status: "armed",
-      finalWarningStartedAtMs: undefined,
-      triggerAtMs: state.nowMs + safeMinutes * 60 * 1000,
-    });
+  const active = state.active;
+  const base =
+    active.status !== "armed"
+      ? state.nowMs
+      : Math.max(
+          state.nowMs,
+          (active.mode === "processExit" ? active.snoozeUntilMs : active.triggerAtMs) ?? state.nowMs,
+        );
+  applyMockPostpone(base + safeMinutes * 60 * 1000, base);
+  pushHistory("postponed", "ok", reason, state.active.id);
+  return buildSnapshot();
+}
+
+async function postponeScheduleUntil(
+  untilLocalTime: string,
+  reason = "MOCK: USER_POSTPONED",
+): Promise<SchedulerSnapshot> {
+  state.nowMs = Date.now();
+  if (!state.active) {
+    return buildSnapshot();
   }
 
+  applyMockPostpone(computeSpecificTimeTrigger(untilLocalTime, state.nowMs), state.nowMs);
   pushHistory("postponed", "ok", reason, state.active.id);
   return buildSnapshot();
 }
 
+function applyMockPostpone(nextMs: number, baseMs: number): void {
+  if (!state.active) {
+    return;
+  }
+
+  const active = state.active;
+  const delaySec = Math.max(0, Math.round((nextMs - baseMs) / 1000));
+  const { maxPostponeCount, maxPostponedTotalSec } = state.settings;
+  if (maxPostponeCount && (active.postponeCount ?? 0) >= maxPostponeCount) {
+    throw { code: "countLimit", message: "MOCK: SNOOZE_LIMIT_REACHED" };
+  }
+  if (maxPostponedTotalSec && (active.postponedTotalSec ?? 0) + delaySec > maxPostponedTotalSec) {
+    throw { code: "delayLimit", message: "MOCK: SNOOZE_DELAY_LIMIT_REACHED" };
+  }
+
+  const timing =
+    active.mode === "processExit" ? { snoozeUntilMs: nextMs } : { triggerAtMs: nextMs };
+  state.active = withComputedShutdownAt({
+    ...active,
+    ...timing,
+    status: "armed",
+    finalWarningStartedAtMs: undefined,
+    pausedAtMs: undefined,
+    postponeCount: (active.postponeCount ?? 0) + 1,
+    postponedTotalSec: (active.postponedTotalSec ?? 0) + delaySec,
+  });
+}
+
+async function updateActiveSchedule(update: ScheduleUpdate): Promise<SchedulerSnapshot> {
+  state.nowMs = Date.now();
+  if (!state.active || state.active.status !== "armed") {
+    return buildSnapshot();
+  }
+
+  const previous = state.active;
+  const changes: string[] = [];
+  let next: ActiveSchedule = { ...previous };
+  if (update.durationSec !== undefined && previous.mode === "countdown") {
+    const duration = Math.max(1, update.durationSec);
+    const previousTrigger = previous.triggerAtMs ?? previous.armedAtMs;
+    const previousDuration =
+      previous.durationSec ?? Math.round((previousTrigger - previous.armedAtMs) / 1000);
+    next.durationSec = duration;
+    next.triggerAtMs = previousTrigger + (duration - previousDuration) * 1000;
+    next.firedAlerts = [];
+    changes.push(`durationSec: ${previousDuration} -> ${duration}`);
+  }
+  if (update.targetLocalTime !== undefined && previous.mode === "specificTime") {
+    next.targetLocalTime = update.targetLocalTime;
+    next.triggerAtMs = computeSpecificTimeTrigger(update.targetLocalTime, state.nowMs);
+    next.firedAlerts = [];
+    changes.push(`targetLocalTime: ${previous.targetLocalTime ?? ""} -> ${update.targetLocalTime}`);
+  }
+  if (update.preAlerts !== undefined) {
+    next.preAlerts = [...update.preAlerts];
+    next.firedAlerts = next.firedAlerts.filter((value) => next.preAlerts.includes(value));
+    changes.push(`preAlerts: [${previous.preAlerts.join(", ")}] -> [${update.preAlerts.join(", ")}]`);
+  }
+  if (update.processStableSec !== undefined) {
+    next.processStableSec = Math.max(5, Math.round(update.processStableSec));
+    changes.push(`processStableSec: ${previous.processStableSec} -> ${next.processStableSec}`);
+  }
+  if (update.processSelector !== undefined && previous.mode === "processExit") {
+    next.processSelector = update.processSelector;
+    changes.push("processSelector updated");
+  }
+
+  if (changes.length > 0) {
+    next = withComputedShutdownAt(next);
+    state.active = next;
+    pushHistory("modified", "ok", changes.join("; "), next.id);
+  }
+  return buildSnapshot();
+}
+
+async function pauseSchedule(reason = "MOCK: USER_PAUSED"): Promise<SchedulerSnapshot> {
+  state.nowMs = Date.now();
+  if (!state.active || state.active.status !== "armed" || state.active.pausedAtMs !== undefined) {
+    return buildSnapshot();
+  }
+
+  state.active = withComputedShutdownAt({ ...state.active, pausedAtMs: state.nowMs });
+  pushHistory("paused", "ok", reason, state.active.id);
+  return buildSnapshot();
+}
+
+async function resumeSchedule(reason = "MOCK: USER_RESUMED"): Promise<SchedulerSnapshot> {
+  state.nowMs = Date.now();
+  if (!state.active || state.active.pausedAtMs === undefined) {
+    return buildSnapshot();
+  }
+
+  const pausedForMs = state.nowMs - state.active.pausedAtMs;
+  state.active = withComputedShutdownAt({
+    ...state.active,
+    pausedAtMs: undefined,
+    triggerAtMs:
+      state.active.triggerAtMs === undefined ? undefined : state.active.triggerAtMs + pausedForMs,
+  });
+  pushHistory("resumed", "ok", reason, state.active.id);
+  return buildSnapshot();
+}
+
 async function updateSettings(updates: SettingsUpdate): Promise<SchedulerSnapshot> {
   state.nowMs = Date.now();
   state.settings = {
     defaultPreAlerts: updates.defaultPreAlerts ? [...updates.defaultPreAlerts] : state.settings.defaultPreAlerts,
     finalWarningSec: updates.finalWarningSec ?? state.settings.finalWarningSec,
     simulateOnly: updates.simulateOnly ?? state.settings.simulateOnly,
+    maxPostponeCount:
+      updates.maxPostponeCount === undefined
+        ? state.settings.maxPostponeCount
+        : updates.maxPostponeCount || undefined,
+    maxPostponedTotalSec:
+      updates.maxPostponedTotalSec === undefined
+        ? state.settings.maxPostponedTotalSec
+        : updates.maxPostponedTotalSec || undefined,
+    postponeDeadlineLocalTime:
+      updates.postponeDeadlineLocalTime === undefined
+        ? state.settings.postponeDeadlineLocalTime
+        : updates.postponeDeadlineLocalTime || undefined,
+    undoCancelWindowSec: updates.undoCancelWindowSec ?? state.settings.undoCancelWindowSec,
+    preShutdownHooks: updates.preShutdownHooks
+      ? updates.preShutdownHooks.map((hook) => ({ ...hook }))
+      : state.settings.preShutdownHooks,
+    gracefulClose:
+      updates.gracefulClose === undefined
+        ? state.settings.gracefulClose
+        : updates.gracefulClose.selectors.length > 0
+          ? { ...updates.gracefulClose, selectors: [...updates.gracefulClose.selectors] }
+          : undefined,
   };
 
   if (state.active) {
@@ -287,9 +629,24 @@ async function resolveQuitGuard(action: QuitGuardAction): Promise<SchedulerSnaps
 export const mockSchedulerApi = {
   getSchedulerSnapshot,
   listProcesses,
+  listThermalSensors,
   armSchedule,
+  previewSchedule,
+  enqueueSchedule,
+  listScheduleQueue,
+  reorderScheduleQueue,
+  removeQueuedSchedule,
+  listPresets,
+  savePreset,
+  deletePreset,
+  armPreset,
   cancelSchedule,
+  undoCancel,
   postponeSchedule,
+  postponeScheduleUntil,
+  updateActiveSchedule,
+  pauseSchedule,
+  resumeSchedule,
   updateSettings,
   requestAppQuit,
   resolveQuitGuard,
@@ -1,4 +1,22 @@
-export type ScheduleMode = "countdown" | "specificTime" | "processExit";
+export type ScheduleMode =
+  | "countdown"
+  | "specificTime"
+  | "processExit"
+  | "launchAndWatch"
+  | "systemdUnit"
+  | "networkIdle"
+  | "portClosed"
+  | "logPattern"
+  | "markerFile"
+  | "httpPoll"
+  | "mediaIdle"
+  | "batteryLow"
+  | "thermalHigh"
+  | "uptimeLimit";
+export type PortProtocol = "tcp" | "udp";
+export type PortProbe = "listening" | "connect";
+export type MarkerCondition = "appears" | "disappears";
+export type LaunchExitPolicy = "always" | "onSuccess" | "onFailure";
 export type ScheduleStatus = "armed" | "finalWarning";
 export type QuitGuardAction = "cancelAndQuit" | "keepBackground" | "return";
 
@@ -14,6 +32,53 @@ export interface ProcessSelector {
   cmdlineContains?: string;
 }
 
+export interface LaunchCommand {
+  program: string;
+  args?: string[];
+  workingDir?: string;
+}
+
+/** What happens once the final warning elapses; defaults to shutdown. */
+export type ScheduleAction =
+  | { type: "shutdown" }
+  | { type: "runCommand"; command: LaunchCommand; timeoutSec: number };
+
+export interface SystemdUnitWatch {
+  unit: string;
+  requireSuccess?: boolean;
+}
+
+export interface PortWatch {
+  port: number;
+  protocol?: PortProtocol;
+  probe?: PortProbe;
+}
+
+export interface LogWatch {
+  path: string;
+  pattern: string;
+  onlyNewLines?: boolean;
+}
+
+export interface MarkerWatch {
+  path: string;
+  condition?: MarkerCondition;
+}
+
+export interface HttpWatch {
+  url: string;
+  expression: string;
+}
+
+export interface MediaWatch {
+  playerFilter?: string;
+}
+
+export interface ThermalWatch {
+  sensor: string;
+  thresholdCelsius: number;
+}
+
 export interface ScheduleRequest {
   mode: ScheduleMode;
   durationSec?: number;
@@ -21,6 +86,23 @@ export interface ScheduleRequest {
   processSelector?: ProcessSelector;
   preAlerts?: number[];
   processStableSec?: number;
+  processInstanceThreshold?: number;
+  launchCommand?: LaunchCommand;
+  launchExitPolicy?: LaunchExitPolicy;
+  systemdUnit?: SystemdUnitWatch;
+  portWatch?: PortWatch;
+  logWatch?: LogWatch;
+  markerWatch?: MarkerWatch;
+  httpWatch?: HttpWatch;
+  mediaWatch?: MediaWatch;
+  batteryThresholdPercent?: number;
+  requireBatteryPower?: boolean;
+  thermalWatch?: ThermalWatch;
+  uptimeLimitSec?: number;
+  notBeforeLocalTime?: string;
+  deadlineLocalTime?: string;
+  postConditionDelaySec?: number;
+  action?: ScheduleAction;
 }
 
 export interface ActiveSchedule {
@@ -38,6 +120,42 @@ export interface ActiveSchedule {
   processTrackedPids?: number[];
   processStableSec: number;
   processMissingSinceMs?: number;
+  processInstanceThreshold?: number;
+  processInstanceCount?: number;
+  launchCommand?: LaunchCommand;
+  launchExitPolicy?: LaunchExitPolicy;
+  launchPid?: number;
+  launchExitCode?: number;
+  launchExitedAtMs?: number;
+  systemdUnit?: SystemdUnitWatch;
+  unitSeenActive?: boolean;
+  networkConnectionCount?: number;
+  portWatch?: PortWatch;
+  portOpen?: boolean;
+  logWatch?: LogWatch;
+  logMatchedLine?: string;
+  markerWatch?: MarkerWatch;
+  httpWatch?: HttpWatch;
+  mediaWatch?: MediaWatch;
+  batteryThresholdPercent?: number;
+  requireBatteryPower?: boolean;
+  batteryGuardHolding?: boolean;
+  thermalWatch?: ThermalWatch;
+  thermalPeakCelsius?: number;
+  uptimeLimitSec?: number;
+  bootTimeMs?: number;
+  durationSec?: number;
+  notBeforeMs?: number;
+  deadlineMs?: number;
+  finalWarningDeferred?: boolean;
+  postConditionDelaySec?: number;
+  postDelayStartedAtMs?: number;
+  pausedAtMs?: number;
+  postponeCount?: number;
+  postponedTotalSec?: number;
+  action?: ScheduleAction;
+  conditionMetSinceMs?: number;
+  conditionDetail?: string;
   snoozeUntilMs?: number;
   status: ScheduleStatus;
   finalWarningStartedAtMs?: number;
@@ -53,10 +171,90 @@ export interface ExecutionEvent {
   reason?: string;
 }
 
+export interface ShutdownHook {
+  name: string;
+  command: LaunchCommand;
+  timeoutSec: number;
+  abortOnFailure?: boolean;
+}
+
+export type LingeringProcessPolicy = "escalate" | "abort";
+
+export interface GracefulClose {
+  selectors: ProcessSelector[];
+  timeoutSec: number;
+  onTimeout: LingeringProcessPolicy;
+}
+
 export interface AppSettings {
   defaultPreAlerts: number[];
   finalWarningSec: number;
   simulateOnly: boolean;
+  maxPostponeCount?: number;
+  maxPostponedTotalSec?: number;
+  postponeDeadlineLocalTime?: string;
+  undoCancelWindowSec?: number;
+  preShutdownHooks?: ShutdownHook[];
+  gracefulClose?: GracefulClose;
+}
+
+export type PostponeErrorCode = "countLimit" | "delayLimit" | "deadline" | "rejected";
+
+export interface PostponeError {
+  code: PostponeErrorCode;
+  message: string;
+}
+
+export interface BatteryStatus {
+  percent: number;
+  onAc: boolean;
+  charging: boolean;
+  batteries: number;
+}
+
+export interface ThermalSensor {
+  id: string;
+  label: string;
+  celsius: number;
+}
+
+export interface QueuedSchedule {
+  id: string;
+  summary: string;
+  queuedAtMs: number;
+  behindScheduleId?: string;
+  request: ScheduleRequest;
+}
+
+export type DstResolution = "unambiguous" | "earliestOfRepeated";
+
+export interface PreAlertPreview {
+  thresholdSec: number;
+  firesAtMs: number;
+}
+
+export interface SchedulePreview {
+  mode: ScheduleMode;
+  summary: string;
+  computedAtMs: number;
+  triggerAtMs?: number;
+  preAlerts: PreAlertPreview[];
+  finalWarningStartsAtMs?: number;
+  finalWarningDurationSec: number;
+  shutdownAtMs?: number;
+  notBeforeMs?: number;
+  deadlineMs?: number;
+  targetTzOffsetMinutes?: number;
+  dstResolution?: DstResolution;
+  warnings: string[];
+}
+
+export interface SchedulePreset {
+  id: string;
+  name: string;
+  request: ScheduleRequest;
+  createdAtMs: number;
+  updatedAtMs: number;
 }
 
 export interface SchedulerSnapshot {
@@ -64,6 +262,10 @@ export interface SchedulerSnapshot {
   settings: AppSettings;
   history: ExecutionEvent[];
   nowMs: number;
+  battery?: BatteryStatus;
+  undoCancelUntilMs?: number;
+  queue?: QueuedSchedule[];
+  presets?: SchedulePreset[];
 }
 
 export interface ProcessInfo {
@@ -72,8 +274,28 @@ export interface ProcessInfo {
   executable?: string;
 }
 
+export interface ScheduleUpdate {
+  durationSec?: number;
+  targetLocalTime?: string;
+  preAlerts?: number[];
+  processStableSec?: number;
+  processSelector?: ProcessSelector;
+}
+
 export interface SettingsUpdate {
   defaultPreAlerts?: number[];
   finalWarningSec?: number;
   simulateOnly?: boolean;
+  /** `0` removes the limit. */
+  maxPostponeCount?: number;
+  /** `0` removes the limit. */
+  maxPostponedTotalSec?: number;
+  /** An empty string removes the deadline. */
+  postponeDeadlineLocalTime?: string;
+  /** `0` turns undo off. */
+  undoCancelWindowSec?: number;
+  /** Replaces the whole list; `[]` removes every hook. */
+  preShutdownHooks?: ShutdownHook[];
+  /** An empty `selectors` list turns the phase off. */
+  gracefulClose?: GracefulClose;
 }